@@ -11,6 +11,7 @@ use std::fmt::Display;
 ///   value. This [AccessType] is not allowed for bindings to scalar or vector values and will
 ///   result in an error.
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AccessType {
     Default,
     Slice(Range),
@@ -36,6 +37,7 @@ impl Display for AccessType {
 /// - `name`: is the identifier of the [ConstantBinding] or [VariableBinding] being accessed.
 /// - `access_type`: specifies the [AccessType] by which the identifier is being accessed.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SymbolAccess {
     name: Identifier,
     access_type: AccessType,
@@ -75,6 +77,7 @@ impl SymbolAccess {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Range {
     start: usize,
     end: usize,
@@ -108,6 +111,7 @@ impl Display for Range {
 /// `0..5` is an Iterable of type Range representing the range to iterate over,
 /// `z[1..6]` is an Iterable of type Slice representing the slice of the vector z to iterate over.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Iterable {
     Identifier(Identifier),
     Range(Range),
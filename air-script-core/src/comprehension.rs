@@ -5,6 +5,7 @@ use super::{Expression, Identifier, Iterable};
 pub type ComprehensionContext = Vec<(Identifier, Iterable)>;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListComprehension {
     expression: Box<Expression>,
     context: ComprehensionContext,
@@ -31,12 +32,14 @@ impl ListComprehension {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ListFolding {
     Sum(ListFoldingValueExpr),
     Prod(ListFoldingValueExpr),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ListFoldingValueExpr {
     Identifier(Identifier),
     Vector(Vec<Expression>),
@@ -2,6 +2,7 @@ use super::{ListFolding, SymbolAccess, TraceAccess};
 
 /// Arithmetic expressions for evaluation of constraints.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expression {
     Const(u64),
     /// Represents a reference to all or part of a constant, variable, or trace binding.
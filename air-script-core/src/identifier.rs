@@ -2,6 +2,7 @@ use std::fmt;
 
 /// [Identifier] is used to represent variable names.
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Identifier(pub String);
 
 impl Identifier {
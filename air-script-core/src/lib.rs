@@ -15,8 +15,14 @@ pub use expression::Expression;
 mod identifier;
 pub use identifier::Identifier;
 
+mod span;
+pub use span::SourceSpan;
+
 mod trace;
 pub use trace::{TraceAccess, TraceBinding, TraceSegment};
 
 mod variable;
 pub use variable::{VariableBinding, VariableValueExpr};
+
+#[cfg(feature = "serde")]
+pub mod schema;
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The version of the serialization schema used for the AirScript AST and `AirIR` types. This
+/// must be incremented whenever a change to any of the serializable types changes their
+/// serialized representation.
+pub const SCHEMA_VERSION: u32 = 5;
+
+// VERSIONED
+// ================================================================================================
+
+/// Wraps a serializable AirScript type together with the version of the schema it was serialized
+/// with, so that consumers can reject data produced by an incompatible version of AirScript.
+///
+/// For example, a serialized `AirIR` has the form `{ "version": 5, "data": { ... } }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    version: u32,
+    data: T,
+}
+
+impl<T> Versioned<T> {
+    /// Wraps the provided value using the current [SCHEMA_VERSION].
+    pub fn new(data: T) -> Self {
+        Self {
+            version: SCHEMA_VERSION,
+            data,
+        }
+    }
+
+    /// Returns the schema version with which the wrapped value was serialized.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Consumes this wrapper and returns the wrapped value.
+    ///
+    /// # Errors
+    /// Returns an error if the value was serialized with a schema version other than the current
+    /// [SCHEMA_VERSION].
+    pub fn into_inner(self) -> Result<T, SchemaError> {
+        if self.version != SCHEMA_VERSION {
+            return Err(SchemaError::UnsupportedVersion(self.version));
+        }
+
+        Ok(self.data)
+    }
+}
+
+// ERRORS
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// The serialized data was produced with a schema version which is not supported.
+    UnsupportedVersion(u32),
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported schema version {version}, expected version {SCHEMA_VERSION}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
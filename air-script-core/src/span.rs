@@ -0,0 +1,60 @@
+use std::ops::Range;
+
+/// [SourceSpan] is used to represent the range of byte offsets of the source from which a
+/// statement was parsed, so that errors found in the statement can be reported at its location.
+///
+/// Spans only locate nodes for diagnostics and are not part of their meaning, so all spans are
+/// equal: two nodes parsed from different sources are equal when their contents are equal.
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceSpan {
+    start: usize,
+    end: usize,
+}
+
+impl SourceSpan {
+    /// Creates a new span from the specified start and end byte offsets.
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the byte offset at which the span starts.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the byte offset at which the span ends.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the 1-based line of the specified source on which the span starts.
+    pub fn line(&self, source: &str) -> usize {
+        let start = self.start.min(source.len());
+        source.as_bytes()[..start]
+            .iter()
+            .filter(|&&byte| byte == b'\n')
+            .count()
+            + 1
+    }
+}
+
+impl From<Range<usize>> for SourceSpan {
+    fn from(range: Range<usize>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl From<SourceSpan> for Range<usize> {
+    fn from(span: SourceSpan) -> Self {
+        span.start..span.end
+    }
+}
+
+impl PartialEq for SourceSpan {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for SourceSpan {}
@@ -16,6 +16,7 @@ pub type TraceSegment = u8;
 /// For example, a single element in the "next" row of
 /// the "main" trace would be specified by a trace_segment of 0, a size of 1, and a row_offset of 1.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TraceAccess {
     trace_segment: TraceSegment,
     col_idx: usize,
@@ -64,6 +65,7 @@ impl TraceAccess {
 /// a name. For single columns, the size is 1. For groups, the size is the number of columns in the
 /// group. The offset is the column index in the trace where the first column of the binding starts.
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TraceBinding {
     binding: Identifier,
     trace_segment: TraceSegment,
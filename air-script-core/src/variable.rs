@@ -1,15 +1,32 @@
-use super::{Expression, Identifier, ListComprehension};
+use super::{Expression, Identifier, ListComprehension, SourceSpan};
 use std::fmt::Display;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VariableBinding {
     name: Identifier,
     value: VariableValueExpr,
+    span: SourceSpan,
 }
 
 impl VariableBinding {
     pub fn new(name: Identifier, value: VariableValueExpr) -> Self {
-        Self { name, value }
+        Self {
+            name,
+            value,
+            span: SourceSpan::default(),
+        }
+    }
+
+    /// Sets the span of the source from which the variable binding was parsed.
+    pub fn with_span(mut self, span: impl Into<SourceSpan>) -> Self {
+        self.span = span.into();
+        self
+    }
+
+    /// Returns the span of the source from which the variable binding was parsed.
+    pub fn span(&self) -> SourceSpan {
+        self.span
     }
 
     pub fn name(&self) -> &str {
@@ -27,6 +44,7 @@ impl VariableBinding {
 
 /// The expression or expressions that define the value of a variable binding.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VariableValueExpr {
     Scalar(Expression),
     Vector(Vec<Expression>),
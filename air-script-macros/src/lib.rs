@@ -0,0 +1,82 @@
+use codegen_winter::CodeGenerator;
+use ir::AirIR;
+use parser::{parse, Diagnostic};
+use proc_macro::TokenStream;
+use quote::quote;
+use std::{env, fs, path::PathBuf};
+use syn::{parse_macro_input, LitStr};
+
+// INCLUDE AIR
+// ================================================================================================
+
+/// Compiles the AirScript file at the specified path to Rust code targeting Winterfell and
+/// expands to the generated code. The path is resolved relative to the root of the crate being
+/// built (the directory containing its `Cargo.toml`).
+///
+/// Errors in the AirScript file are reported as compile errors which render the location of the
+/// problem in the AirScript source.
+#[proc_macro]
+pub fn include_air(input: TokenStream) -> TokenStream {
+    let path_lit = parse_macro_input!(input as LitStr);
+
+    let path = match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) => PathBuf::from(manifest_dir).join(path_lit.value()),
+        None => PathBuf::from(path_lit.value()),
+    };
+    let file_name = path.display().to_string();
+
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(err) => {
+            let message = format!("failed to read `{file_name}`: {err}");
+            return compile_error(&path_lit, &message);
+        }
+    };
+
+    let code = match compile(&source) {
+        Ok(code) => code,
+        Err(diagnostic) => {
+            // the compiler already prefixes the message with "error: "
+            let rendered = diagnostic.render(&file_name, &source);
+            let message = rendered.trim_start_matches("error: ").trim_end();
+            return compile_error(&path_lit, message);
+        }
+    };
+
+    let code: proc_macro2::TokenStream = match code.parse() {
+        Ok(code) => code,
+        Err(err) => {
+            let message = format!("generated code for `{file_name}` is not valid Rust: {err}");
+            return compile_error(&path_lit, &message);
+        }
+    };
+
+    // include the source file so that the crate is rebuilt whenever the AIR changes.
+    quote! {
+        const _: &str = include_str!(#file_name);
+        #code
+    }
+    .into()
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Compiles the provided AirScript source to Rust code targeting Winterfell.
+fn compile(source: &str) -> Result<String, Diagnostic> {
+    let parsed = parse(source).map_err(|err| Diagnostic::from(&err))?;
+    let ir = AirIR::new(parsed)
+        .map_err(|err| Diagnostic::new(err.to_string(), err.span().map(Into::into)))?;
+    CodeGenerator::new(&ir).generate().map_err(|err| {
+        let err = err.with_source_span(&ir);
+        let span = err.constraint().and_then(|location| location.span());
+        Diagnostic::new(err.to_string(), span.map(Into::into))
+    })
+}
+
+/// Returns a compile error with the provided message, reported at the span of the path literal.
+fn compile_error(path_lit: &LitStr, message: &str) -> TokenStream {
+    syn::Error::new(path_lit.span(), message)
+        .to_compile_error()
+        .into()
+}
@@ -0,0 +1,143 @@
+//! Helpers for compiling AirScript files from a crate's `build.rs` script.
+//!
+//! Example `build.rs`:
+//!
+//! ```ignore
+//! fn main() {
+//!     air_script::build::compile_air("air/vm.air").unwrap();
+//! }
+//! ```
+//!
+//! The generated Winterfell code can then be included in the crate with:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/vm.rs"));
+//! ```
+
+use super::{CompileError, Compiler, CompilerOptions};
+use std::{
+    env,
+    fmt::{Debug, Display},
+    fs,
+    path::{Path, PathBuf},
+};
+
+// BUILD HELPERS
+// ================================================================================================
+
+/// Compiles the AirScript file at the specified path to Rust code targeting Winterfell, and writes
+/// it to `$OUT_DIR/<file stem>.rs`. Relative paths are resolved against the directory of the
+/// crate being built. Cargo is instructed to re-run the build script when the file changes.
+///
+/// Returns the path of the generated file.
+///
+/// # Errors
+/// Returns an error if the file cannot be compiled. The error renders the location of the
+/// problem in the AirScript source.
+pub fn compile_air(path: impl AsRef<Path>) -> Result<PathBuf, BuildError> {
+    let paths = compile_air_with_options(path, CompilerOptions::default())?;
+    Ok(paths
+        .into_iter()
+        .next()
+        .expect("the Winterfell target was requested"))
+}
+
+/// Compiles the AirScript file at the specified path for each of the targets in the provided
+/// options, and writes the code generated for each target to `$OUT_DIR/<file stem>.<extension>`.
+/// Relative paths are resolved against the directory of the crate being built, followed by the
+/// include paths in the provided options. Cargo is instructed to re-run the build script when the
+/// file changes.
+///
+/// Returns the paths of the generated files, in the order of the targets.
+///
+/// # Errors
+/// Returns an error if the file cannot be compiled. The error renders the location of the
+/// problem in the AirScript source.
+pub fn compile_air_with_options(
+    path: impl AsRef<Path>,
+    options: CompilerOptions,
+) -> Result<Vec<PathBuf>, BuildError> {
+    let path = path.as_ref();
+    let out_dir = env::var_os("OUT_DIR")
+        .map(PathBuf::from)
+        .ok_or_else(|| BuildError::new(path, "OUT_DIR is not set; call this from build.rs"))?;
+
+    // relative paths are looked up in the directory of the crate first, and the original path is
+    // passed on to the compiler otherwise, so that it is searched for in the include paths.
+    let path = match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) if path.is_relative() => {
+            let candidate = Path::new(&manifest_dir).join(path);
+            if candidate.exists() {
+                candidate
+            } else {
+                path.to_path_buf()
+            }
+        }
+        _ => path.to_path_buf(),
+    };
+
+    let compiler = Compiler::new(options);
+    let path = compiler
+        .resolve_path(&path)
+        .map_err(|err| BuildError::new(&path, err))?;
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let source = fs::read_to_string(&path)
+        .map_err(|err| BuildError::new(&path, CompileError::Io(path.clone(), err)))?;
+    let output = compiler
+        .compile_source(&source)
+        .map_err(|err| BuildError::from_compile_error(&path, &source, &err))?;
+
+    let file_stem = path.file_stem().unwrap_or_default();
+    let mut generated_paths = Vec::new();
+    for (target, code) in output.iter() {
+        let out_path = out_dir.join(file_stem).with_extension(target.extension());
+        fs::write(&out_path, code).map_err(|err| {
+            BuildError::new(
+                &path,
+                format!("failed to write `{}`: {err}", out_path.display()),
+            )
+        })?;
+        generated_paths.push(out_path);
+    }
+
+    Ok(generated_paths)
+}
+
+// BUILD ERROR
+// ================================================================================================
+
+/// An error which occurred while compiling an AirScript file from a build script. Both the
+/// [Display] and [Debug] representations render the error against the AirScript source, so that
+/// the location of the problem is shown when the error is unwrapped in `build.rs`.
+pub struct BuildError {
+    rendered: String,
+}
+
+impl BuildError {
+    fn new(path: &Path, message: impl Display) -> Self {
+        Self {
+            rendered: format!("error: {message}\n --> {}\n", path.display()),
+        }
+    }
+
+    fn from_compile_error(path: &Path, source: &str, err: &CompileError) -> Self {
+        Self {
+            rendered: err.diagnostic().render(&path.display().to_string(), source),
+        }
+    }
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rendered)
+    }
+}
+
+impl Debug for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\n{}", self.rendered)
+    }
+}
+
+impl std::error::Error for BuildError {}
@@ -0,0 +1,70 @@
+use std::{fs, path::PathBuf};
+use structopt::StructOpt;
+
+use air_script::{Compiler, CompilerOptions, DocFormat, Specification};
+
+#[derive(StructOpt, Debug)]
+#[structopt(
+    name = "Doc",
+    about = "Export a human-readable specification of an AirScript AIR"
+)]
+pub struct DocCmd {
+    /// Path to input file
+    #[structopt(short = "i", long = "input", parse(from_os_str))]
+    input_file: Option<PathBuf>,
+    /// Path to output file, which defaults to the input file with the extension of the format
+    #[structopt(short = "o", long = "output", parse(from_os_str))]
+    output_file: Option<PathBuf>,
+    /// Format of the specification: md or latex
+    #[structopt(short = "f", long = "format", default_value = "md")]
+    format: DocFormat,
+}
+
+impl DocCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        println!("============================================================");
+        println!("Exporting the specification...");
+
+        // get the input path
+        let input_path = match &self.input_file {
+            Some(path) => path.clone(),
+            None => {
+                return Err("No input file specified".to_string());
+            }
+        };
+
+        // read the input file
+        let source = fs::read_to_string(&input_path)
+            .map_err(|err| format!("failed to read `{}`: {err}", input_path.display()))?;
+
+        // compile the input file without generating code, rendering any error against the source
+        let options = CompilerOptions::default().with_targets([]);
+        let output = Compiler::new(options)
+            .compile_source(&source)
+            .map_err(|err| {
+                err.diagnostic()
+                    .render(&input_path.display().to_string(), &source)
+            })?;
+
+        // render the specification and write it to the output path
+        let spec = Specification::new(output.ir())
+            .with_source(&source)
+            .render(self.format);
+        let output_path = match &self.output_file {
+            Some(path) => path.clone(),
+            None => input_path.with_extension(self.format.extension()),
+        };
+        if let Err(err) = fs::write(&output_path, spec) {
+            return Err(format!("{err:?}"));
+        }
+
+        println!(
+            "Success! Exported the {} specification to {}",
+            self.format,
+            output_path.display()
+        );
+        println!("============================================================");
+
+        Ok(())
+    }
+}
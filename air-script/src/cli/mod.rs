@@ -1,2 +1,4 @@
+mod doc;
 mod transpile;
+pub use doc::DocCmd;
 pub use transpile::TranspileCmd;
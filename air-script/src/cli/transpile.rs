@@ -1,23 +1,32 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 use structopt::StructOpt;
 
-use codegen_cairo0::CodeGenerator as CairoCodeGenerator;
-use codegen_winter::CodeGenerator as WinterCodeGenerator;
-use ir::AirIR;
-use parser::parse;
+use air_script::{CodeGenerator, Compiler, CompilerOptions, Target};
 
 #[derive(StructOpt, Debug)]
 #[structopt(
     name = "Transpile",
-    about = "Transpile AirScript source code to Rust targeting Winterfell"
+    about = "Transpile AirScript source code to the code of one or more targets"
 )]
 pub struct TranspileCmd {
     /// Path to input file
     #[structopt(short = "i", long = "input", parse(from_os_str))]
     input_file: Option<PathBuf>,
-    /// Path to output file
+    /// Path to output file. Its extension is replaced by the extension of each target, so that
+    /// the outputs of several targets are written next to each other
     #[structopt(short = "o", long = "output", parse(from_os_str))]
     output_file: Option<PathBuf>,
+    /// Target to generate code for: winterfell, cairo0, cairo1, masm, solidity or plonky3. May
+    /// be repeated, and defaults to winterfell and cairo0
+    #[structopt(short = "t", long = "target")]
+    targets: Vec<Target>,
+    /// Also generate the crate root of a binary which proves and verifies a trace of the AIR
+    /// with Winterfell, next to the Winterfell output
+    #[structopt(long = "prover")]
+    prover: bool,
 }
 
 impl TranspileCmd {
@@ -33,69 +42,109 @@ impl TranspileCmd {
             }
         };
 
-        // get the output path
-        let output_path = match &self.output_file {
-            Some(path) => path.clone(),
-            None => {
-                let mut path = input_path.clone();
-                path.set_extension("rs");
-                path
-            }
-        };
+        // read the input file
+        let source = fs::read_to_string(&input_path)
+            .map_err(|err| format!("failed to read `{}`: {err}", input_path.display()))?;
 
-        // transpile to cairo0
-        let output_path_cairo0 = match &self.output_file {
-            Some(path) => path.clone(),
-            None => {
-                let mut path = input_path.clone();
-                path.set_extension("cairo");
-                path
-            }
+        // get the targets, making sure that their outputs are written to different files
+        let targets = if self.targets.is_empty() {
+            vec![Target::Winterfell, Target::Cairo0]
+        } else {
+            self.targets.clone()
         };
-
-        // load source input from file
-        let source = fs::read_to_string(input_path).map_err(|err| {
-            format!(
-                "Failed to open input file `{:?}` - {}",
-                &self.input_file, err
-            )
-        })?;
-
-        // parse the input file to the internal representation
-        let parsed = parse(source.as_str());
-        if let Err(err) = parsed {
-            return Err(format!("{err:?}"));
+        for (i, target) in targets.iter().enumerate() {
+            let collision = targets[..i]
+                .iter()
+                .find(|other| other.extension() == target.extension());
+            if let Some(other) = collision {
+                return Err(format!(
+                    "the {other} and {target} outputs would both be written to a `.{}` file, \
+                    transpile them separately",
+                    target.extension()
+                ));
+            }
         }
-        let parsed = parsed.unwrap();
 
-        let ir = AirIR::new(parsed);
-        if let Err(err) = ir {
-            return Err(format!("{err:?}"));
+        if self.prover && !targets.contains(&Target::Winterfell) {
+            return Err("the prover can only be generated with the winterfell target".to_string());
         }
-        let ir = ir.unwrap();
 
-        // generate Rust code targeting Winterfell
-        let codegen_winter = WinterCodeGenerator::new(&ir);
-        let codegen_cairo0 = CairoCodeGenerator::new(&ir);
+        // compile the input file for each target, rendering any error against the source
+        let options = CompilerOptions::default().with_targets(targets);
+        let output = Compiler::new(options)
+            .compile_source(&source)
+            .map_err(|err| {
+                err.diagnostic()
+                    .render(&input_path.display().to_string(), &source)
+            })?;
 
-        // write transpiled output to the output path
-        let result = fs::write(output_path.clone(), codegen_winter.generate());
-        if let Err(err) = result {
-            return Err(format!("{err:?}"));
+        for (target, code) in output.iter() {
+            // get the output path
+            let output_path = self.output_path(&input_path, target);
+
+            // write transpiled output to the output path
+            let result = fs::write(output_path.clone(), code);
+            if let Err(err) = result {
+                return Err(format!("{err:?}"));
+            }
+
+            println!("Success! Transpiled {target} to {}", output_path.display());
         }
 
-        let result_cairo = fs::write(output_path_cairo0.clone(), codegen_cairo0.generate());
-        if let Err(err) = result_cairo {
-            return Err(format!("{err:?}"));
+        // write the prover next to the Winterfell output, as `<name>_prover.rs`
+        if self.prover {
+            let air_path = self.output_path(&input_path, Target::Winterfell);
+            let air_module = air_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .filter(|stem| is_identifier(stem))
+                .ok_or_else(|| {
+                    format!(
+                        "the name of `{}` is not a valid Rust module name",
+                        air_path.display()
+                    )
+                })?;
+            let prover_path = air_path.with_file_name(format!("{air_module}_prover.rs"));
+            let code = CodeGenerator::new(output.ir())
+                .generate_prover(air_module)
+                .map_err(|err| format!("failed to generate the Winterfell prover: {err}"))?;
+            if let Err(err) = fs::write(&prover_path, code) {
+                return Err(format!("{err:?}"));
+            }
+            println!(
+                "Success! Transpiled the Winterfell prover to {}",
+                prover_path.display()
+            );
         }
 
-        println!("Success! Transpiled Winter to {}", output_path.display());
-        println!(
-            "Success! Transpiled Cairo to {}",
-            output_path_cairo0.display()
-        );
+        // write the layout manifest next to the outputs
+        let mut manifest_path = match &self.output_file {
+            Some(path) => path.clone(),
+            None => input_path.clone(),
+        };
+        manifest_path.set_extension("manifest.json");
+        if let Err(err) = fs::write(&manifest_path, output.manifest().to_json()) {
+            return Err(format!("{err:?}"));
+        }
+        println!("Success! Wrote the manifest to {}", manifest_path.display());
         println!("============================================================");
 
         Ok(())
     }
+
+    /// Returns the path to which the output of the specified target is written: the output path,
+    /// or the input path if none was specified, with the extension of the target.
+    fn output_path(&self, input_path: &Path, target: Target) -> PathBuf {
+        self.output_file
+            .as_deref()
+            .unwrap_or(input_path)
+            .with_extension(target.extension())
+    }
+}
+
+/// Returns true if the provided name can be used as the name of a Rust module.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
 }
@@ -0,0 +1,81 @@
+use super::Target;
+use ir::{CodegenError, SemanticError};
+use parser::{Diagnostic, SourceError};
+use std::{fmt::Display, io, path::PathBuf};
+
+// COMPILE ERROR
+// ================================================================================================
+
+/// Errors which can occur while compiling an AIR with the [Compiler](super::Compiler).
+#[derive(Debug)]
+pub enum CompileError {
+    /// The source file at the specified path could not be read.
+    Io(PathBuf, io::Error),
+    /// The source file was not found at the specified path or in any of the include paths.
+    SourceNotFound(PathBuf),
+    /// The source is not syntactically valid AirScript.
+    Parse(SourceError),
+    /// The source is syntactically valid, but does not describe a valid AIR.
+    Semantic(SemanticError),
+    /// The named pass failed with the specified message.
+    Pass(String, String),
+    /// The AIR is valid, but code cannot be generated for it for the specified target.
+    Codegen(Target, CodegenError),
+}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "failed to read `{}`: {err}", path.display()),
+            Self::SourceNotFound(path) => write!(
+                f,
+                "`{}` was not found in the current directory or any include path",
+                path.display()
+            ),
+            Self::Parse(err) => write!(
+                f,
+                "failed to parse the AIR: {}",
+                Diagnostic::from(err).message()
+            ),
+            Self::Semantic(err) => write!(f, "invalid AIR: {err}"),
+            Self::Pass(name, message) => write!(f, "pass `{name}` failed: {message}"),
+            Self::Codegen(target, err) => write!(f, "failed to generate {target} code: {err}"),
+        }
+    }
+}
+
+impl CompileError {
+    /// Returns a [Diagnostic] describing this error, which can be rendered against the source
+    /// which was being compiled to show the location of the problem.
+    ///
+    /// Parse errors are located at the offending token. Semantic errors found in a statement of
+    /// the constraints sections, and code generation errors raised for a constraint, are located
+    /// at the statement. Other errors have no location.
+    pub fn diagnostic(&self) -> Diagnostic {
+        match self {
+            Self::Parse(err) => Diagnostic::from(err),
+            Self::Semantic(err) => Diagnostic::new(err.to_string(), err.span().map(Into::into)),
+            Self::Codegen(_, err) => Diagnostic::new(
+                self.to_string(),
+                err.constraint()
+                    .and_then(|location| location.span())
+                    .map(Into::into),
+            ),
+            _ => Diagnostic::new(self.to_string(), None),
+        }
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+impl From<SourceError> for CompileError {
+    fn from(err: SourceError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl From<SemanticError> for CompileError {
+    fn from(err: SemanticError) -> Self {
+        Self::Semantic(err)
+    }
+}
@@ -0,0 +1,171 @@
+use super::Manifest;
+use codegen_cairo0::CodeGenerator as Cairo0CodeGenerator;
+use codegen_cairo1::CodeGenerator as Cairo1CodeGenerator;
+use codegen_masm::CodeGenerator as MasmCodeGenerator;
+use codegen_plonky3::CodeGenerator as Plonky3CodeGenerator;
+use codegen_solidity::CodeGenerator as SolidityCodeGenerator;
+use codegen_winter::CodeGenerator as WinterfellCodeGenerator;
+use ir::AirIR;
+use parser::parse;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+mod error;
+pub use error::CompileError;
+
+mod options;
+pub use options::{CompilerOptions, Field, Pass, Target};
+
+// COMPILER
+// ================================================================================================
+
+/// Runs the full AirScript pipeline, from source to generated code for each requested [Target].
+///
+/// Example usage:
+///
+/// ```ignore
+/// let options = CompilerOptions::default().with_targets([Target::Winterfell, Target::Cairo0]);
+/// let output = Compiler::new(options).compile_file("example.air")?;
+/// let rust_code = output.get(Target::Winterfell).unwrap();
+/// ```
+#[derive(Default)]
+pub struct Compiler {
+    options: CompilerOptions,
+}
+
+impl Compiler {
+    // --- CONSTRUCTOR ----------------------------------------------------------------------------
+
+    /// Returns a new [Compiler] configured with the provided options.
+    pub fn new(options: CompilerOptions) -> Self {
+        Self { options }
+    }
+
+    // --- PUBLIC ACCESSORS -----------------------------------------------------------------------
+
+    /// Returns the options with which this compiler was configured.
+    pub fn options(&self) -> &CompilerOptions {
+        &self.options
+    }
+
+    /// Returns the path of the specified source file. Relative paths which do not exist are
+    /// resolved against each of the include paths in order.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be found.
+    pub fn resolve_path(&self, path: &Path) -> Result<PathBuf, CompileError> {
+        if path.exists() {
+            return Ok(path.to_path_buf());
+        }
+
+        if path.is_relative() {
+            for include_path in self.options.include_paths() {
+                let candidate = include_path.join(path);
+                if candidate.exists() {
+                    return Ok(candidate);
+                }
+            }
+        }
+
+        Err(CompileError::SourceNotFound(path.to_path_buf()))
+    }
+
+    // --- COMPILATION ----------------------------------------------------------------------------
+
+    /// Reads the AirScript source file at the specified path and compiles it.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be found or read, or if compiling its contents fails.
+    pub fn compile_file(&self, path: impl AsRef<Path>) -> Result<CompileOutput, CompileError> {
+        let path = self.resolve_path(path.as_ref())?;
+        let source = fs::read_to_string(&path).map_err(|err| CompileError::Io(path, err))?;
+        self.compile_source(&source)
+    }
+
+    /// Compiles the provided AirScript source.
+    ///
+    /// # Errors
+    /// Returns an error if the source cannot be parsed, does not describe a valid AIR, if any of
+    /// the passes fail, or if code cannot be generated for any of the targets.
+    pub fn compile_source(&self, source: &str) -> Result<CompileOutput, CompileError> {
+        let parsed = parse(source)?;
+        let ir = AirIR::new(parsed)?;
+        self.compile_ir(ir)
+    }
+
+    /// Applies the configured passes to the provided [AirIR] and generates code for each target.
+    ///
+    /// This can be used to generate code for an [AirIR] which was not built from AirScript
+    /// source, for example one which was deserialized.
+    ///
+    /// # Errors
+    /// Returns an error if any of the passes fail, or if code cannot be generated for any of the
+    /// targets.
+    pub fn compile_ir(&self, mut ir: AirIR) -> Result<CompileOutput, CompileError> {
+        for pass in self.options.passes() {
+            pass.run(&mut ir)
+                .map_err(|message| CompileError::Pass(pass.name().to_string(), message))?;
+        }
+
+        let mut outputs = BTreeMap::new();
+        for &target in self.options.targets() {
+            outputs.insert(target, self.generate(&ir, target)?);
+        }
+
+        Ok(CompileOutput { ir, outputs })
+    }
+
+    // --- HELPERS --------------------------------------------------------------------------------
+
+    /// Generates the code for the specified target from the provided [AirIR].
+    fn generate(&self, ir: &AirIR, target: Target) -> Result<String, CompileError> {
+        let code = match (target, self.options.field()) {
+            (Target::Winterfell, Field::Goldilocks) => WinterfellCodeGenerator::new(ir).generate(),
+            (Target::Cairo0, Field::Goldilocks) => Cairo0CodeGenerator::new(ir).generate(),
+            (Target::Cairo1, Field::Goldilocks) => Cairo1CodeGenerator::new(ir).generate(),
+            (Target::Masm, Field::Goldilocks) => MasmCodeGenerator::new(ir).generate(),
+            (Target::Solidity, Field::Goldilocks) => SolidityCodeGenerator::new(ir).generate(),
+            (Target::Plonky3, Field::Goldilocks) => Plonky3CodeGenerator::new(ir).generate(),
+        };
+        code.map_err(|err| CompileError::Codegen(target, err.with_source_span(ir)))
+    }
+}
+
+// COMPILE OUTPUT
+// ================================================================================================
+
+/// The result of a successful compilation, containing the [AirIR] and the code generated from it
+/// for each of the requested targets.
+#[derive(Debug)]
+pub struct CompileOutput {
+    ir: AirIR,
+    outputs: BTreeMap<Target, String>,
+}
+
+impl CompileOutput {
+    /// Returns the [AirIR] from which the outputs were generated.
+    pub fn ir(&self) -> &AirIR {
+        &self.ir
+    }
+
+    /// Returns the code generated for the specified target, or `None` if the target was not
+    /// requested.
+    pub fn get(&self, target: Target) -> Option<&str> {
+        self.outputs.get(&target).map(String::as_str)
+    }
+
+    /// Returns the [Manifest] describing the layout of the compiled AIR.
+    pub fn manifest(&self) -> Manifest {
+        Manifest::new(&self.ir)
+    }
+
+    /// Returns an iterator over the generated code for each requested target.
+    pub fn iter(&self) -> impl Iterator<Item = (Target, &str)> {
+        self.outputs
+            .iter()
+            .map(|(target, code)| (*target, code.as_str()))
+    }
+}
@@ -0,0 +1,182 @@
+use ir::AirIR;
+use std::{fmt::Display, path::PathBuf, str::FromStr};
+
+// TARGETS
+// ================================================================================================
+
+/// The languages and proving systems for which the [Compiler](super::Compiler) can generate
+/// constraint evaluation code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Target {
+    /// Rust code implementing the `Air` trait of the Winterfell prover.
+    Winterfell,
+    /// Cairo 0 code for evaluating the constraints in the Cairo STARK verifier.
+    Cairo0,
+    /// Cairo 1 code for evaluating the constraints in a Cairo 1 STARK verifier.
+    Cairo1,
+    /// Miden assembly procedures for evaluating the constraints in a recursive STARK verifier
+    /// running in the Miden VM.
+    Masm,
+    /// A Solidity library for evaluating the constraints in an on-chain STARK verifier.
+    Solidity,
+    /// Rust code implementing the `BaseAir` and `Air` traits of the Plonky3 proving stack.
+    Plonky3,
+}
+
+impl Target {
+    /// Returns the file extension conventionally used for code generated for this target.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Winterfell | Self::Plonky3 => "rs",
+            Self::Cairo0 | Self::Cairo1 => "cairo",
+            Self::Masm => "masm",
+            Self::Solidity => "sol",
+        }
+    }
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Winterfell => write!(f, "Winterfell"),
+            Self::Cairo0 => write!(f, "Cairo0"),
+            Self::Cairo1 => write!(f, "Cairo1"),
+            Self::Masm => write!(f, "Masm"),
+            Self::Solidity => write!(f, "Solidity"),
+            Self::Plonky3 => write!(f, "Plonky3"),
+        }
+    }
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    /// Parses the name of a target, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "winterfell" => Ok(Self::Winterfell),
+            "cairo0" => Ok(Self::Cairo0),
+            "cairo1" => Ok(Self::Cairo1),
+            "masm" => Ok(Self::Masm),
+            "solidity" => Ok(Self::Solidity),
+            "plonky3" => Ok(Self::Plonky3),
+            _ => Err(format!(
+                "unknown target `{s}`, expected one of: winterfell, cairo0, cairo1, masm, solidity, plonky3"
+            )),
+        }
+    }
+}
+
+// FIELDS
+// ================================================================================================
+
+/// The base field over which the constraints are evaluated by the generated code.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Field {
+    /// The 64-bit prime field with modulus 2^64 - 2^32 + 1.
+    #[default]
+    Goldilocks,
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Goldilocks => write!(f, "Goldilocks"),
+        }
+    }
+}
+
+// PASSES
+// ================================================================================================
+
+/// A transformation or analysis which is applied to the [AirIR] after it has been built and
+/// before any code is generated from it. Passes are run in the order in which they were added to
+/// the [CompilerOptions].
+pub trait Pass {
+    /// Returns the name of the pass, which is used to identify it in errors.
+    fn name(&self) -> &str;
+
+    /// Runs the pass against the provided [AirIR].
+    ///
+    /// # Errors
+    /// Returns a description of the problem if the pass cannot be applied to the [AirIR].
+    fn run(&self, ir: &mut AirIR) -> Result<(), String>;
+}
+
+// COMPILER OPTIONS
+// ================================================================================================
+
+/// Options which control how the [Compiler](super::Compiler) processes an AIR and which outputs it
+/// produces.
+///
+/// By default, only the [Target::Winterfell] target is generated over the [Field::Goldilocks]
+/// field, no include paths are searched, and no passes are applied.
+pub struct CompilerOptions {
+    targets: Vec<Target>,
+    field: Field,
+    include_paths: Vec<PathBuf>,
+    passes: Vec<Box<dyn Pass>>,
+}
+
+impl Default for CompilerOptions {
+    fn default() -> Self {
+        Self {
+            targets: vec![Target::Winterfell],
+            field: Field::default(),
+            include_paths: Vec::new(),
+            passes: Vec::new(),
+        }
+    }
+}
+
+impl CompilerOptions {
+    // --- BUILDERS -------------------------------------------------------------------------------
+
+    /// Replaces the targets for which code will be generated.
+    pub fn with_targets(mut self, targets: impl IntoIterator<Item = Target>) -> Self {
+        self.targets = targets.into_iter().collect();
+        self
+    }
+
+    /// Sets the base field over which constraints will be evaluated.
+    pub fn with_field(mut self, field: Field) -> Self {
+        self.field = field;
+        self
+    }
+
+    /// Adds a directory which is searched for source files that are not found at the path they
+    /// were specified with. Directories are searched in the order in which they were added.
+    pub fn with_include_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.include_paths.push(path.into());
+        self
+    }
+
+    /// Adds a pass which is applied to the [AirIR] before code generation.
+    pub fn with_pass(mut self, pass: impl Pass + 'static) -> Self {
+        self.passes.push(Box::new(pass));
+        self
+    }
+
+    // --- ACCESSORS ------------------------------------------------------------------------------
+
+    /// Returns the targets for which code will be generated.
+    pub fn targets(&self) -> &[Target] {
+        &self.targets
+    }
+
+    /// Returns the base field over which constraints will be evaluated.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// Returns the directories which are searched for source files.
+    pub fn include_paths(&self) -> &[PathBuf] {
+        &self.include_paths
+    }
+
+    /// Returns the passes which are applied to the [AirIR] before code generation.
+    pub fn passes(&self) -> &[Box<dyn Pass>] {
+        &self.passes
+    }
+}
@@ -0,0 +1,509 @@
+use ir::{
+    constraints::{ConstraintRoot, Operation},
+    AccessType, AirIR, ConstraintKind, ConstraintLocation, NodeIndex, TraceAccess, Value,
+};
+use std::{fmt::Display, str::FromStr};
+
+// FORMATS
+// ================================================================================================
+
+/// The formats in which a [Specification] can be rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocFormat {
+    /// A Markdown document.
+    Markdown,
+    /// A LaTeX fragment, which can be included in a document with `\input`.
+    Latex,
+}
+
+impl DocFormat {
+    /// Returns the file extension conventionally used for documents in this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Latex => "tex",
+        }
+    }
+}
+
+impl Display for DocFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Markdown => write!(f, "Markdown"),
+            Self::Latex => write!(f, "LaTeX"),
+        }
+    }
+}
+
+impl FromStr for DocFormat {
+    type Err = String;
+
+    /// Parses the name of a format, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "md" | "markdown" => Ok(Self::Markdown),
+            "latex" | "tex" => Ok(Self::Latex),
+            _ => Err(format!("unknown format `{s}`, expected one of: md, latex")),
+        }
+    }
+}
+
+// SPECIFICATION
+// ================================================================================================
+
+/// A human-readable specification of an AIR, for auditing its constraints as mathematical
+/// statements rather than as generated code.
+///
+/// The specification contains tables of the trace columns, public inputs, periodic columns and
+/// random values of the AIR, followed by its boundary and integrity constraints grouped by trace
+/// segment. Each constraint is written with the names of the columns and inputs it uses, and is
+/// shown with its domain, its degree and the statement of the source it was built from.
+///
+/// Evaluators are not lowered by the IR yet, so the constraints are not grouped by evaluator.
+pub struct Specification<'a> {
+    ir: &'a AirIR,
+    source: Option<&'a str>,
+}
+
+impl<'a> Specification<'a> {
+    // --- CONSTRUCTORS ---------------------------------------------------------------------------
+
+    /// Returns the specification of the provided [AirIR]. The source of each constraint is given
+    /// by the position of its statement within its section.
+    pub fn new(ir: &'a AirIR) -> Self {
+        Self { ir, source: None }
+    }
+
+    /// Uses the AirScript source from which the [AirIR] was built to give the source of each
+    /// constraint by the line of its statement, which is located by the span recorded by the
+    /// parser.
+    pub fn with_source(mut self, source: &'a str) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    // --- RENDERING ------------------------------------------------------------------------------
+
+    /// Renders the specification in the specified format.
+    pub fn render(&self, format: DocFormat) -> String {
+        let ir = self.ir;
+        let mut doc = Document::new(format);
+        doc.heading(1, ir.air_name());
+
+        // trace columns
+        doc.heading(2, "Trace columns");
+        let rows = ir
+            .trace_bindings()
+            .iter()
+            .map(|binding| {
+                vec![
+                    doc.text(segment_name(binding.trace_segment())),
+                    binding.offset().to_string(),
+                    doc.math(&doc.name(binding.name())),
+                    binding.size().to_string(),
+                ]
+            })
+            .collect();
+        doc.table(&["Segment", "Index", "Name", "Size"], rows);
+
+        // public inputs
+        if !ir.public_inputs().is_empty() {
+            doc.heading(2, "Public inputs");
+            let rows = ir
+                .public_inputs()
+                .iter()
+                .map(|(name, size)| vec![doc.math(&doc.name(name)), size.to_string()])
+                .collect();
+            doc.table(&["Name", "Size"], rows);
+        }
+
+        // periodic columns
+        if !ir.periodic_columns().is_empty() {
+            doc.heading(2, "Periodic columns");
+            let rows = ir
+                .periodic_column_names()
+                .iter()
+                .zip(ir.periodic_columns())
+                .map(|(name, values)| {
+                    let values = values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+                    vec![
+                        doc.math(&doc.name(name)),
+                        values.len().to_string(),
+                        doc.math(&values.join(", ")),
+                    ]
+                })
+                .collect();
+            doc.table(&["Name", "Cycle length", "Values"], rows);
+        }
+
+        // random values
+        if let Some(name) = ir.random_values_name() {
+            doc.heading(2, "Random values");
+            let mut rows = vec![vec![
+                doc.math(&doc.name(name)),
+                "0".to_string(),
+                ir.num_random_values().to_string(),
+            ]];
+            let mut index = 0;
+            for (name, size) in ir.random_value_bindings() {
+                rows.push(vec![
+                    doc.math(&doc.name(name)),
+                    index.to_string(),
+                    size.to_string(),
+                ]);
+                index += size;
+            }
+            doc.table(&["Name", "Index", "Size"], rows);
+        }
+
+        // constraints
+        for kind in [ConstraintKind::Boundary, ConstraintKind::Integrity] {
+            doc.heading(
+                2,
+                match kind {
+                    ConstraintKind::Boundary => "Boundary constraints",
+                    ConstraintKind::Integrity => "Integrity constraints",
+                },
+            );
+            for segment in 0..ir.trace_segment_widths().len() as u8 {
+                let constraints = match kind {
+                    ConstraintKind::Boundary => ir.boundary_constraints(segment),
+                    ConstraintKind::Integrity => ir.integrity_constraints(segment),
+                };
+                if constraints.is_empty() {
+                    continue;
+                }
+                doc.heading(3, &format!("{} trace", capitalize(segment_name(segment))));
+                let rows = constraints
+                    .iter()
+                    .enumerate()
+                    .map(|(index, constraint)| {
+                        let location = match kind {
+                            ConstraintKind::Boundary => {
+                                ConstraintLocation::boundary(segment, index)
+                            }
+                            ConstraintKind::Integrity => {
+                                ConstraintLocation::integrity(segment, index)
+                            }
+                        };
+                        self.constraint_row(&doc, &location, constraint)
+                    })
+                    .collect();
+                doc.table(&["#", "Constraint", "Domain", "Degree", "Source"], rows);
+            }
+        }
+
+        doc.finish()
+    }
+
+    // --- HELPERS --------------------------------------------------------------------------------
+
+    /// Returns the row of the constraint tables which describes the specified constraint.
+    fn constraint_row(
+        &self,
+        doc: &Document,
+        location: &ConstraintLocation,
+        constraint: &ConstraintRoot,
+    ) -> Vec<String> {
+        let graph = self.ir.constraint_graph();
+        let degree = graph.degree(constraint.node_index());
+        let degree = if degree.cycles().is_empty() {
+            degree.base().to_string()
+        } else {
+            let cycles = degree
+                .cycles()
+                .iter()
+                .map(|cycle| cycle.to_string())
+                .collect::<Vec<_>>();
+            format!("{} (cycles {})", degree.base(), cycles.join(", "))
+        };
+
+        vec![
+            location.index().to_string(),
+            doc.math(&self.equation(doc, constraint.node_index())),
+            doc.text(&constraint.domain().to_string()),
+            doc.text(&degree),
+            doc.text(&self.source(location)),
+        ]
+    }
+
+    /// Returns the constraint rooted at the specified node as an equation. Constraints are built
+    /// from the difference of the two sides of an equality, which is written as the equality.
+    fn equation(&self, doc: &Document, index: &NodeIndex) -> String {
+        match self.ir.constraint_graph().node(index).op() {
+            Operation::Sub(lhs, rhs) => {
+                format!("{} = {}", self.expr(doc, lhs, 0), self.expr(doc, rhs, 0))
+            }
+            _ => format!("{} = 0", self.expr(doc, index, 0)),
+        }
+    }
+
+    /// Returns the expression rooted at the specified node, which is parenthesized if its
+    /// precedence is lower than the specified one.
+    fn expr(&self, doc: &Document, index: &NodeIndex, precedence: usize) -> String {
+        let op = self.ir.constraint_graph().node(index).op();
+        let expr = match op {
+            Operation::Value(value) => self.value(doc, value),
+            Operation::Add(lhs, rhs) => format!(
+                "{} + {}",
+                self.expr(doc, lhs, op.precedence()),
+                self.expr(doc, rhs, op.precedence())
+            ),
+            Operation::Sub(lhs, rhs) => format!(
+                "{} - {}",
+                self.expr(doc, lhs, op.precedence()),
+                self.expr(doc, rhs, op.precedence() + 1)
+            ),
+            Operation::Mul(lhs, rhs) => format!(
+                "{} {} {}",
+                self.expr(doc, lhs, op.precedence()),
+                doc.times(),
+                self.expr(doc, rhs, op.precedence())
+            ),
+            Operation::Exp(lhs, exponent) => {
+                doc.power(&self.expr(doc, lhs, op.precedence() + 1), *exponent)
+            }
+        };
+        if op.precedence() < precedence {
+            format!("({expr})")
+        } else {
+            expr
+        }
+    }
+
+    /// Returns a leaf of the constraint graph, using the names of the columns and inputs.
+    fn value(&self, doc: &Document, value: &Value) -> String {
+        let ir = self.ir;
+        match value {
+            Value::InlineConstant(value) => value.to_string(),
+            Value::BoundConstant(symbol_access) => {
+                let name = doc.name(symbol_access.name());
+                match symbol_access.access_type() {
+                    AccessType::Default => name,
+                    AccessType::Vector(idx) => format!("{name}[{idx}]"),
+                    AccessType::Matrix(row_idx, col_idx) => format!("{name}[{row_idx}][{col_idx}]"),
+                    AccessType::Slice(range) => {
+                        format!("{name}[{}..{}]", range.start(), range.end())
+                    }
+                }
+            }
+            Value::TraceElement(trace_access) => self.trace_element(doc, trace_access),
+            Value::PeriodicColumn(idx, _) => doc.name(&ir.periodic_column_names()[*idx]),
+            Value::PublicInput(name, idx) => format!("{}[{idx}]", doc.name(name)),
+            Value::RandomValue(idx) => {
+                let mut offset = 0;
+                for (name, size) in ir.random_value_bindings() {
+                    if (offset..offset + size).contains(idx) {
+                        return bound_element(&doc.name(name), *size, idx - offset);
+                    }
+                    offset += size;
+                }
+                let name = ir.random_values_name().unwrap_or("$rand");
+                format!("{}[{idx}]", doc.name(name))
+            }
+        }
+    }
+
+    /// Returns the name of the column of a trace access, followed by a prime for each row of
+    /// offset.
+    fn trace_element(&self, doc: &Document, trace_access: &TraceAccess) -> String {
+        let col_idx = trace_access.col_idx();
+        let binding = self.ir.trace_bindings().iter().find(|binding| {
+            binding.trace_segment() == trace_access.trace_segment()
+                && (binding.offset()..binding.offset() + binding.size()).contains(&col_idx)
+        });
+        let column = match binding {
+            Some(binding) => bound_element(
+                &doc.name(binding.name()),
+                binding.size(),
+                col_idx - binding.offset(),
+            ),
+            None => {
+                let name = match trace_access.trace_segment() {
+                    0 => "$main",
+                    _ => "$aux",
+                };
+                format!("{}[{col_idx}]", doc.name(name))
+            }
+        };
+        format!("{column}{}", "'".repeat(trace_access.row_offset()))
+    }
+
+    /// Returns the source of the constraint at the specified location: the line of its statement
+    /// if the source is known, or the position of its statement within its section otherwise.
+    fn source(&self, location: &ConstraintLocation) -> String {
+        let span = self.ir.constraint_span(location);
+        if let (Some(source), Some(span)) = (self.source, span) {
+            return format!("line {}", span.line(source));
+        }
+
+        let section = match location.kind() {
+            ConstraintKind::Boundary => "boundary_constraints",
+            ConstraintKind::Integrity => "integrity_constraints",
+        };
+        match self.ir.constraint_statement(location) {
+            Some(statement) => format!("statement {} of {section}", statement + 1),
+            None => "unknown".to_string(),
+        }
+    }
+}
+
+// DOCUMENT
+// ================================================================================================
+
+/// A document which is being written in a [DocFormat].
+struct Document {
+    format: DocFormat,
+    content: String,
+}
+
+impl Document {
+    fn new(format: DocFormat) -> Self {
+        Self {
+            format,
+            content: String::new(),
+        }
+    }
+
+    /// Appends a heading of the specified level, starting from 1.
+    fn heading(&mut self, level: usize, title: &str) {
+        let heading = match self.format {
+            DocFormat::Markdown => format!("{} {title}\n\n", "#".repeat(level)),
+            DocFormat::Latex => {
+                let command = match level {
+                    1 => "section",
+                    2 => "subsection",
+                    _ => "subsubsection",
+                };
+                format!("\\{command}*{{{}}}\n\n", self.text(title))
+            }
+        };
+        self.content += &heading;
+    }
+
+    /// Appends a table with the provided headers and rows, whose cells are already formatted.
+    fn table(&mut self, headers: &[&str], rows: Vec<Vec<String>>) {
+        let headers = headers
+            .iter()
+            .map(|header| self.text(header))
+            .collect::<Vec<_>>();
+        let table = match self.format {
+            DocFormat::Markdown => {
+                let separator = headers.iter().map(|_| "---").collect::<Vec<_>>();
+                let mut table = format!("| {} |\n", headers.join(" | "));
+                table += &format!("| {} |\n", separator.join(" | "));
+                for row in rows {
+                    table += &format!("| {} |\n", row.join(" | "));
+                }
+                table
+            }
+            DocFormat::Latex => {
+                let mut table = format!(
+                    "\\begin{{tabular}}{{{}}}\n\\hline\n",
+                    "l".repeat(headers.len())
+                );
+                table += &format!("{} \\\\\n\\hline\n", headers.join(" & "));
+                for row in rows {
+                    table += &format!("{} \\\\\n", row.join(" & "));
+                }
+                table + "\\hline\n\\end{tabular}\n"
+            }
+        };
+        self.content += &table;
+        self.content += "\n";
+    }
+
+    /// Returns the document.
+    fn finish(self) -> String {
+        self.content.trim_end().to_string() + "\n"
+    }
+
+    // --- INLINE FORMATTING ----------------------------------------------------------------------
+
+    /// Returns plain text, escaped if required by the format.
+    fn text(&self, text: &str) -> String {
+        match self.format {
+            DocFormat::Markdown => text.replace('|', "\\|"),
+            DocFormat::Latex => escape_latex(text),
+        }
+    }
+
+    /// Returns a mathematical expression set in the format.
+    fn math(&self, expr: &str) -> String {
+        match self.format {
+            DocFormat::Markdown => format!("`{expr}`"),
+            DocFormat::Latex => format!("${expr}$"),
+        }
+    }
+
+    /// Returns an identifier within a mathematical expression. In LaTeX, identifiers longer than
+    /// one character are set in italics as a single word.
+    fn name(&self, name: &str) -> String {
+        match self.format {
+            DocFormat::Markdown => name.to_string(),
+            DocFormat::Latex if name.chars().count() == 1 => name.to_string(),
+            DocFormat::Latex => format!("\\mathit{{{}}}", escape_latex(name)),
+        }
+    }
+
+    /// Returns the multiplication operator.
+    fn times(&self) -> &'static str {
+        match self.format {
+            DocFormat::Markdown => "*",
+            DocFormat::Latex => "\\cdot",
+        }
+    }
+
+    /// Returns the power of a base with a constant exponent.
+    fn power(&self, base: &str, exponent: usize) -> String {
+        match self.format {
+            DocFormat::Markdown => format!("{base}^{exponent}"),
+            DocFormat::Latex => format!("{base}^{{{exponent}}}"),
+        }
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the name of the trace segment with the specified index.
+fn segment_name(segment: u8) -> &'static str {
+    match segment {
+        0 => "main",
+        _ => "aux",
+    }
+}
+
+/// Returns the provided word with its first letter in uppercase.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Returns the element at the specified index of a name bound to `size` elements, which is the
+/// name itself if it is bound to a single element.
+fn bound_element(name: &str, size: usize, idx: usize) -> String {
+    if size == 1 {
+        name.to_string()
+    } else {
+        format!("{name}[{idx}]")
+    }
+}
+
+/// Escapes the characters of the provided text which are special in LaTeX.
+fn escape_latex(text: &str) -> String {
+    let mut result = String::new();
+    for c in text.chars() {
+        match c {
+            '_' | '$' | '#' | '%' | '&' | '{' | '}' => {
+                result.push('\\');
+                result.push(c);
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
@@ -9,3 +9,36 @@ pub use ir::AirIR;
 
 /// Code generation targeting Rust for the Winterfell prover
 pub use codegen_winter::CodeGenerator;
+
+/// Code generation targeting Cairo 0 for the Cairo STARK verifier
+pub use codegen_cairo0::CodeGenerator as Cairo0CodeGenerator;
+
+/// Code generation targeting Cairo 1 for Cairo 1 STARK verifiers
+pub use codegen_cairo1::CodeGenerator as Cairo1CodeGenerator;
+
+/// Code generation targeting Miden assembly for recursive verification in the Miden VM
+pub use codegen_masm::CodeGenerator as MasmCodeGenerator;
+
+/// Code generation targeting Solidity for on-chain STARK verifiers
+pub use codegen_solidity::CodeGenerator as SolidityCodeGenerator;
+
+/// Code generation targeting the Plonky3 proving stack
+pub use codegen_plonky3::CodeGenerator as Plonky3CodeGenerator;
+
+/// Library interface for running the full compilation pipeline
+pub mod compiler;
+pub use compiler::{CompileError, CompileOutput, Compiler, CompilerOptions, Target};
+
+/// JSON manifest describing the layout of a compiled AIR
+pub mod manifest;
+pub use manifest::Manifest;
+
+/// Human-readable specification of an AIR in Markdown or LaTeX
+pub mod doc;
+pub use doc::{DocFormat, Specification};
+
+/// Helpers for compiling AIRs from a `build.rs` script
+pub mod build;
+
+/// Macro which compiles an AIR and expands to the generated Winterfell code
+pub use air_script_macros::include_air;
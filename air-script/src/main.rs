@@ -15,12 +15,14 @@ pub struct Cli {
 #[derive(StructOpt, Debug)]
 pub enum Actions {
     Transpile(cli::TranspileCmd),
+    Doc(cli::DocCmd),
 }
 
 impl Cli {
     pub fn execute(&self) -> Result<(), String> {
         match &self.action {
             Actions::Transpile(transpile) => transpile.execute(),
+            Actions::Doc(doc) => doc.execute(),
         }
     }
 }
@@ -0,0 +1,222 @@
+use ir::{AirIR, IntegrityConstraintDegree};
+use serde::{Deserialize, Serialize};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The version of the manifest format. This must be incremented whenever a change to the
+/// manifest changes its JSON representation.
+pub const MANIFEST_VERSION: u32 = 2;
+
+// MANIFEST
+// ================================================================================================
+
+/// A description of the layout of a compiled AIR, which is written as JSON next to the generated
+/// code so that provers, verifiers and test harnesses can locate columns, public inputs and random
+/// values without parsing the AirScript source.
+///
+/// For example, the manifest of an AIR with a single main trace column `a` has the form:
+///
+/// ```text
+/// {
+///   "version": 2,
+///   "air_name": "SimpleAir",
+///   "trace_segments": [
+///     {
+///       "width": 1,
+///       "columns": [{ "name": "a", "index": 0, "size": 1 }],
+///       "num_boundary_constraints": 1,
+///       "integrity_constraint_degrees": [{ "base": 1, "cycles": [] }]
+///     }
+///   ],
+///   "num_transition_exemptions": 1,
+///   ...
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The version of the manifest format, which is [MANIFEST_VERSION].
+    pub version: u32,
+    /// The name of the AIR.
+    pub air_name: String,
+    /// The trace segments, starting with the main segment.
+    pub trace_segments: Vec<TraceSegmentLayout>,
+    /// The number of rows at the end of the trace over which the transition constraints are not
+    /// enforced.
+    pub num_transition_exemptions: usize,
+    /// The public inputs, in declaration order.
+    pub public_inputs: Vec<PublicInputLayout>,
+    /// The random values used by the auxiliary trace segments, if any were declared.
+    pub random_values: Option<RandomValuesLayout>,
+    /// The periodic columns, in declaration order.
+    pub periodic_columns: Vec<PeriodicColumnLayout>,
+}
+
+/// The columns and constraints of one trace segment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceSegmentLayout {
+    /// The number of columns in the segment.
+    pub width: usize,
+    /// The named columns and groups of columns of the segment, in declaration order.
+    pub columns: Vec<Binding>,
+    /// The number of boundary constraints against the segment.
+    pub num_boundary_constraints: usize,
+    /// The degree of each integrity constraint against the segment, in order.
+    pub integrity_constraint_degrees: Vec<ConstraintDegree>,
+}
+
+/// A name bound to `size` consecutive elements, starting at `index`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Binding {
+    pub name: String,
+    pub index: usize,
+    pub size: usize,
+}
+
+/// A public input and the number of elements it holds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicInputLayout {
+    pub name: String,
+    pub size: usize,
+}
+
+/// The random values array and the names bound to its elements.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RandomValuesLayout {
+    /// The name of the random values array.
+    pub name: String,
+    /// The number of random values.
+    pub size: usize,
+    /// The random values bound to a name, in order.
+    pub bindings: Vec<Binding>,
+}
+
+/// A periodic column and the length of its cycle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeriodicColumnLayout {
+    pub name: String,
+    pub index: usize,
+    pub cycle_length: usize,
+}
+
+/// The degree of an integrity constraint, as in Winterfell's `TransitionConstraintDegree`: the
+/// base degree in the trace columns and the cycle lengths of the periodic columns it uses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstraintDegree {
+    pub base: usize,
+    pub cycles: Vec<usize>,
+}
+
+impl Manifest {
+    // --- CONSTRUCTOR ----------------------------------------------------------------------------
+
+    /// Returns the manifest describing the layout of the provided [AirIR].
+    pub fn new(ir: &AirIR) -> Self {
+        let trace_segments = ir
+            .trace_segment_widths()
+            .iter()
+            .enumerate()
+            .map(|(segment, &width)| {
+                let segment = segment as u8;
+                let columns = ir
+                    .trace_bindings()
+                    .iter()
+                    .filter(|binding| binding.trace_segment() == segment)
+                    .map(|binding| Binding {
+                        name: binding.name().to_string(),
+                        index: binding.offset(),
+                        size: binding.size(),
+                    })
+                    .collect();
+                let integrity_constraint_degrees = ir
+                    .integrity_constraint_degrees(segment)
+                    .iter()
+                    .map(ConstraintDegree::from)
+                    .collect();
+                TraceSegmentLayout {
+                    width: width as usize,
+                    columns,
+                    num_boundary_constraints: ir.num_boundary_constraints(segment),
+                    integrity_constraint_degrees,
+                }
+            })
+            .collect();
+
+        let public_inputs = ir
+            .public_inputs()
+            .iter()
+            .map(|(name, size)| PublicInputLayout {
+                name: name.clone(),
+                size: *size,
+            })
+            .collect();
+
+        let random_values = ir.random_values_name().map(|name| {
+            let mut index = 0;
+            let bindings = ir
+                .random_value_bindings()
+                .iter()
+                .map(|(name, size)| {
+                    let binding = Binding {
+                        name: name.clone(),
+                        index,
+                        size: *size,
+                    };
+                    index += size;
+                    binding
+                })
+                .collect();
+            RandomValuesLayout {
+                name: name.to_string(),
+                size: ir.num_random_values() as usize,
+                bindings,
+            }
+        });
+
+        let periodic_columns = ir
+            .periodic_column_names()
+            .iter()
+            .zip(ir.periodic_columns())
+            .enumerate()
+            .map(|(index, (name, values))| PeriodicColumnLayout {
+                name: name.clone(),
+                index,
+                cycle_length: values.len(),
+            })
+            .collect();
+
+        Self {
+            version: MANIFEST_VERSION,
+            air_name: ir.air_name().to_string(),
+            trace_segments,
+            num_transition_exemptions: ir.num_transition_exemptions(),
+            public_inputs,
+            random_values,
+            periodic_columns,
+        }
+    }
+
+    // --- SERIALIZATION --------------------------------------------------------------------------
+
+    /// Returns the JSON representation of this manifest.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a manifest can always be serialized")
+    }
+
+    /// Reads a manifest from its JSON representation.
+    ///
+    /// # Errors
+    /// Returns an error if the JSON does not describe a manifest.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl From<&IntegrityConstraintDegree> for ConstraintDegree {
+    fn from(degree: &IntegrityConstraintDegree) -> Self {
+        Self {
+            base: degree.base(),
+            cycles: degree.cycles().to_vec(),
+        }
+    }
+}
@@ -1,17 +1,102 @@
 use winter_air::{Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, ProofOptions as WinterProofOptions, TransitionConstraintDegree, TraceInfo};
 use winter_math::fields::f64::BaseElement as Felt;
-use winter_math::{ExtensionOf, FieldElement};
+use winter_math::{ExtensionOf, FieldElement, ToElements};
 use winter_utils::collections::Vec;
-use winter_utils::{ByteWriter, Serializable};
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
+pub mod columns {
+    //! The indices of the columns of the execution trace, by their declared names.
+
+    /// The number of columns of the main trace.
+    pub const MAIN_WIDTH: usize = 3;
+    /// The number of columns of the auxiliary trace.
+    pub const AUX_WIDTH: usize = 2;
+
+    /// The index of the `a` column of the main trace.
+    pub const A: usize = 0;
+
+    /// The index of the `b` column of the main trace.
+    pub const B: usize = 1;
+
+    /// The index of the `c` column of the main trace.
+    pub const C: usize = 2;
+
+    /// The index of the `p0` column of the auxiliary trace.
+    pub const P0: usize = 0;
+
+    /// The index of the `p1` column of the auxiliary trace.
+    pub const P1: usize = 1;
+}
+
+/// A row of the main trace, whose columns are set by their declared names.
+#[derive(Debug, Clone, Copy)]
+pub struct MainTraceRow {
+    values: [Felt; columns::MAIN_WIDTH],
+}
+
+impl MainTraceRow {
+    /// Returns a row whose columns are all zero.
+    pub fn new() -> Self {
+        Self {
+            values: [Felt::ZERO; columns::MAIN_WIDTH],
+        }
+    }
+
+    /// Sets the value of the `a` column.
+    pub fn set_a(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::A] = value;
+        self
+    }
+
+    /// Sets the value of the `b` column.
+    pub fn set_b(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::B] = value;
+        self
+    }
+
+    /// Sets the value of the `c` column.
+    pub fn set_c(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::C] = value;
+        self
+    }
+
+    /// Returns the values of the columns of the row, in the order of the main trace.
+    pub fn values(&self) -> &[Felt; columns::MAIN_WIDTH] {
+        &self.values
+    }
+}
+
+impl Default for MainTraceRow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The public inputs of the AIR, laid out as elements in the order of declaration.
 pub struct PublicInputs {
     stack_inputs: [Felt; 16],
 }
 
 impl PublicInputs {
+    pub const NUM_ELEMENTS: usize = 16;
     pub fn new(stack_inputs: [Felt; 16]) -> Self {
         Self { stack_inputs }
     }
+
+    /// Returns the public inputs laid out in the provided elements, or an error if the
+    /// number of elements does not match the sizes of the public inputs.
+    pub fn from_elements(elements: &[Felt]) -> Result<Self, String> {
+        if elements.len() != Self::NUM_ELEMENTS {
+            return Err(format!(
+                "expected {} public input elements, found {}",
+                Self::NUM_ELEMENTS,
+                elements.len()
+            ));
+        }
+        Ok(Self {
+            stack_inputs: elements[0..16].try_into().unwrap(),
+        })
+    }
 }
 
 impl Serializable for PublicInputs {
@@ -20,6 +105,23 @@ impl Serializable for PublicInputs {
     }
 }
 
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let elements = (0..Self::NUM_ELEMENTS)
+            .map(|_| Felt::read_from(source))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_elements(&elements).map_err(DeserializationError::InvalidValue)
+    }
+}
+
+impl ToElements<Felt> for PublicInputs {
+    fn to_elements(&self) -> Vec<Felt> {
+        let mut result = Vec::new();
+        result.extend_from_slice(&self.stack_inputs);
+        result
+    }
+}
+
 pub struct AuxiliaryAir {
     context: AirContext<Felt>,
     stack_inputs: [Felt; 16],
@@ -53,7 +155,7 @@ impl Air for AuxiliaryAir {
             num_aux_assertions,
             options,
         )
-        .set_num_transition_exemptions(2);
+        .set_num_transition_exemptions(1);
         Self { context, stack_inputs: public_inputs.stack_inputs }
     }
 
@@ -62,18 +164,19 @@ impl Air for AuxiliaryAir {
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Felt>> {
+        type E = Felt;
         let mut result = Vec::new();
-        result.push(Assertion::single(0, 0, Felt::ONE));
-        result.push(Assertion::single(1, 0, Felt::ONE));
+        result.push(Assertion::<E>::single(0, 0, Felt::ONE.into()));
+        result.push(Assertion::<E>::single(1, 0, Felt::ONE.into()));
         result
     }
 
     fn get_aux_assertions<E: FieldElement<BaseField = Felt>>(&self, aux_rand_elements: &AuxTraceRandElements<E>) -> Vec<Assertion<E>> {
         let mut result = Vec::new();
-        result.push(Assertion::single(0, 0, E::ONE));
-        result.push(Assertion::single(0, self.last_step(), E::ONE));
-        result.push(Assertion::single(1, 0, aux_rand_elements.get_segment_elements(0)[0]));
-        result.push(Assertion::single(1, self.last_step(), E::ONE));
+        result.push(Assertion::<E>::single(0, 0, E::ONE.into()));
+        result.push(Assertion::<E>::single(0, self.last_step(), E::ONE.into()));
+        result.push(Assertion::<E>::single(1, 0, aux_rand_elements.get_segment_elements(0)[0].into()));
+        result.push(Assertion::<E>::single(1, self.last_step(), E::ONE.into()));
         result
     }
 
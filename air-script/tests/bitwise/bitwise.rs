@@ -1,17 +1,193 @@
 use winter_air::{Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, ProofOptions as WinterProofOptions, TransitionConstraintDegree, TraceInfo};
 use winter_math::fields::f64::BaseElement as Felt;
-use winter_math::{ExtensionOf, FieldElement};
+use winter_math::{ExtensionOf, FieldElement, ToElements};
 use winter_utils::collections::Vec;
-use winter_utils::{ByteWriter, Serializable};
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
+pub mod columns {
+    //! The indices of the columns of the execution trace, by their declared names.
+
+    /// The number of columns of the main trace.
+    pub const MAIN_WIDTH: usize = 14;
+
+    /// The index of the `s` column of the main trace.
+    pub const S: usize = 0;
+
+    /// The index of the `a` column of the main trace.
+    pub const A: usize = 1;
+
+    /// The index of the `b` column of the main trace.
+    pub const B: usize = 2;
+
+    /// The index of the `a0` column of the main trace.
+    pub const A0: usize = 3;
+
+    /// The index of the `a1` column of the main trace.
+    pub const A1: usize = 4;
+
+    /// The index of the `a2` column of the main trace.
+    pub const A2: usize = 5;
+
+    /// The index of the `a3` column of the main trace.
+    pub const A3: usize = 6;
+
+    /// The index of the `b0` column of the main trace.
+    pub const B0: usize = 7;
+
+    /// The index of the `b1` column of the main trace.
+    pub const B1: usize = 8;
+
+    /// The index of the `b2` column of the main trace.
+    pub const B2: usize = 9;
+
+    /// The index of the `b3` column of the main trace.
+    pub const B3: usize = 10;
+
+    /// The index of the `zp` column of the main trace.
+    pub const ZP: usize = 11;
+
+    /// The index of the `z` column of the main trace.
+    pub const Z: usize = 12;
+
+    /// The index of the `dummy` column of the main trace.
+    pub const DUMMY: usize = 13;
+}
+
+/// A row of the main trace, whose columns are set by their declared names.
+#[derive(Debug, Clone, Copy)]
+pub struct MainTraceRow {
+    values: [Felt; columns::MAIN_WIDTH],
+}
+
+impl MainTraceRow {
+    /// Returns a row whose columns are all zero.
+    pub fn new() -> Self {
+        Self {
+            values: [Felt::ZERO; columns::MAIN_WIDTH],
+        }
+    }
+
+    /// Sets the value of the `s` column.
+    pub fn set_s(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::S] = value;
+        self
+    }
+
+    /// Sets the value of the `a` column.
+    pub fn set_a(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::A] = value;
+        self
+    }
+
+    /// Sets the value of the `b` column.
+    pub fn set_b(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::B] = value;
+        self
+    }
+
+    /// Sets the value of the `a0` column.
+    pub fn set_a0(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::A0] = value;
+        self
+    }
+
+    /// Sets the value of the `a1` column.
+    pub fn set_a1(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::A1] = value;
+        self
+    }
+
+    /// Sets the value of the `a2` column.
+    pub fn set_a2(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::A2] = value;
+        self
+    }
+
+    /// Sets the value of the `a3` column.
+    pub fn set_a3(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::A3] = value;
+        self
+    }
+
+    /// Sets the value of the `b0` column.
+    pub fn set_b0(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::B0] = value;
+        self
+    }
+
+    /// Sets the value of the `b1` column.
+    pub fn set_b1(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::B1] = value;
+        self
+    }
+
+    /// Sets the value of the `b2` column.
+    pub fn set_b2(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::B2] = value;
+        self
+    }
+
+    /// Sets the value of the `b3` column.
+    pub fn set_b3(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::B3] = value;
+        self
+    }
+
+    /// Sets the value of the `zp` column.
+    pub fn set_zp(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::ZP] = value;
+        self
+    }
+
+    /// Sets the value of the `z` column.
+    pub fn set_z(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::Z] = value;
+        self
+    }
+
+    /// Sets the value of the `dummy` column.
+    pub fn set_dummy(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::DUMMY] = value;
+        self
+    }
+
+    /// Returns the values of the columns of the row, in the order of the main trace.
+    pub fn values(&self) -> &[Felt; columns::MAIN_WIDTH] {
+        &self.values
+    }
+}
+
+impl Default for MainTraceRow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The public inputs of the AIR, laid out as elements in the order of declaration.
 pub struct PublicInputs {
     stack_inputs: [Felt; 16],
 }
 
 impl PublicInputs {
+    pub const NUM_ELEMENTS: usize = 16;
     pub fn new(stack_inputs: [Felt; 16]) -> Self {
         Self { stack_inputs }
     }
+
+    /// Returns the public inputs laid out in the provided elements, or an error if the
+    /// number of elements does not match the sizes of the public inputs.
+    pub fn from_elements(elements: &[Felt]) -> Result<Self, String> {
+        if elements.len() != Self::NUM_ELEMENTS {
+            return Err(format!(
+                "expected {} public input elements, found {}",
+                Self::NUM_ELEMENTS,
+                elements.len()
+            ));
+        }
+        Ok(Self {
+            stack_inputs: elements[0..16].try_into().unwrap(),
+        })
+    }
 }
 
 impl Serializable for PublicInputs {
@@ -20,6 +196,23 @@ impl Serializable for PublicInputs {
     }
 }
 
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let elements = (0..Self::NUM_ELEMENTS)
+            .map(|_| Felt::read_from(source))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_elements(&elements).map_err(DeserializationError::InvalidValue)
+    }
+}
+
+impl ToElements<Felt> for PublicInputs {
+    fn to_elements(&self) -> Vec<Felt> {
+        let mut result = Vec::new();
+        result.extend_from_slice(&self.stack_inputs);
+        result
+    }
+}
+
 pub struct BitwiseAir {
     context: AirContext<Felt>,
     stack_inputs: [Felt; 16],
@@ -53,7 +246,7 @@ impl Air for BitwiseAir {
             num_aux_assertions,
             options,
         )
-        .set_num_transition_exemptions(2);
+        .set_num_transition_exemptions(1);
         Self { context, stack_inputs: public_inputs.stack_inputs }
     }
 
@@ -62,8 +255,9 @@ impl Air for BitwiseAir {
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Felt>> {
+        type E = Felt;
         let mut result = Vec::new();
-        result.push(Assertion::single(13, 0, Felt::ZERO));
+        result.push(Assertion::<E>::single(13, 0, Felt::ZERO.into()));
         result
     }
 
@@ -75,6 +269,17 @@ impl Air for BitwiseAir {
     fn evaluate_transition<E: FieldElement<BaseField = Felt>>(&self, frame: &EvaluationFrame<E>, periodic_values: &[E], result: &mut [E]) {
         let main_current = frame.current();
         let main_next = frame.next();
+        let t0 = E::ONE * main_current[3];
+        let t1 = E::from(2_u64) * main_current[4];
+        let t2 = E::from(2_u64).exp(E::PositiveInteger::from(2_u64));
+        let t3 = t2 * main_current[5];
+        let t4 = E::from(2_u64).exp(E::PositiveInteger::from(3_u64));
+        let t5 = t4 * main_current[6];
+        let t6 = E::ONE * main_current[7];
+        let t7 = E::from(2_u64) * main_current[8];
+        let t8 = t2 * main_current[9];
+        let t9 = t4 * main_current[10];
+        let t10 = main_current[11] * E::from(16_u64);
         result[0] = main_current[0].exp(E::PositiveInteger::from(2_u64)) - main_current[0] - E::ZERO;
         result[1] = periodic_values[1] * (main_next[0] - main_current[0]) - E::ZERO;
         result[2] = main_current[3].exp(E::PositiveInteger::from(2_u64)) - main_current[3] - E::ZERO;
@@ -85,13 +290,13 @@ impl Air for BitwiseAir {
         result[7] = main_current[8].exp(E::PositiveInteger::from(2_u64)) - main_current[8] - E::ZERO;
         result[8] = main_current[9].exp(E::PositiveInteger::from(2_u64)) - main_current[9] - E::ZERO;
         result[9] = main_current[10].exp(E::PositiveInteger::from(2_u64)) - main_current[10] - E::ZERO;
-        result[10] = periodic_values[0] * (main_current[1] - (E::ONE * main_current[3] + E::from(2_u64) * main_current[4] + E::from(2_u64).exp(E::PositiveInteger::from(2_u64)) * main_current[5] + E::from(2_u64).exp(E::PositiveInteger::from(3_u64)) * main_current[6])) - E::ZERO;
-        result[11] = periodic_values[0] * (main_current[2] - (E::ONE * main_current[7] + E::from(2_u64) * main_current[8] + E::from(2_u64).exp(E::PositiveInteger::from(2_u64)) * main_current[9] + E::from(2_u64).exp(E::PositiveInteger::from(3_u64)) * main_current[10])) - E::ZERO;
-        result[12] = periodic_values[1] * (main_next[1] - (main_current[1] * E::from(16_u64) + E::ONE * main_current[3] + E::from(2_u64) * main_current[4] + E::from(2_u64).exp(E::PositiveInteger::from(2_u64)) * main_current[5] + E::from(2_u64).exp(E::PositiveInteger::from(3_u64)) * main_current[6])) - E::ZERO;
-        result[13] = periodic_values[1] * (main_next[2] - (main_current[2] * E::from(16_u64) + E::ONE * main_current[7] + E::from(2_u64) * main_current[8] + E::from(2_u64).exp(E::PositiveInteger::from(2_u64)) * main_current[9] + E::from(2_u64).exp(E::PositiveInteger::from(3_u64)) * main_current[10])) - E::ZERO;
+        result[10] = periodic_values[0] * (main_current[1] - (t0 + t1 + t3 + t5)) - E::ZERO;
+        result[11] = periodic_values[0] * (main_current[2] - (t6 + t7 + t8 + t9)) - E::ZERO;
+        result[12] = periodic_values[1] * (main_next[1] - (main_current[1] * E::from(16_u64) + t0 + t1 + t3 + t5)) - E::ZERO;
+        result[13] = periodic_values[1] * (main_next[2] - (main_current[2] * E::from(16_u64) + t6 + t7 + t8 + t9)) - E::ZERO;
         result[14] = periodic_values[0] * main_current[11] - E::ZERO;
         result[15] = periodic_values[1] * (main_current[12] - main_next[11]) - E::ZERO;
-        result[16] = (E::ONE - main_current[0]) * (main_current[12] - (main_current[11] * E::from(16_u64) + E::ONE * main_current[3] * main_current[7] + E::from(2_u64) * main_current[4] * main_current[8] + E::from(2_u64).exp(E::PositiveInteger::from(2_u64)) * main_current[5] * main_current[9] + E::from(2_u64).exp(E::PositiveInteger::from(3_u64)) * main_current[6] * main_current[10])) + main_current[0] * (main_current[12] - (main_current[11] * E::from(16_u64) + E::ONE * (main_current[3] + main_current[7] - E::from(2_u64) * main_current[3] * main_current[7]) + E::from(2_u64) * (main_current[4] + main_current[8] - E::from(2_u64) * main_current[4] * main_current[8]) + E::from(2_u64).exp(E::PositiveInteger::from(2_u64)) * (main_current[5] + main_current[9] - E::from(2_u64) * main_current[5] * main_current[9]) + E::from(2_u64).exp(E::PositiveInteger::from(3_u64)) * (main_current[6] + main_current[10] - E::from(2_u64) * main_current[6] * main_current[10]))) - E::ZERO;
+        result[16] = (E::ONE - main_current[0]) * (main_current[12] - (t10 + t0 * main_current[7] + t1 * main_current[8] + t3 * main_current[9] + t5 * main_current[10])) + main_current[0] * (main_current[12] - (t10 + E::ONE * (main_current[3] + main_current[7] - E::from(2_u64) * main_current[3] * main_current[7]) + E::from(2_u64) * (main_current[4] + main_current[8] - E::from(2_u64) * main_current[4] * main_current[8]) + t2 * (main_current[5] + main_current[9] - E::from(2_u64) * main_current[5] * main_current[9]) + t4 * (main_current[6] + main_current[10] - E::from(2_u64) * main_current[6] * main_current[10]))) - E::ZERO;
     }
 
     fn evaluate_aux_transition<F, E>(&self, main_frame: &EvaluationFrame<F>, aux_frame: &EvaluationFrame<E>, _periodic_values: &[F], aux_rand_elements: &AuxTraceRandElements<E>, result: &mut [E])
@@ -0,0 +1,440 @@
+//! A minimal interpreter for the subset of Cairo 0 emitted by the Cairo0 code generator. It is
+//! used to check the generated code against values computed by Winterfell, since a Cairo runner
+//! is not available when running `cargo test`.
+//!
+//! Field arithmetic, including native felt arithmetic, is performed in the Goldilocks field, and
+//! the functions of `math_goldilocks.cairo` are implemented natively. The functions of
+//! `ext2.cairo` are interpreted from its source, except for `pow_e`, whose loop relies on
+//! conditionals.
+
+use std::collections::BTreeMap;
+use winter_math::{
+    fields::{f64::BaseElement as Felt, QuadExtension},
+    FieldElement, StarkField,
+};
+
+// VALUES
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Val {
+    Felt(Felt),
+    /// A pointer to the specified offset of a memory segment.
+    Ptr(usize, usize),
+    /// An instance of the named struct.
+    Struct(String, Vec<Val>),
+}
+
+impl Val {
+    pub fn felt(&self) -> Felt {
+        match self {
+            Val::Felt(value) => *value,
+            _ => panic!("expected a felt, found {self:?}"),
+        }
+    }
+
+    /// Returns an `Ext2` struct holding the specified element of the quadratic extension.
+    pub fn ext2(value: QuadExtension<Felt>) -> Self {
+        let values = QuadExtension::<Felt>::as_base_elements(&[value])
+            .iter()
+            .map(|v| Val::Felt(*v))
+            .collect();
+        Val::Struct("Ext2".to_string(), values)
+    }
+
+    fn as_ext2(&self) -> QuadExtension<Felt> {
+        match self {
+            Val::Struct(name, values) if name == "Ext2" => {
+                QuadExtension::new(values[0].felt(), values[1].felt())
+            }
+            _ => panic!("expected an Ext2, found {self:?}"),
+        }
+    }
+}
+
+// INTERPRETER
+// ================================================================================================
+
+struct Function {
+    params: Vec<String>,
+    statements: Vec<String>,
+}
+
+pub struct Interpreter {
+    constants: BTreeMap<String, Val>,
+    functions: BTreeMap<String, Function>,
+    structs: BTreeMap<String, Vec<String>>,
+    memory: Vec<Vec<Option<Felt>>>,
+}
+
+impl Interpreter {
+    /// Parses the functions and structs of the provided Cairo source.
+    pub fn new(source: &str) -> Self {
+        let mut constants = BTreeMap::new();
+        let mut functions = BTreeMap::new();
+        let mut structs = BTreeMap::new();
+
+        let mut lines = source.lines();
+        while let Some(line) = lines.next() {
+            if let Some(rest) = line.strip_prefix("const ") {
+                let (name, value) = rest.trim_end_matches(';').split_once(" = ").unwrap();
+                let value = Felt::new(value.parse::<u64>().unwrap());
+                constants.insert(name.to_string(), Val::Felt(value));
+            } else if let Some(rest) = line.strip_prefix("struct ") {
+                let name = rest.trim_end_matches('{').trim().to_string();
+                let fields = lines
+                    .by_ref()
+                    .take_while(|line| line.trim() != "}")
+                    .map(|line| line.split(':').next().unwrap().trim().to_string())
+                    .collect();
+                structs.insert(name, fields);
+            } else if let Some(rest) = line.strip_prefix("func ") {
+                let name = rest.split(['{', '(']).next().unwrap().trim().to_string();
+
+                // the signature may span several lines
+                let mut signature = line.to_string();
+                while !signature.trim_end().ends_with('{') {
+                    signature += lines.next().expect("unterminated signature");
+                }
+                let args = signature.split_once('(').unwrap().1;
+                let args = args.split(')').next().unwrap();
+                let params = args
+                    .split(',')
+                    .map(|param| param.split(':').next().unwrap().trim().to_string())
+                    .filter(|param| !param.is_empty())
+                    .collect();
+
+                let body = lines
+                    .by_ref()
+                    .take_while(|line| *line != "}")
+                    .collect::<Vec<_>>();
+                functions.insert(
+                    name,
+                    Function {
+                        params,
+                        statements: split_statements(&body),
+                    },
+                );
+            }
+        }
+
+        Self {
+            constants,
+            functions,
+            structs,
+            memory: Vec::new(),
+        }
+    }
+
+    /// Allocates a new memory segment holding the provided values and returns a pointer to it.
+    pub fn alloc(&mut self, values: &[Felt]) -> Val {
+        self.memory.push(values.iter().map(|v| Some(*v)).collect());
+        Val::Ptr(self.memory.len() - 1, 0)
+    }
+
+    /// Returns the values stored at the specified pointer.
+    pub fn read(&self, ptr: &Val, len: usize) -> Vec<Felt> {
+        (0..len).map(|i| self.load(ptr, i)).collect()
+    }
+
+    /// Calls the named function with the provided arguments and returns its result.
+    pub fn call(&mut self, name: &str, args: Vec<Val>) -> Option<Val> {
+        if let Some(result) = builtin(name, &args) {
+            return Some(result);
+        }
+        if let Some(fields) = self.structs.get(name) {
+            assert_eq!(
+                fields.len(),
+                args.len(),
+                "wrong number of fields for {name}"
+            );
+            return Some(Val::Struct(name.to_string(), args));
+        }
+
+        let function = self
+            .functions
+            .get(name)
+            .unwrap_or_else(|| panic!("unknown function {name}"));
+        assert_eq!(
+            function.params.len(),
+            args.len(),
+            "wrong number of arguments for {name}"
+        );
+        let mut env = function
+            .params
+            .iter()
+            .cloned()
+            .zip(args)
+            .collect::<BTreeMap<_, _>>();
+
+        let statements = function.statements.clone();
+        for statement in statements.iter() {
+            if let Some(result) = self.exec(statement, &mut env) {
+                return result;
+            }
+        }
+        None
+    }
+
+    // STATEMENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes a statement, returning `Some` if the statement returns from the function.
+    fn exec(&mut self, statement: &str, env: &mut BTreeMap<String, Val>) -> Option<Option<Val>> {
+        if statement == "alloc_locals" {
+            None
+        } else if let Some(rest) = statement.strip_prefix("let (") {
+            let (name, rhs) = rest.split_once(')').unwrap();
+            assert_eq!(rhs.trim(), "= alloc()", "unsupported statement {statement}");
+            let name = name.split(':').next().unwrap().trim().to_string();
+            let ptr = self.alloc(&[]);
+            env.insert(name, ptr);
+            None
+        } else if let Some(rest) = statement
+            .strip_prefix("let ")
+            .or_else(|| statement.strip_prefix("local "))
+        {
+            let (name, expr) = rest.split_once('=').unwrap();
+            let name = name.split(':').next().unwrap().trim().to_string();
+            let value = self.eval(expr, env);
+            env.insert(name, value);
+            None
+        } else if let Some(rest) = statement.strip_prefix("assert ") {
+            let (lhs, rhs) = rest.split_once('=').unwrap();
+            let value = self.eval(rhs, env);
+            match lhs
+                .trim()
+                .strip_suffix(']')
+                .and_then(|lhs| lhs.split_once('['))
+            {
+                Some((ptr, idx)) => {
+                    let ptr = self.eval(ptr, env);
+                    let idx = self.eval(idx, env).felt().as_int() as usize;
+                    self.store(&ptr, idx, value.felt());
+                }
+                None => assert_eq!(self.eval(lhs, env), value, "assertion failed: {statement}"),
+            }
+            None
+        } else if let Some(rest) = statement.strip_prefix("return") {
+            match rest.trim() {
+                "()" => Some(None),
+                expr => Some(Some(self.eval(expr, env))),
+            }
+        } else {
+            self.eval(statement, env);
+            None
+        }
+    }
+
+    // EXPRESSIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn eval(&mut self, expr: &str, env: &BTreeMap<String, Val>) -> Val {
+        let tokens = tokenize(expr);
+        let mut pos = 0;
+        let value = self.eval_sum(&tokens, &mut pos, env);
+        assert_eq!(pos, tokens.len(), "unexpected tokens in {expr}");
+        value
+    }
+
+    fn eval_sum(&mut self, tokens: &[String], pos: &mut usize, env: &BTreeMap<String, Val>) -> Val {
+        let mut lhs = self.eval_product(tokens, pos, env);
+        while let Some(op) = tokens.get(*pos).filter(|t| *t == "+" || *t == "-").cloned() {
+            *pos += 1;
+            let rhs = self.eval_product(tokens, pos, env);
+            lhs = match (lhs, rhs, op.as_str()) {
+                (Val::Felt(a), Val::Felt(b), "+") => Val::Felt(a + b),
+                (Val::Felt(a), Val::Felt(b), "-") => Val::Felt(a - b),
+                (Val::Ptr(seg, offset), Val::Felt(b), "+") => {
+                    Val::Ptr(seg, offset + b.as_int() as usize)
+                }
+                (lhs, rhs, op) => panic!("unsupported operation {lhs:?} {op} {rhs:?}"),
+            };
+        }
+        lhs
+    }
+
+    fn eval_product(
+        &mut self,
+        tokens: &[String],
+        pos: &mut usize,
+        env: &BTreeMap<String, Val>,
+    ) -> Val {
+        let mut lhs = self.eval_postfix(tokens, pos, env);
+        while tokens.get(*pos).map(String::as_str) == Some("*") {
+            *pos += 1;
+            let rhs = self.eval_postfix(tokens, pos, env);
+            lhs = Val::Felt(lhs.felt() * rhs.felt());
+        }
+        lhs
+    }
+
+    fn eval_postfix(
+        &mut self,
+        tokens: &[String],
+        pos: &mut usize,
+        env: &BTreeMap<String, Val>,
+    ) -> Val {
+        let token = tokens[*pos].clone();
+        *pos += 1;
+
+        let mut value = if token == "(" {
+            let value = self.eval_sum(tokens, pos, env);
+            expect(tokens, pos, ")");
+            value
+        } else if token.chars().next().unwrap().is_ascii_digit() {
+            Val::Felt(Felt::new(token.parse::<u64>().unwrap()))
+        } else if tokens.get(*pos).map(String::as_str) == Some("(") {
+            *pos += 1;
+            let mut args = Vec::new();
+            while tokens[*pos] != ")" {
+                // named arguments of struct constructors are passed in order
+                if tokens.get(*pos + 1).map(String::as_str) == Some("=") {
+                    *pos += 2;
+                }
+                args.push(self.eval_sum(tokens, pos, env));
+                if tokens[*pos] == "," {
+                    *pos += 1;
+                }
+            }
+            *pos += 1;
+            self.call(&token, args)
+                .unwrap_or_else(|| Val::Struct("()".to_string(), vec![]))
+        } else {
+            env.get(&token)
+                .or_else(|| self.constants.get(&token))
+                .unwrap_or_else(|| panic!("unknown identifier {token}"))
+                .clone()
+        };
+
+        loop {
+            match tokens.get(*pos).map(String::as_str) {
+                Some("[") => {
+                    *pos += 1;
+                    let idx = self.eval_sum(tokens, pos, env).felt().as_int() as usize;
+                    expect(tokens, pos, "]");
+                    value = Val::Felt(self.load(&value, idx));
+                }
+                Some(".") => {
+                    let field = &tokens[*pos + 1];
+                    *pos += 2;
+                    value = match value {
+                        Val::Struct(name, values) => {
+                            let idx = self.structs[&name]
+                                .iter()
+                                .position(|f| f == field)
+                                .unwrap_or_else(|| panic!("unknown field {name}.{field}"));
+                            values[idx].clone()
+                        }
+                        value => panic!("expected a struct, found {value:?}"),
+                    };
+                }
+                _ => return value,
+            }
+        }
+    }
+
+    // MEMORY
+    // --------------------------------------------------------------------------------------------
+
+    fn load(&self, ptr: &Val, idx: usize) -> Felt {
+        match ptr {
+            Val::Ptr(seg, offset) => self.memory[*seg]
+                .get(offset + idx)
+                .copied()
+                .flatten()
+                .unwrap_or_else(|| panic!("read of unset memory {ptr:?}[{idx}]")),
+            _ => panic!("expected a pointer, found {ptr:?}"),
+        }
+    }
+
+    fn store(&mut self, ptr: &Val, idx: usize, value: Felt) {
+        match ptr {
+            Val::Ptr(seg, offset) => {
+                let segment = &mut self.memory[*seg];
+                let addr = offset + idx;
+                if segment.len() <= addr {
+                    segment.resize(addr + 1, None);
+                }
+                match segment[addr] {
+                    Some(existing) => assert_eq!(existing, value, "assertion failed at {ptr:?}"),
+                    None => segment[addr] = Some(value),
+                }
+            }
+            _ => panic!("expected a pointer, found {ptr:?}"),
+        }
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Implements the functions of `math_goldilocks.cairo`, and `pow_e` of `ext2.cairo`.
+fn builtin(name: &str, args: &[Val]) -> Option<Val> {
+    let result = match name {
+        "pow_e" => {
+            let result = args[0].as_ext2().exp(args[1].felt().as_int());
+            return Some(Val::ext2(result));
+        }
+        "add_g" => args[0].felt() + args[1].felt(),
+        "sub_g" => args[0].felt() - args[1].felt(),
+        "mul_g" => args[0].felt() * args[1].felt(),
+        "div_g" => args[0].felt() / args[1].felt(),
+        "inv_g" => args[0].felt().inv(),
+        "pow_g" => args[0].felt().exp(args[1].felt().as_int()),
+        _ => return None,
+    };
+    Some(Val::Felt(result))
+}
+
+/// Splits the lines of a function body into statements, dropping comments and hints.
+fn split_statements(body: &[&str]) -> Vec<String> {
+    let mut code = String::new();
+    let mut in_hint = false;
+    for line in body {
+        let line = line.trim();
+        if line.starts_with("%{") {
+            in_hint = true;
+        }
+        if !in_hint && !line.starts_with("//") {
+            code += line;
+            code += " ";
+        }
+        if line.ends_with("%}") {
+            in_hint = false;
+        }
+    }
+    code.split(';')
+        .map(|statement| statement.trim().to_string())
+        .filter(|statement| !statement.is_empty())
+        .collect()
+}
+
+fn tokenize(expr: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = expr.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            let mut token = String::new();
+            while let Some(&c) = chars
+                .peek()
+                .filter(|c| c.is_ascii_alphanumeric() || **c == '_')
+            {
+                token.push(c);
+                chars.next();
+            }
+            tokens.push(token);
+        } else {
+            tokens.push(c.to_string());
+            chars.next();
+        }
+    }
+    tokens
+}
+
+fn expect(tokens: &[String], pos: &mut usize, token: &str) {
+    assert_eq!(tokens[*pos], token, "expected {token}");
+    *pos += 1;
+}
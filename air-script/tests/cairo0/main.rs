@@ -0,0 +1,791 @@
+use air_script::{compiler::Target, Compiler, CompilerOptions};
+use common::{
+    air_context, assert_linear_in_nested_variables, coefficients, ext2_elements, rand_elements,
+    rand_ext2_elements, Ext2,
+};
+use interpreter::{Interpreter, Val};
+use winter_air::{
+    AirContext, Assertion, BoundaryConstraints, FieldExtension, ProofOptions, TraceInfo,
+    TraceLayout, TransitionConstraintDegree, TransitionConstraints,
+};
+use winter_math::{fft, fields::f64::BaseElement as Felt, polynom, FieldElement, StarkField};
+
+#[path = "../common/mod.rs"]
+mod common;
+mod interpreter;
+
+/// The quadratic extension library shipped next to `math_goldilocks.cairo`.
+const EXT2_LIBRARY: &str = include_str!("../../../codegen/cairo0/src/ext2.cairo");
+
+// HELPERS
+// ================================================================================================
+
+fn generate_cairo0(path: &str) -> String {
+    let options = CompilerOptions::default().with_targets([Target::Cairo0]);
+    let output = Compiler::new(options).compile_file(path).unwrap();
+    output.get(Target::Cairo0).unwrap().to_string()
+}
+
+/// Returns an interpreter for the provided Cairo code and the quadratic extension library.
+fn interpreter(code: &str) -> Interpreter {
+    Interpreter::new(&format!("{code}\n{EXT2_LIBRARY}"))
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn evaluate_constraints_main_segment() {
+    let code = generate_cairo0("tests/binary/binary.air");
+
+    assert!(
+        code.contains("func evaluate_constraints{range_check_ptr}(\n  frame_0: EvaluationFrame,\n")
+    );
+    assert!(!code.contains("frame_1"));
+    assert!(!code.contains("rand: felt*"));
+    assert!(code.contains("  evaluate_transition_0(frame_0, t_evaluations_0, periodic_row);\n"));
+    assert!(code.contains("  evaluate_boundary_0(b_frame_0, b_evaluations_0, stack_inputs);\n"));
+    assert!(code.contains("\n  return sum_1;\n}\n"));
+    assert!(!code.contains("PUT CONSTRAINT EVALUATION FUNCTION HERE"));
+}
+
+#[test]
+fn evaluate_constraints_aux_segment() {
+    let code = generate_cairo0("tests/aux_trace/aux_trace.air");
+
+    assert!(code.contains("  frame_0: EvaluationFrame,\n  frame_1: EvaluationFrame,\n"));
+    assert!(code.contains("  rand: felt*,\n"));
+    assert!(code.contains(
+        "  evaluate_transition_1(frame_0, frame_1, t_evaluations_1, periodic_row, rand);\n"
+    ));
+    assert!(code.contains(
+        "  evaluate_boundary_1(b_frame_0, b_frame_1, b_evaluations_1, stack_inputs, rand);\n"
+    ));
+
+    // the coefficients of the aux segment follow those of the main segment, two felts each
+    assert!(code.contains("    coeffs_transition_a + 6,\n"));
+    assert!(code.contains("    coeffs_boundary_b + 4,\n"));
+    assert!(code.contains("\n  return sum_2;\n}\n"));
+
+    // every segment is evaluated in the quadratic extension
+    assert!(code.contains("from ext2 import Ext2, "));
+    assert!(code.contains("  x: Ext2,\n) -> Ext2 {\n"));
+    assert!(code.contains("  local t_0: Ext2 = merge_transitions_0(\n"));
+    assert!(code.contains("  local sum_1: Ext2 = add_e(sum_0, segment_sum_0);\n"));
+}
+
+#[test]
+fn periodic_columns_evaluated_in_circuit() {
+    let code = generate_cairo0("tests/periodic_columns/periodic_columns.air");
+
+    assert!(!code.contains("  periodic_row: felt*,\n) -> felt {"));
+    assert!(code.contains("  evaluate_periodic_columns(periodic_row, trace_length, x);\n"));
+
+    // k0 = [1, 0, 0, 0] interpolates to 1/4 * (1 + z + z^2 + z^3)
+    assert!(code.contains("  let num_cycles_0 = div_g(trace_length, 4);\n"));
+    assert!(code.contains("  local p_0_3 = 13835058052060938241;\n"));
+    assert!(code.contains("  local p_0_0 = add_g(t, 13835058052060938241);\n"));
+    assert!(code.contains("  assert periodic_row[0] = p_0_0;\n"));
+
+    assert!(code.contains("  let num_cycles_1 = div_g(trace_length, 8);\n"));
+    assert!(code.contains("  assert periodic_row[1] = p_1_0;\n"));
+}
+
+#[test]
+fn periodic_transitions_match_winterfell() {
+    let options = CompilerOptions::default().with_targets([Target::Cairo0]);
+    let output = Compiler::new(options)
+        .compile_file("tests/periodic_columns/periodic_columns.air")
+        .unwrap();
+    let (code, ir) = (output.get(Target::Cairo0).unwrap(), output.ir());
+    let mut cairo = interpreter(code);
+
+    // constraints using periodic columns are merged with the degree of their cycles
+    assert!(code.contains("  // Merge degree 1, cycles 4\n"));
+    assert!(code.contains("  let num_cycles_0 = div_g(trace_length, 8);\n"));
+    assert!(code.contains("  let evaluation_degree = 1 * (trace_length - 1) + num_cycles_0 * 7;\n"));
+
+    let trace_length = 32;
+    let context = air_context(ir, trace_length);
+    let g = Felt::get_root_of_unity(trace_length.trailing_zeros());
+    let felt = |value: usize| Val::Felt(Felt::new(value as u64));
+
+    // at the OOD point, the periodic columns are evaluated from their interpolated polynomials
+    let x = rand_elements(1, 1)[0];
+    let periodic_values = [&[1, 0, 0, 0][..], &[1, 1, 1, 1, 1, 1, 1, 0][..]].map(|values| {
+        let mut poly = values.iter().map(|v| Felt::new(*v)).collect::<Vec<_>>();
+        let inv_twiddles = fft::get_inv_twiddles::<Felt>(poly.len());
+        fft::interpolate_poly(&mut poly, &inv_twiddles);
+        polynom::eval(&poly, x.exp((trace_length / values.len()) as u64))
+    });
+
+    // k0 * (b + c) = 0 and k1 * (a' - a) = 0, merged by Winterfell
+    let frame = rand_elements(2, 6);
+    let [a, b, c, a_next, _, _] = frame.as_slice() else {
+        unreachable!()
+    };
+    let evaluations = [
+        periodic_values[0] * (*b + *c),
+        periodic_values[1] * (*a_next - *a),
+    ]
+    .map(Ext2::from);
+    let (coeffs_a, coeffs_b, coeffs) = coefficients(3, 2);
+    let transitions = TransitionConstraints::<Ext2>::new(&context, &coeffs);
+    let expected = transitions.combine_evaluations::<Ext2>(&evaluations, &[], Ext2::from(x));
+
+    // the same constraints, evaluated and merged by the generated Cairo code
+    let periodic_row = cairo.alloc(&[]);
+    cairo.call(
+        "evaluate_periodic_columns",
+        vec![periodic_row.clone(), felt(trace_length), Val::Felt(x)],
+    );
+    assert_eq!(cairo.read(&periodic_row, 2), periodic_values);
+
+    let (current, next) = (cairo.alloc(&frame[..3]), cairo.alloc(&frame[3..]));
+    let frame_0 = Val::Struct(
+        "EvaluationFrame".to_string(),
+        vec![felt(3), current, felt(3), next],
+    );
+    let t_evaluations = cairo.alloc(&[]);
+    cairo.call(
+        "evaluate_transition_0",
+        vec![frame_0, t_evaluations.clone(), periodic_row],
+    );
+    let target_degree = context.composition_degree() + trace_length - 1;
+    let (transition_a, transition_b) = (cairo.alloc(&coeffs_a), cairo.alloc(&coeffs_b));
+    let result = cairo.call(
+        "merge_transitions_0",
+        vec![
+            felt(trace_length),
+            felt(target_degree),
+            transition_a,
+            transition_b,
+            t_evaluations,
+            Val::Felt(x),
+            Val::Felt(g),
+        ],
+    );
+    assert_eq!(result.map(|v| Ext2::from(v.felt())), Some(expected));
+}
+
+#[test]
+fn periodic_columns_evaluated_in_extension() {
+    let source = "
+def PeriodicAuxAir
+
+trace_columns:
+    main: [a]
+    aux: [p]
+
+public_inputs:
+    stack_inputs: [2]
+
+periodic_columns:
+    k0: [1, 0, 0, 0]
+
+random_values:
+    rand: [1]
+
+boundary_constraints:
+    enf a.first = 0
+    enf p.first = 0
+
+integrity_constraints:
+    enf a' = k0 * a
+    enf p' = p * $rand[0]";
+    let options = CompilerOptions::default().with_targets([Target::Cairo0]);
+    let output = Compiler::new(options).compile_source(source).unwrap();
+    let mut cairo = interpreter(output.get(Target::Cairo0).unwrap());
+
+    // with an aux segment, the OOD point and the periodic values are in the extension
+    let trace_length = 16;
+    let x = rand_ext2_elements(31, 1)[0];
+    let mut poly = [1, 0, 0, 0].map(Felt::new).to_vec();
+    let inv_twiddles = fft::get_inv_twiddles::<Felt>(poly.len());
+    fft::interpolate_poly(&mut poly, &inv_twiddles);
+    let poly = poly.into_iter().map(Ext2::from).collect::<Vec<_>>();
+    let expected = polynom::eval(&poly, x.exp(trace_length / 4));
+
+    let periodic_row = cairo.alloc(&[]);
+    cairo.call(
+        "evaluate_periodic_columns",
+        vec![
+            periodic_row.clone(),
+            Val::Felt(Felt::new(trace_length)),
+            Val::ext2(x),
+        ],
+    );
+    assert_eq!(ext2_elements(&cairo.read(&periodic_row, 2)), vec![expected]);
+}
+
+#[test]
+fn boundary_merge_matches_winterfell() {
+    let code = generate_cairo0("tests/aux_trace/aux_trace.air");
+    let mut cairo = interpreter(&code);
+
+    // the layout of the AuxiliaryAir
+    let trace_length = 16;
+    let last_step = trace_length - 1;
+    let trace_info =
+        TraceInfo::new_multi_segment(TraceLayout::new(3, [2], [2]), trace_length, vec![]);
+    let options = ProofOptions::new(27, 8, 0, FieldExtension::Quadratic, 4, 32);
+    let context = AirContext::new_multi_segment(
+        trace_info,
+        vec![TransitionConstraintDegree::new(3); 3],
+        vec![TransitionConstraintDegree::new(2); 2],
+        2,
+        4,
+        options,
+    );
+    let blowup_factor = context.ce_domain_size() / trace_length;
+
+    // the rows, the random values, the coefficients and x are in the quadratic extension
+    let values = rand_elements(42, 44);
+    let (main_row, values) = values.split_at(6);
+    let (aux_row, values) = values.split_at(8);
+    let (rand, values) = values.split_at(4);
+    let (coeffs_a, values) = values.split_at(12);
+    let (coeffs_b, values) = values.split_at(12);
+    let x = ext2_elements(values)[0];
+
+    // the boundary constraints of the AuxiliaryAir, evaluated by Winterfell
+    let main_assertions = vec![
+        Assertion::single(0, 0, Felt::ONE),
+        Assertion::single(1, 0, Felt::ONE),
+    ];
+    let aux_assertions = vec![
+        Assertion::single(0, 0, Ext2::ONE),
+        Assertion::single(0, last_step, Ext2::ONE),
+        Assertion::single(1, 0, ext2_elements(rand)[0]),
+        Assertion::single(1, last_step, Ext2::ONE),
+    ];
+    let coeffs = ext2_elements(coeffs_a)
+        .into_iter()
+        .zip(ext2_elements(coeffs_b))
+        .collect::<Vec<_>>();
+    let constraints = BoundaryConstraints::new(&context, main_assertions, aux_assertions, &coeffs);
+    let main_row_e = ext2_elements(main_row);
+    let main_expected = constraints
+        .main_constraints()
+        .iter()
+        .fold(Ext2::ZERO, |sum, group| {
+            sum + group.evaluate_at(&main_row_e, x, x.exp(group.degree_adjustment()))
+        });
+    let aux_row_e = ext2_elements(aux_row);
+    let aux_expected = constraints
+        .aux_constraints()
+        .iter()
+        .fold(Ext2::ZERO, |sum, group| {
+            sum + group.evaluate_at(&aux_row_e, x, x.exp(group.degree_adjustment()))
+        });
+
+    // the same boundary constraints, evaluated by the generated Cairo code
+    let main_ptr = cairo.alloc(main_row);
+    let aux_ptr = cairo.alloc(aux_row);
+    let frame = |width: usize, ptr: &Val| {
+        let width = Val::Felt(Felt::new(width as u64));
+        Val::Struct(
+            "EvaluationFrame".to_string(),
+            vec![width.clone(), ptr.clone(), width, ptr.clone()],
+        )
+    };
+    let stack_inputs = cairo.alloc(&rand_elements(7, 16));
+    let rand = cairo.alloc(rand);
+    let coeffs_a = cairo.alloc(coeffs_a);
+    let coeffs_b = cairo.alloc(coeffs_b);
+    let felt = |value: u64| Val::Felt(Felt::new(value));
+    let g = Val::Felt(Felt::get_root_of_unity(trace_length.trailing_zeros()));
+
+    let b_evaluations_0 = cairo.alloc(&[]);
+    cairo.call(
+        "evaluate_boundary_0",
+        vec![
+            frame(3, &main_ptr),
+            b_evaluations_0.clone(),
+            stack_inputs.clone(),
+        ],
+    );
+    let main_result = cairo.call(
+        "merge_boundary_0",
+        vec![
+            felt(trace_length as u64),
+            felt(blowup_factor as u64),
+            coeffs_a.clone(),
+            coeffs_b.clone(),
+            b_evaluations_0,
+            g.clone(),
+            felt(last_step as u64),
+            Val::ext2(x),
+        ],
+    );
+    assert_eq!(main_result, Some(Val::ext2(main_expected)));
+
+    let b_evaluations_1 = cairo.alloc(&[]);
+    cairo.call(
+        "evaluate_boundary_1",
+        vec![
+            frame(3, &main_ptr),
+            frame(4, &aux_ptr),
+            b_evaluations_1.clone(),
+            stack_inputs,
+            rand,
+        ],
+    );
+    let offset = |ptr: Val| match ptr {
+        Val::Ptr(segment, offset) => Val::Ptr(segment, offset + 4),
+        _ => unreachable!(),
+    };
+    let aux_result = cairo.call(
+        "merge_boundary_1",
+        vec![
+            felt(trace_length as u64),
+            felt(blowup_factor as u64),
+            offset(coeffs_a),
+            offset(coeffs_b),
+            b_evaluations_1,
+            g,
+            felt(last_step as u64),
+            Val::ext2(x),
+        ],
+    );
+    assert_eq!(aux_result, Some(Val::ext2(aux_expected)));
+}
+
+#[test]
+fn transition_exemptions_match_winterfell() {
+    let code = generate_cairo0("tests/transition_exemptions/transition_exemptions.air");
+    let mut cairo = interpreter(&code);
+
+    // the last 3 rows are exempted from the transition constraints
+    assert!(code
+        .contains("  local transition_target_degree = composition_degree + trace_length - 3;\n"));
+    assert!(code.contains("  let d3 = mul_g(d2, f3);\n  let denominator = d3;\n"));
+
+    // the layout of the PaddedAir
+    let trace_length = 16;
+    let last_step = trace_length - 3;
+    let trace_info = TraceInfo::new(2, trace_length);
+    let options = ProofOptions::new(27, 8, 0, FieldExtension::Quadratic, 4, 32);
+    let context = AirContext::new(
+        trace_info,
+        vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(2),
+        ],
+        2,
+        options,
+    )
+    .set_num_transition_exemptions(3);
+    let blowup_factor = context.ce_domain_size() / trace_length;
+
+    let values = rand_elements(42, 13);
+    let (frame, values) = values.split_at(4);
+    let (coeffs_a, values) = values.split_at(4);
+    let (coeffs_b, values) = values.split_at(4);
+    let x = values[0];
+
+    // clk' = clk + 1 and a^2 - a = 0, merged by Winterfell
+    let [clk, a, clk_next, _] = frame else {
+        unreachable!()
+    };
+    let evaluations = [*clk_next - (*clk + Felt::ONE), *a * *a - *a].map(Ext2::from);
+    let coeffs = coeffs_a
+        .iter()
+        .zip(coeffs_b)
+        .map(|(a, b)| (Ext2::from(*a), Ext2::from(*b)))
+        .collect::<Vec<_>>();
+    let transitions = TransitionConstraints::<Ext2>::new(&context, &coeffs[..2]);
+    let x_e = Ext2::from(x);
+    let mut expected = transitions.combine_evaluations::<Ext2>(&evaluations, &[], x_e);
+
+    // the boundary constraint on the last row applies to the last step which is not exempted
+    let assertions = vec![
+        Assertion::single(0, 0, Felt::ZERO),
+        Assertion::single(1, last_step, Felt::ONE),
+    ];
+    let boundaries = BoundaryConstraints::new(&context, assertions, vec![], &coeffs[2..]);
+    let row = frame[..2]
+        .iter()
+        .map(|v| Ext2::from(*v))
+        .collect::<Vec<_>>();
+    for group in boundaries.main_constraints() {
+        expected += group.evaluate_at(&row, x_e, x_e.exp(group.degree_adjustment()));
+    }
+
+    // the same constraints, evaluated and merged by the generated Cairo code
+    let current = cairo.alloc(&frame[..2]);
+    let next = cairo.alloc(&frame[2..]);
+    let width = Val::Felt(Felt::new(2));
+    let frame_0 = Val::Struct(
+        "EvaluationFrame".to_string(),
+        vec![width.clone(), current, width, next],
+    );
+    let stack_inputs = cairo.alloc(&rand_elements(7, 16));
+    let transition_a = cairo.alloc(&coeffs_a[..2]);
+    let transition_b = cairo.alloc(&coeffs_b[..2]);
+    let boundary_a = cairo.alloc(&coeffs_a[2..]);
+    let boundary_b = cairo.alloc(&coeffs_b[2..]);
+    let felt = |value: usize| Val::Felt(Felt::new(value as u64));
+    let g = Val::Felt(Felt::get_root_of_unity(trace_length.trailing_zeros()));
+    let result = cairo.call(
+        "evaluate_constraints",
+        vec![
+            frame_0,
+            stack_inputs,
+            transition_a,
+            transition_b,
+            boundary_a,
+            boundary_b,
+            felt(trace_length),
+            felt(blowup_factor),
+            g,
+            Val::Felt(x),
+        ],
+    );
+    assert_eq!(result.map(|v| Ext2::from(v.felt())), Some(expected));
+}
+
+#[test]
+fn shared_subexpressions_emitted_once() {
+    let code = generate_cairo0("tests/aux_trace/aux_trace.air");
+
+    // $rand[0] is used by both auxiliary transition constraints
+    let transition_1 = code
+        .split("func evaluate_transition_1")
+        .nth(1)
+        .unwrap()
+        .split("\n}\n")
+        .next()
+        .unwrap();
+    assert_eq!(
+        transition_1
+            .matches(" = Ext2(a0=rand[0], a1=rand[1]);\n")
+            .count(),
+        1
+    );
+    assert!(transition_1.contains("  local v23: Ext2 = add_e(v0, v8);\n"));
+    assert!(transition_1.contains("  local v30: Ext2 = add_e(v13, v8);\n"));
+
+    // every node is bound at most once in each constraint evaluation function
+    let code = generate_cairo0("tests/variables/variables.air");
+    let functions = code
+        .split("\nfunc evaluate_")
+        .filter(|f| f.starts_with("transition_") || f.starts_with("boundary_"));
+    for function in functions {
+        let mut bindings = function
+            .split("\n}\n")
+            .next()
+            .unwrap()
+            .lines()
+            .filter(|line| line.starts_with("  let ") || line.starts_with("  local "))
+            .filter_map(|line| line.split_once(" = "))
+            .map(|(lhs, _)| {
+                lhs.trim_start_matches("  let ")
+                    .trim_start_matches("  local ")
+            })
+            .collect::<Vec<_>>();
+        let len = bindings.len();
+        bindings.sort();
+        bindings.dedup();
+        assert_eq!(bindings.len(), len);
+    }
+}
+
+#[test]
+fn named_constants() {
+    let code = generate_cairo0("tests/constants/constants.air");
+    assert!(code.contains("const A = 1;\n"));
+    assert!(code.contains("const B_0 = 0;\nconst B_1 = 1;\n"));
+    assert!(
+        code.contains("const C_0_0 = 1;\nconst C_0_1 = 2;\nconst C_1_0 = 2;\nconst C_1_1 = 0;\n")
+    );
+    assert!(!code.contains("BoundConstant"));
+
+    // evaluate the main boundary constraints against a row of the trace, which is in the
+    // quadratic extension since the AIR has an aux segment
+    let mut cairo = interpreter(&code);
+    let row = rand_elements(3, 8);
+    let row_ptr = cairo.alloc(&row);
+    let width = Val::Felt(Felt::new(4));
+    let frame = Val::Struct(
+        "EvaluationFrame".to_string(),
+        vec![width.clone(), row_ptr.clone(), width, row_ptr],
+    );
+    let b_evaluations = cairo.alloc(&[]);
+    let mut args = vec![frame, b_evaluations.clone()];
+    for len in [4, 4, 20, 4] {
+        args.push(cairo.alloc(&rand_elements(len, len as usize)));
+    }
+    cairo.call("evaluate_boundary_0", args);
+
+    // a = A, b = A + B[0] * C[0][1], c = (B[0] - C[1][1]) * A, and
+    // d = A + B[0] - B[1] + C[0][0] - C[0][1] + C[1][0] - C[1][1]
+    let expected = [1, 1, 0, 1]
+        .iter()
+        .zip(ext2_elements(&row))
+        .map(|(value, cell)| cell - Ext2::from(Felt::new(*value)))
+        .collect::<Vec<_>>();
+    assert_eq!(ext2_elements(&cairo.read(&b_evaluations, 8)), expected);
+}
+
+#[test]
+fn ext2_library_matches_winterfell() {
+    let mut cairo = interpreter("");
+    let values = ext2_elements(&rand_elements(11, 4));
+    let (a, b) = (values[0], values[1]);
+    let c = rand_elements(12, 1)[0];
+
+    let mut call = |name: &str, args: Vec<Val>| cairo.call(name, args).unwrap();
+    let (va, vb) = (Val::ext2(a), Val::ext2(b));
+    assert_eq!(
+        call("add_e", vec![va.clone(), vb.clone()]),
+        Val::ext2(a + b)
+    );
+    assert_eq!(
+        call("sub_e", vec![va.clone(), vb.clone()]),
+        Val::ext2(a - b)
+    );
+    assert_eq!(
+        call("mul_e", vec![va.clone(), vb.clone()]),
+        Val::ext2(a * b)
+    );
+    assert_eq!(
+        call("mul_eg", vec![va.clone(), Val::Felt(c)]),
+        Val::ext2(a * Ext2::from(c))
+    );
+    assert_eq!(call("inv_e", vec![va.clone()]), Val::ext2(a.inv()));
+    assert_eq!(call("div_e", vec![va, vb]), Val::ext2(a / b));
+}
+
+#[test]
+fn aux_transitions_evaluated_in_extension() {
+    let code = generate_cairo0("tests/aux_trace/aux_trace.air");
+    let mut cairo = interpreter(&code);
+
+    let main = rand_elements(5, 12);
+    let aux = rand_elements(6, 8);
+    let rand = rand_elements(7, 4);
+    let frame = |cairo: &mut Interpreter, values: &[Felt]| {
+        let (current, next) = values.split_at(values.len() / 2);
+        let width = Val::Felt(Felt::new(current.len() as u64));
+        Val::Struct(
+            "EvaluationFrame".to_string(),
+            vec![
+                width.clone(),
+                cairo.alloc(current),
+                width,
+                cairo.alloc(next),
+            ],
+        )
+    };
+    let frame_0 = frame(&mut cairo, &main);
+    let frame_1 = frame(&mut cairo, &aux);
+    let t_evaluations = cairo.alloc(&[]);
+    let periodic_row = cairo.alloc(&[]);
+    let rand_ptr = cairo.alloc(&rand);
+    cairo.call(
+        "evaluate_transition_1",
+        vec![
+            frame_0,
+            frame_1,
+            t_evaluations.clone(),
+            periodic_row,
+            rand_ptr,
+        ],
+    );
+
+    // p0' = p0 * (a + $rand[0] + b + $rand[1]) and p1 = p1' * (c + $rand[0])
+    let [a, b, c, ..] = ext2_elements(&main)[..] else {
+        unreachable!()
+    };
+    let aux = ext2_elements(&aux);
+    let rand = ext2_elements(&rand);
+    let expected = [
+        aux[2] - aux[0] * (a + rand[0] + b + rand[1]),
+        aux[1] - aux[3] * (c + rand[0]),
+    ];
+    assert_eq!(
+        ext2_elements(&cairo.read(&t_evaluations, 4)),
+        expected.to_vec()
+    );
+}
+
+#[test]
+fn air_instance_matches_winterfell() {
+    for path in [
+        "tests/aux_trace/aux_trace.air",
+        "tests/bitwise/bitwise.air",
+        "tests/list_folding/list_folding.air",
+    ] {
+        let options = CompilerOptions::default().with_targets([Target::Cairo0]);
+        let output = Compiler::new(options).compile_file(path).unwrap();
+        let code = output.get(Target::Cairo0).unwrap();
+        let ir = output.ir();
+
+        // Winterfell's AirContext takes the blowup factor of the highest degree constraint
+        let widths = ir.trace_segment_widths();
+        let segments = 0..widths.len() as u8;
+        let ce_blowup_factor = segments
+            .clone()
+            .flat_map(|segment| ir.integrity_constraint_degrees(segment))
+            .map(|degree| {
+                TransitionConstraintDegree::with_cycles(degree.base(), degree.cycles().to_vec())
+                    .min_blowup_factor()
+            })
+            .max()
+            .unwrap();
+        let num_transition_constraints = segments
+            .clone()
+            .map(|segment| ir.integrity_constraints(segment).len())
+            .sum::<usize>();
+        let num_assertions = segments
+            .map(|segment| ir.boundary_constraints(segment).len())
+            .sum::<usize>();
+
+        let expected = [
+            ("MAIN_SEGMENT_WIDTH", widths[0] as usize),
+            ("AUX_TRACE_WIDTH", widths[1..].iter().sum::<u16>() as usize),
+            ("NUM_AUX_SEGMENTS", widths.len() - 1),
+            ("NUM_TRANSITION_CONSTRAINTS", num_transition_constraints),
+            ("NUM_ASSERTIONS", num_assertions),
+            ("CE_BLOWUP_FACTOR", ce_blowup_factor),
+            // the evaluation frames of Winterfell hold the current and next rows
+            ("EVAL_FRAME_SIZE", 2),
+        ];
+        for (name, value) in expected {
+            assert!(
+                code.contains(&format!("\nconst {name} = {value};\n")),
+                "{path}: expected {name} = {value}"
+            );
+        }
+    }
+
+    // every field of the AirInstance is initialized, in order
+    let air_processing = include_str!("../../../codegen/cairo0/src/air-processing.cairo");
+    let fields = air_processing
+        .split("struct AirInstance {\n")
+        .nth(1)
+        .unwrap()
+        .split("\n}\n")
+        .next()
+        .unwrap()
+        .lines()
+        .filter(|line| !line.trim().starts_with("//"))
+        .map(|line| line.split(':').next().unwrap().trim())
+        .collect::<Vec<_>>();
+    let code = generate_cairo0("tests/aux_trace/aux_trace.air");
+    let initialized = code
+        .split("  return AirInstance(\n")
+        .nth(1)
+        .unwrap()
+        .split("  );\n")
+        .next()
+        .unwrap()
+        .lines()
+        .map(|line| line.split('=').next().unwrap().trim())
+        .collect::<Vec<_>>();
+    assert_eq!(initialized, fields);
+}
+
+#[test]
+fn constant_exponents_unrolled() {
+    let source = "
+def PowersAir
+
+trace_columns:
+    main: [a, b]
+    aux: [p]
+
+public_inputs:
+    stack_inputs: [2]
+
+random_values:
+    rand: [1]
+
+boundary_constraints:
+    enf a.first = 0
+    enf p.first = 0
+
+integrity_constraints:
+    enf b' = a^0 + a^1 + a^2 + a^3 + a^5 + a^7 + a^16 + a^31
+    enf p' = (p + $rand[0])^0 + (p + $rand[0])^7";
+    let options = CompilerOptions::default().with_targets([Target::Cairo0]);
+    let output = Compiler::new(options).compile_source(source).unwrap();
+    let code = output.get(Target::Cairo0).unwrap();
+
+    // constant exponents are expanded into multiplications
+    assert!(!code.contains("pow_g(v"));
+    assert!(!code.contains("pow_e(v"));
+    assert!(code.contains("  local v13_2: Ext2 = mul_e(v0, v0);\n  local v13_4: Ext2 = mul_e(v13_2, v13_2);\n  local v13: Ext2 = mul_e(v13_4, v0);\n"));
+
+    let mut cairo = interpreter(code);
+    let frame = |cairo: &mut Interpreter, values: &[Felt]| {
+        let (current, next) = values.split_at(values.len() / 2);
+        let width = Val::Felt(Felt::new(current.len() as u64));
+        Val::Struct(
+            "EvaluationFrame".to_string(),
+            vec![
+                width.clone(),
+                cairo.alloc(current),
+                width,
+                cairo.alloc(next),
+            ],
+        )
+    };
+    let main = rand_elements(21, 8);
+    let aux = rand_elements(22, 4);
+    let rand = rand_elements(23, 2);
+    let frame_0 = frame(&mut cairo, &main);
+    let frame_1 = frame(&mut cairo, &aux);
+    let periodic_row = cairo.alloc(&[]);
+    let rand_ptr = cairo.alloc(&rand);
+
+    let t_evaluations_0 = cairo.alloc(&[]);
+    cairo.call(
+        "evaluate_transition_0",
+        vec![
+            frame_0.clone(),
+            t_evaluations_0.clone(),
+            periodic_row.clone(),
+        ],
+    );
+    let main = ext2_elements(&main);
+    let a = main[0];
+    let powers = [0, 1, 2, 3, 5, 7, 16, 31]
+        .iter()
+        .fold(Ext2::ZERO, |sum, k| sum + a.exp(*k));
+    assert_eq!(
+        ext2_elements(&cairo.read(&t_evaluations_0, 2)),
+        vec![main[3] - powers]
+    );
+
+    let t_evaluations_1 = cairo.alloc(&[]);
+    cairo.call(
+        "evaluate_transition_1",
+        vec![
+            frame_0,
+            frame_1,
+            t_evaluations_1.clone(),
+            periodic_row,
+            rand_ptr,
+        ],
+    );
+    let aux = ext2_elements(&aux);
+    let x = aux[0] + ext2_elements(&rand)[0];
+    assert_eq!(
+        ext2_elements(&cairo.read(&t_evaluations_1, 2)),
+        vec![aux[1] - (Ext2::ONE + x.exp(7))]
+    );
+}
+
+#[test]
+fn nested_variables_emitted_once() {
+    assert_linear_in_nested_variables(Target::Cairo0);
+}
+
+#[test]
+fn no_debug_hints() {
+    for path in ["tests/binary/binary.air", "tests/aux_trace/aux_trace.air"] {
+        assert!(!generate_cairo0(path).contains("%{"));
+    }
+}
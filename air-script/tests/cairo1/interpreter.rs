@@ -0,0 +1,646 @@
+//! A minimal interpreter for the subset of Cairo 1 emitted by the Cairo 1 code generator and used
+//! by `math_goldilocks.cairo` and `ext2.cairo`. It is used to check the generated code and the
+//! libraries against values computed by Winterfell, since the Cairo 1 toolchain is not available
+//! when running `cargo test`.
+//!
+//! Integers of every width are represented by `u128` values. As in Cairo, arithmetic panics on
+//! overflow and underflow, but only at the width of a `u128`, and `try_into` checks that a value
+//! fits in a `u64`. Field arithmetic is not built in: it is interpreted from the source of
+//! `math_goldilocks.cairo`.
+
+use std::collections::BTreeMap;
+use winter_math::{
+    fields::{f64::BaseElement as Felt, QuadExtension},
+    FieldElement, StarkField,
+};
+
+// VALUES
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Val {
+    /// An unsigned integer, which holds the canonical value of a field element.
+    Int(u128),
+    Bool(bool),
+    /// A short string, used as the message of an assertion.
+    Str(String),
+    /// An `Array` or a `Span`.
+    Array(Vec<Val>),
+    /// An instance of the named struct, with its fields in the order of their declaration.
+    Struct(String, Vec<Val>),
+}
+
+impl Val {
+    /// Returns the field element whose canonical value is held by this integer.
+    pub fn felt(&self) -> Felt {
+        match self {
+            Val::Int(value) => {
+                assert!(
+                    *value < Felt::MODULUS as u128,
+                    "{value} is not a canonical felt"
+                );
+                Felt::new(*value as u64)
+            }
+            _ => panic!("expected a felt, found {self:?}"),
+        }
+    }
+
+    /// Returns a span holding the provided base field elements.
+    pub fn felts(values: &[Felt]) -> Self {
+        Val::Array(values.iter().map(|v| Val::from(*v)).collect())
+    }
+
+    /// Returns an `Ext2` struct holding the specified element of the quadratic extension.
+    pub fn ext2(value: QuadExtension<Felt>) -> Self {
+        let values = QuadExtension::<Felt>::as_base_elements(&[value])
+            .iter()
+            .map(|v| Val::from(*v))
+            .collect();
+        Val::Struct("Ext2".to_string(), values)
+    }
+
+    /// Returns a span holding the provided elements of the quadratic extension.
+    pub fn ext2s(values: &[QuadExtension<Felt>]) -> Self {
+        Val::Array(values.iter().map(|v| Val::ext2(*v)).collect())
+    }
+
+    fn int(&self) -> u128 {
+        match self {
+            Val::Int(value) => *value,
+            _ => panic!("expected an integer, found {self:?}"),
+        }
+    }
+
+    fn bool(&self) -> bool {
+        match self {
+            Val::Bool(value) => *value,
+            _ => panic!("expected a bool, found {self:?}"),
+        }
+    }
+
+    fn elements(&self) -> &[Val] {
+        match self {
+            Val::Array(values) => values,
+            _ => panic!("expected an array, found {self:?}"),
+        }
+    }
+
+    fn index(&self) -> usize {
+        self.int() as usize
+    }
+}
+
+impl From<Felt> for Val {
+    fn from(value: Felt) -> Self {
+        Val::Int(value.as_int() as u128)
+    }
+}
+
+// INTERPRETER
+// ================================================================================================
+
+/// A statement of a block. Expressions are kept as the tokens of their source.
+enum Stmt {
+    Let(String, Vec<String>),
+    Assign(String, Vec<String>),
+    Expr(Vec<String>),
+    If(Vec<String>, Block, Option<Block>),
+    While(Vec<String>, Block),
+}
+
+/// The statements of a block, followed by the expression or `if` statement which gives the value
+/// of the block, if any.
+#[derive(Default)]
+struct Block {
+    statements: Vec<Stmt>,
+    tail: Option<Box<Stmt>>,
+}
+
+struct Function {
+    params: Vec<String>,
+    body: Block,
+}
+
+pub struct Interpreter {
+    constants: BTreeMap<String, Val>,
+    functions: BTreeMap<String, Function>,
+    structs: BTreeMap<String, Vec<String>>,
+}
+
+impl Interpreter {
+    /// Parses the constants, structs and functions of the provided Cairo source.
+    pub fn new(source: &str) -> Self {
+        let mut constants = BTreeMap::new();
+        let mut functions = BTreeMap::new();
+        let mut structs = BTreeMap::new();
+
+        let mut lines = source.lines();
+        while let Some(line) = lines.next() {
+            let line = line.strip_prefix("pub ").unwrap_or(line);
+            if let Some(rest) = line.strip_prefix("const ") {
+                let (name, value) = rest.trim_end_matches(';').split_once(" = ").unwrap();
+                let name = name.split(':').next().unwrap().trim().to_string();
+                constants.insert(name, Val::Int(value.parse::<u128>().unwrap()));
+            } else if let Some(rest) = line.strip_prefix("struct ") {
+                let name = rest.trim_end_matches('{').trim().to_string();
+                let fields = lines
+                    .by_ref()
+                    .take_while(|line| line.trim() != "}")
+                    .map(|line| {
+                        let field = line.trim().trim_start_matches("pub ");
+                        field.split(':').next().unwrap().trim().to_string()
+                    })
+                    .collect();
+                structs.insert(name, fields);
+            } else if let Some(rest) = line.strip_prefix("fn ") {
+                let name = rest.split('(').next().unwrap().trim().to_string();
+
+                // the signature may span several lines
+                let mut signature = line.to_string();
+                while !signature.trim_end().ends_with('{') {
+                    signature += lines.next().expect("unterminated signature");
+                }
+                let args = signature.split_once('(').unwrap().1;
+                let args = args.rsplit_once(')').unwrap().0;
+                let params = args
+                    .split(',')
+                    .map(|param| param.split(':').next().unwrap().trim().to_string())
+                    .filter(|param| !param.is_empty())
+                    .collect();
+
+                let body = lines
+                    .by_ref()
+                    .take_while(|line| *line != "}")
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let tokens = tokenize(&body);
+                let mut pos = 0;
+                let body = parse_block(&tokens, &mut pos);
+                assert_eq!(pos, tokens.len(), "unexpected tokens in the body of {name}");
+                functions.insert(name, Function { params, body });
+            }
+        }
+
+        Self {
+            constants,
+            functions,
+            structs,
+        }
+    }
+
+    /// Returns an instance of the named struct with the provided fields.
+    pub fn instance(&self, name: &str, fields: Vec<Val>) -> Val {
+        assert_eq!(
+            self.structs[name].len(),
+            fields.len(),
+            "wrong number of fields for {name}"
+        );
+        Val::Struct(name.to_string(), fields)
+    }
+
+    /// Calls the named function with the provided arguments and returns its result.
+    pub fn call(&mut self, name: &str, args: Vec<Val>) -> Val {
+        if let Some(result) = builtin(name, &args) {
+            return result;
+        }
+
+        // the function is taken out of the interpreter while it runs, so that its body can be
+        // borrowed while the interpreter is mutated, and put back afterwards
+        let function = self
+            .functions
+            .remove(name)
+            .unwrap_or_else(|| panic!("unknown function {name}"));
+        assert_eq!(
+            function.params.len(),
+            args.len(),
+            "wrong number of arguments for {name}"
+        );
+        let mut env = function
+            .params
+            .iter()
+            .cloned()
+            .zip(args)
+            .collect::<BTreeMap<_, _>>();
+
+        let result = self.exec_block(&function.body, &mut env);
+        self.functions.insert(name.to_string(), function);
+        result.unwrap_or_else(|| panic!("{name} does not return a value"))
+    }
+
+    // STATEMENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes the statements of the block and returns its value, if it has one.
+    fn exec_block(&mut self, block: &Block, env: &mut BTreeMap<String, Val>) -> Option<Val> {
+        for statement in block.statements.iter() {
+            self.exec(statement, env);
+        }
+        block
+            .tail
+            .as_ref()
+            .and_then(|statement| self.exec(statement, env))
+    }
+
+    /// Executes the statement and returns its value, if it has one.
+    fn exec(&mut self, statement: &Stmt, env: &mut BTreeMap<String, Val>) -> Option<Val> {
+        match statement {
+            Stmt::Let(name, expr) => {
+                let value = self.eval(expr, env);
+                env.insert(name.clone(), value);
+                None
+            }
+            Stmt::Assign(name, expr) => {
+                let value = self.eval(expr, env);
+                *env.get_mut(name)
+                    .unwrap_or_else(|| panic!("unknown variable {name}")) = value;
+                None
+            }
+            // `array.append(value)` is the only method which mutates its receiver
+            Stmt::Expr(expr) if expr.get(2).map(String::as_str) == Some("append") => {
+                let value = self.eval(&expr[4..expr.len() - 1], env);
+                match env.get_mut(&expr[0]) {
+                    Some(Val::Array(values)) => values.push(value),
+                    _ => panic!("unknown array {}", expr[0]),
+                }
+                None
+            }
+            Stmt::Expr(expr) => Some(self.eval(expr, env)),
+            Stmt::If(condition, then_block, else_block) => {
+                if self.eval(condition, env).bool() {
+                    self.exec_block(then_block, env)
+                } else {
+                    else_block
+                        .as_ref()
+                        .and_then(|block| self.exec_block(block, env))
+                }
+            }
+            Stmt::While(condition, block) => {
+                while self.eval(condition, env).bool() {
+                    self.exec_block(block, env);
+                }
+                None
+            }
+        }
+    }
+
+    // EXPRESSIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn eval(&mut self, tokens: &[String], env: &BTreeMap<String, Val>) -> Val {
+        let mut pos = 0;
+        let value = self.eval_comparison(tokens, &mut pos, env);
+        assert_eq!(
+            pos,
+            tokens.len(),
+            "unexpected tokens in {}",
+            tokens.join(" ")
+        );
+        value
+    }
+
+    fn eval_comparison(
+        &mut self,
+        tokens: &[String],
+        pos: &mut usize,
+        env: &BTreeMap<String, Val>,
+    ) -> Val {
+        let lhs = self.eval_sum(tokens, pos, env);
+        let op = match tokens.get(*pos).map(String::as_str) {
+            Some(op @ ("==" | "!=" | ">=" | "<=" | ">" | "<")) => op.to_string(),
+            _ => return lhs,
+        };
+        *pos += 1;
+        let rhs = self.eval_sum(tokens, pos, env);
+        Val::Bool(match op.as_str() {
+            "==" => lhs == rhs,
+            "!=" => lhs != rhs,
+            ">=" => lhs.int() >= rhs.int(),
+            "<=" => lhs.int() <= rhs.int(),
+            ">" => lhs.int() > rhs.int(),
+            _ => lhs.int() < rhs.int(),
+        })
+    }
+
+    fn eval_sum(&mut self, tokens: &[String], pos: &mut usize, env: &BTreeMap<String, Val>) -> Val {
+        let mut lhs = self.eval_product(tokens, pos, env);
+        while let Some(op) = tokens.get(*pos).filter(|t| *t == "+" || *t == "-").cloned() {
+            *pos += 1;
+            let (a, b) = (lhs.int(), self.eval_product(tokens, pos, env).int());
+            lhs = Val::Int(match op.as_str() {
+                "+" => a.checked_add(b).expect("addition overflow"),
+                _ => a.checked_sub(b).expect("subtraction underflow"),
+            });
+        }
+        lhs
+    }
+
+    fn eval_product(
+        &mut self,
+        tokens: &[String],
+        pos: &mut usize,
+        env: &BTreeMap<String, Val>,
+    ) -> Val {
+        let mut lhs = self.eval_unary(tokens, pos, env);
+        while let Some(op) = tokens
+            .get(*pos)
+            .filter(|t| *t == "*" || *t == "/" || *t == "%")
+            .cloned()
+        {
+            *pos += 1;
+            let (a, b) = (lhs.int(), self.eval_unary(tokens, pos, env).int());
+            lhs = Val::Int(match op.as_str() {
+                "*" => a.checked_mul(b).expect("multiplication overflow"),
+                "/" => a.checked_div(b).expect("division by zero"),
+                _ => a.checked_rem(b).expect("division by zero"),
+            });
+        }
+        lhs
+    }
+
+    fn eval_unary(
+        &mut self,
+        tokens: &[String],
+        pos: &mut usize,
+        env: &BTreeMap<String, Val>,
+    ) -> Val {
+        // values are never shared, so desnapping is a no-op
+        if tokens[*pos] == "*" {
+            *pos += 1;
+            return self.eval_unary(tokens, pos, env);
+        }
+        self.eval_postfix(tokens, pos, env)
+    }
+
+    fn eval_postfix(
+        &mut self,
+        tokens: &[String],
+        pos: &mut usize,
+        env: &BTreeMap<String, Val>,
+    ) -> Val {
+        let mut token = tokens[*pos].clone();
+        *pos += 1;
+
+        let mut value = if token == "(" {
+            let value = self.eval_comparison(tokens, pos, env);
+            expect(tokens, pos, ")");
+            value
+        } else if token.chars().next().unwrap().is_ascii_digit() {
+            Val::Int(token.parse::<u128>().unwrap())
+        } else if let Some(text) = token.strip_prefix('\'') {
+            Val::Str(text.trim_end_matches('\'').to_string())
+        } else {
+            // paths such as `ArrayTrait::new`
+            while tokens.get(*pos).map(String::as_str) == Some(":") {
+                expect(tokens, pos, ":");
+                expect(tokens, pos, ":");
+                token = format!("{token}::{}", tokens[*pos]);
+                *pos += 1;
+            }
+
+            match tokens.get(*pos).map(String::as_str) {
+                Some("(") => {
+                    let args = self.eval_args(tokens, pos, env);
+                    self.call(&token, args)
+                }
+                Some("{") if self.structs.contains_key(&token) => {
+                    *pos += 1;
+                    let mut fields = BTreeMap::new();
+                    while tokens[*pos] != "}" {
+                        let field = tokens[*pos].clone();
+                        *pos += 1;
+                        expect(tokens, pos, ":");
+                        fields.insert(field, self.eval_comparison(tokens, pos, env));
+                        if tokens[*pos] == "," {
+                            *pos += 1;
+                        }
+                    }
+                    *pos += 1;
+                    let values = self.structs[&token]
+                        .iter()
+                        .map(|field| fields.remove(field).expect("missing field"))
+                        .collect();
+                    Val::Struct(token, values)
+                }
+                _ => env
+                    .get(&token)
+                    .or_else(|| self.constants.get(&token))
+                    .unwrap_or_else(|| panic!("unknown identifier {token}"))
+                    .clone(),
+            }
+        };
+
+        while tokens.get(*pos).map(String::as_str) == Some(".") {
+            let member = tokens[*pos + 1].clone();
+            *pos += 2;
+            if tokens.get(*pos).map(String::as_str) == Some("(") {
+                let args = self.eval_args(tokens, pos, env);
+                value = method(&value, &member, &args);
+            } else {
+                value = match value {
+                    Val::Struct(name, values) => {
+                        let idx = self.structs[&name]
+                            .iter()
+                            .position(|f| *f == member)
+                            .unwrap_or_else(|| panic!("unknown field {name}.{member}"));
+                        values[idx].clone()
+                    }
+                    value => panic!("expected a struct, found {value:?}"),
+                };
+            }
+        }
+        value
+    }
+
+    /// Evaluates a parenthesized list of arguments.
+    fn eval_args(
+        &mut self,
+        tokens: &[String],
+        pos: &mut usize,
+        env: &BTreeMap<String, Val>,
+    ) -> Vec<Val> {
+        expect(tokens, pos, "(");
+        let mut args = Vec::new();
+        while tokens[*pos] != ")" {
+            args.push(self.eval_comparison(tokens, pos, env));
+            if tokens[*pos] == "," {
+                *pos += 1;
+            }
+        }
+        *pos += 1;
+        args
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Implements the constructor of arrays and the `assert` function.
+fn builtin(name: &str, args: &[Val]) -> Option<Val> {
+    match name {
+        "ArrayTrait::new" => Some(Val::Array(Vec::new())),
+        "assert" => {
+            assert!(args[0].bool(), "assertion failed: {:?}", args[1]);
+            Some(Val::Array(Vec::new()))
+        }
+        _ => None,
+    }
+}
+
+/// Implements the methods of arrays and spans, and the conversions of integers.
+fn method(value: &Val, name: &str, args: &[Val]) -> Val {
+    match name {
+        "at" => value
+            .elements()
+            .get(args[0].index())
+            .unwrap_or_else(|| panic!("index {} out of bounds", args[0].index()))
+            .clone(),
+        "span" => value.clone(),
+        "slice" => {
+            let (start, len) = (args[0].index(), args[1].index());
+            Val::Array(value.elements()[start..start + len].to_vec())
+        }
+        "into" | "unwrap" => value.clone(),
+        "try_into" => {
+            assert!(
+                value.int() <= u64::MAX as u128,
+                "{value:?} does not fit in a u64"
+            );
+            value.clone()
+        }
+        _ => panic!("unsupported method {name}"),
+    }
+}
+
+// PARSING
+// ================================================================================================
+
+/// Parses the statements of a block up to its closing brace, which is not consumed.
+fn parse_block(tokens: &[String], pos: &mut usize) -> Block {
+    let mut block = Block::default();
+    while *pos < tokens.len() && tokens[*pos] != "}" {
+        let statement = match tokens[*pos].as_str() {
+            "let" => {
+                *pos += 1;
+                if tokens[*pos] == "mut" {
+                    *pos += 1;
+                }
+                let name = tokens[*pos].clone();
+                // skip the type annotation, if any
+                while tokens[*pos] != "=" {
+                    *pos += 1;
+                }
+                *pos += 1;
+                let expr = take_until(tokens, pos, &[";"]);
+                expect(tokens, pos, ";");
+                block.statements.push(Stmt::Let(name, expr));
+                continue;
+            }
+            "if" => {
+                *pos += 1;
+                let condition = take_until(tokens, pos, &["{"]);
+                let then_block = parse_braced_block(tokens, pos);
+                let else_block = if tokens.get(*pos).map(String::as_str) == Some("else") {
+                    *pos += 1;
+                    Some(parse_braced_block(tokens, pos))
+                } else {
+                    None
+                };
+                Stmt::If(condition, then_block, else_block)
+            }
+            "while" => {
+                *pos += 1;
+                let condition = take_until(tokens, pos, &["{"]);
+                Stmt::While(condition, parse_braced_block(tokens, pos))
+            }
+            _ => {
+                let expr = take_until(tokens, pos, &[";", "}"]);
+                match expr.get(1).map(String::as_str) {
+                    Some("=") => Stmt::Assign(expr[0].clone(), expr[2..].to_vec()),
+                    _ => Stmt::Expr(expr),
+                }
+            }
+        };
+
+        // a statement which is not terminated by a semicolon and ends the block is its tail,
+        // unless it is a loop
+        match tokens.get(*pos).map(String::as_str) {
+            Some(";") => {
+                *pos += 1;
+                block.statements.push(statement);
+            }
+            None | Some("}") if !matches!(statement, Stmt::While(..)) => {
+                block.tail = Some(Box::new(statement));
+            }
+            _ => block.statements.push(statement),
+        }
+    }
+    block
+}
+
+/// Parses a block enclosed in braces, consuming the braces.
+fn parse_braced_block(tokens: &[String], pos: &mut usize) -> Block {
+    expect(tokens, pos, "{");
+    let block = parse_block(tokens, pos);
+    expect(tokens, pos, "}");
+    block
+}
+
+/// Returns the tokens up to the first of the specified tokens which is not nested in brackets,
+/// which is not consumed.
+fn take_until(tokens: &[String], pos: &mut usize, ends: &[&str]) -> Vec<String> {
+    let start = *pos;
+    let mut depth = 0;
+    while let Some(token) = tokens.get(*pos) {
+        if depth == 0 && ends.contains(&token.as_str()) {
+            break;
+        }
+        match token.as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => depth -= 1,
+            _ => (),
+        }
+        *pos += 1;
+    }
+    tokens[start..*pos].to_vec()
+}
+
+/// Splits the source into tokens, dropping comments. Short strings are single tokens, quotes
+/// included.
+fn tokenize(source: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            let mut token = c.to_string();
+            while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                token.push(c);
+            }
+            tokens.push(token);
+        } else if c == '\'' {
+            let mut token = c.to_string();
+            for c in chars.by_ref() {
+                token.push(c);
+                if c == '\'' {
+                    break;
+                }
+            }
+            tokens.push(token);
+        } else if c == '/' && chars.peek() == Some(&'/') {
+            chars.find(|c| *c == '\n');
+        } else if let Some(next) =
+            chars.next_if(|next| matches!(c, '=' | '!' | '>' | '<') && *next == '=')
+        {
+            tokens.push(format!("{c}{next}"));
+        } else {
+            tokens.push(c.to_string());
+        }
+    }
+    tokens
+}
+
+fn expect(tokens: &[String], pos: &mut usize, token: &str) {
+    assert_eq!(tokens[*pos], token, "expected {token}");
+    *pos += 1;
+}
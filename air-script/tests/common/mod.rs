@@ -0,0 +1,131 @@
+//! Helpers shared by the tests of the code generators, which evaluate the generated code against
+//! Winterfell. Each test crate includes this module with `#[path = "../common/mod.rs"]` and uses
+//! only some of the helpers.
+#![allow(dead_code)]
+
+use air_script::{compiler::Target, AirIR, Compiler, CompilerOptions};
+use winter_air::{
+    AirContext, FieldExtension, ProofOptions, TraceInfo, TraceLayout, TransitionConstraintDegree,
+};
+use winter_math::fields::{f64::BaseElement as Felt, QuadExtension};
+
+pub type Ext2 = QuadExtension<Felt>;
+
+/// Returns the Winterfell context of the AIR described by the IR, with the degrees of its
+/// integrity constraints, the number of its boundary constraints and its transition exemptions.
+pub fn air_context(ir: &AirIR, trace_length: usize) -> AirContext<Felt> {
+    let degrees = |segment: u8| {
+        ir.integrity_constraint_degrees(segment)
+            .iter()
+            .map(|degree| {
+                TransitionConstraintDegree::with_cycles(degree.base(), degree.cycles().to_vec())
+            })
+            .collect::<Vec<_>>()
+    };
+    let widths = ir.trace_segment_widths();
+    let options = ProofOptions::new(27, 8, 0, FieldExtension::Quadratic, 4, 32);
+    let context = if widths.len() == 1 {
+        let trace_info = TraceInfo::new(widths[0] as usize, trace_length);
+        AirContext::new(
+            trace_info,
+            degrees(0),
+            ir.num_boundary_constraints(0),
+            options,
+        )
+    } else {
+        let layout = TraceLayout::new(
+            widths[0] as usize,
+            [widths[1] as usize],
+            [ir.num_random_values() as usize],
+        );
+        let trace_info = TraceInfo::new_multi_segment(layout, trace_length, vec![]);
+        AirContext::new_multi_segment(
+            trace_info,
+            degrees(0),
+            degrees(1),
+            ir.num_boundary_constraints(0),
+            ir.num_boundary_constraints(1),
+            options,
+        )
+    };
+    context.set_num_transition_exemptions(ir.num_transition_exemptions())
+}
+
+/// Returns a deterministic sequence of pseudo-random field elements.
+pub fn rand_elements(seed: u64, n: usize) -> Vec<Felt> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            Felt::new(state)
+        })
+        .collect()
+}
+
+/// Returns the elements of the quadratic extension made of consecutive pairs of the provided
+/// base field elements.
+pub fn ext2_elements(values: &[Felt]) -> Vec<Ext2> {
+    values
+        .chunks(2)
+        .map(|pair| Ext2::new(pair[0], pair[1]))
+        .collect()
+}
+
+/// Returns a deterministic sequence of pseudo-random elements of the quadratic extension.
+pub fn rand_ext2_elements(seed: u64, n: usize) -> Vec<Ext2> {
+    ext2_elements(&rand_elements(seed, 2 * n))
+}
+
+/// Returns the composition coefficients for `n` constraints, which the generated code takes as
+/// base field elements.
+pub fn coefficients(seed: u64, n: usize) -> (Vec<Felt>, Vec<Felt>, Vec<(Ext2, Ext2)>) {
+    let a = rand_elements(seed, n);
+    let b = rand_elements(seed + 1, n);
+    let coeffs = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| (Ext2::from(*a), Ext2::from(*b)))
+        .collect();
+    (a, b, coeffs)
+}
+
+/// Returns the source of an AIR whose constraints are built from `depth` nested variables, each of
+/// which uses the previous one twice, so that writing out every use of the variables doubles the
+/// size of the constraints with each level.
+pub fn nested_variables_source(depth: usize) -> String {
+    let mut source = "def NestedVariablesAir
+trace_columns:
+    main: [a, b]
+public_inputs:
+    stack_inputs: [16]
+boundary_constraints:
+    enf a.first = 0
+integrity_constraints:
+    let x0 = a + b
+"
+    .to_string();
+    for i in 1..=depth {
+        source += &format!("    let x{i} = x{} + x{} * b\n", i - 1, i - 1);
+    }
+    source += &format!("    enf a' = x{depth}\n");
+    source
+}
+
+/// Asserts that the size of the code generated for the target grows linearly with the depth of
+/// the nested variables of the AIR, i.e. that every node of the constraints is written once.
+pub fn assert_linear_in_nested_variables(target: Target) {
+    let generate = |depth: usize| {
+        let options = CompilerOptions::default().with_targets([target]);
+        let output = Compiler::new(options)
+            .compile_source(&nested_variables_source(depth))
+            .unwrap();
+        output.get(target).unwrap().len()
+    };
+    let (small, large) = (generate(8), generate(16));
+    assert!(
+        large <= 2 * small,
+        "{target:?} output grows from {small} to {large} bytes when doubling the nesting depth"
+    );
+}
@@ -0,0 +1,541 @@
+use air_script::{
+    compiler::{Pass, Target},
+    manifest::{
+        Binding, ConstraintDegree, PeriodicColumnLayout, PublicInputLayout, RandomValuesLayout,
+        MANIFEST_VERSION,
+    },
+    CodeGenerator, CompileError, Compiler, CompilerOptions, DocFormat, Manifest, Specification,
+};
+use ir::{
+    constraints::{ConstraintDomain, Operation},
+    AirIR, CodegenError, ConstraintLocation, TraceAccess, Value,
+};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn compile_requested_targets() {
+    let options = CompilerOptions::default().with_targets([Target::Winterfell, Target::Cairo0]);
+    let output = Compiler::new(options)
+        .compile_file("tests/binary/binary.air")
+        .unwrap();
+
+    assert_eq!(
+        output.iter().map(|(target, _)| target).collect::<Vec<_>>(),
+        vec![Target::Winterfell, Target::Cairo0]
+    );
+    assert_eq!(
+        output.get(Target::Winterfell).unwrap(),
+        CodeGenerator::new(output.ir()).generate().unwrap()
+    );
+}
+
+#[test]
+fn compile_default_target() {
+    let output = Compiler::default()
+        .compile_file("tests/binary/binary.air")
+        .unwrap();
+
+    assert!(output.get(Target::Winterfell).is_some());
+    assert!(output.get(Target::Cairo0).is_none());
+}
+
+#[test]
+fn compile_from_include_path() {
+    let options = CompilerOptions::default().with_include_path("tests/binary");
+    let output = Compiler::new(options).compile_file("binary.air").unwrap();
+
+    assert_eq!(output.ir().air_name(), "BinaryAir");
+}
+
+#[test]
+fn err_source_not_found() {
+    let result = Compiler::default().compile_file("binary.air");
+
+    assert!(matches!(result, Err(CompileError::SourceNotFound(_))));
+}
+
+#[test]
+fn err_parse() {
+    let result = Compiler::default().compile_source("def Broken\ntrace_columns:");
+
+    assert!(matches!(result, Err(CompileError::Parse(_))));
+}
+
+#[test]
+fn err_semantic() {
+    let source = "
+    def MissingPublicInputs
+    trace_columns:
+        main: [a]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + 1";
+    let result = Compiler::default().compile_source(source);
+
+    assert!(matches!(result, Err(CompileError::Semantic(_))));
+}
+
+#[test]
+fn err_semantic_diagnostic() {
+    let source = "
+def UndeclaredVariable
+trace_columns:
+    main: [a]
+public_inputs:
+    stack_inputs: [16]
+boundary_constraints:
+    enf a.first = 0
+integrity_constraints:
+    let x = a + 1
+    enf a' = y";
+    let err = Compiler::default().compile_source(source).unwrap_err();
+
+    // the diagnostic points at the statement in which the error was found.
+    assert!(matches!(err, CompileError::Semantic(_)));
+    assert_eq!(err.diagnostic().line_col(source), Some((11, 5)));
+}
+
+#[test]
+fn passes_are_applied_in_order() {
+    struct Rename(&'static str);
+    impl Pass for Rename {
+        fn name(&self) -> &str {
+            "rename"
+        }
+
+        fn run(&self, ir: &mut AirIR) -> Result<(), String> {
+            ir.air_name = self.0.to_string();
+            Ok(())
+        }
+    }
+
+    let options = CompilerOptions::default()
+        .with_pass(Rename("FirstAir"))
+        .with_pass(Rename("SecondAir"));
+    let output = Compiler::new(options)
+        .compile_file("tests/binary/binary.air")
+        .unwrap();
+
+    assert_eq!(output.ir().air_name(), "SecondAir");
+    assert!(output
+        .get(Target::Winterfell)
+        .unwrap()
+        .contains("pub struct SecondAir"));
+}
+
+#[test]
+fn err_pass() {
+    struct Reject;
+    impl Pass for Reject {
+        fn name(&self) -> &str {
+            "reject"
+        }
+
+        fn run(&self, _ir: &mut AirIR) -> Result<(), String> {
+            Err("not supported".to_string())
+        }
+    }
+
+    let options = CompilerOptions::default().with_pass(Reject);
+    let result = Compiler::new(options).compile_file("tests/binary/binary.air");
+
+    match result {
+        Err(CompileError::Pass(name, message)) => {
+            assert_eq!(name, "reject");
+            assert_eq!(message, "not supported");
+        }
+        _ => panic!("expected the pass to fail"),
+    }
+}
+
+/// Moves every access to the next row of the trace two rows ahead, which is valid in the IR but
+/// cannot be evaluated against the two-row frames of the targets.
+struct SkipRow;
+impl Pass for SkipRow {
+    fn name(&self) -> &str {
+        "skip-row"
+    }
+
+    fn run(&self, ir: &mut AirIR) -> Result<(), String> {
+        for node in ir.constraints.graph.nodes.iter_mut() {
+            if let Operation::Value(Value::TraceElement(access)) = node.op {
+                if access.row_offset() == 1 {
+                    node.op = Operation::Value(Value::TraceElement(TraceAccess::new(
+                        access.trace_segment(),
+                        access.col_idx(),
+                        access.size(),
+                        2,
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+const COUNTER_AIR: &str = "
+def CounterAir
+trace_columns:
+    main: [a, b]
+public_inputs:
+    stack_inputs: [16]
+boundary_constraints:
+    enf a.first = 0
+integrity_constraints:
+    enf b^2 - b = 0
+    enf a' = a + 1";
+
+#[test]
+fn err_codegen() {
+    for target in [
+        Target::Winterfell,
+        Target::Cairo0,
+        Target::Cairo1,
+        Target::Masm,
+        Target::Solidity,
+        Target::Plonky3,
+    ] {
+        let options = CompilerOptions::default()
+            .with_targets([target])
+            .with_pass(SkipRow);
+        let result = Compiler::new(options).compile_source(COUNTER_AIR);
+
+        match result {
+            Err(CompileError::Codegen(err_target, err)) => {
+                assert_eq!(err_target, target);
+                assert!(matches!(err, CodegenError::UnsupportedFeature(..)));
+                assert_eq!(err.constraint(), Some(&ConstraintLocation::integrity(0, 1)));
+                let diagnostic = CompileError::Codegen(err_target, err).diagnostic();
+                assert_eq!(
+                    diagnostic.message(),
+                    format!(
+                        "failed to generate {target} code: row offsets greater than 1 are not \
+                        supported, but are used by integrity constraint 1 of trace segment 0"
+                    )
+                );
+                // the diagnostic points at the statement of the constraint.
+                assert_eq!(diagnostic.line_col(COUNTER_AIR), Some((11, 5)));
+            }
+            _ => panic!("expected code generation for {target} to fail"),
+        }
+    }
+}
+
+#[test]
+fn err_codegen_boundary_domain() {
+    struct EveryRow;
+    impl Pass for EveryRow {
+        fn name(&self) -> &str {
+            "every-row"
+        }
+
+        fn run(&self, ir: &mut AirIR) -> Result<(), String> {
+            ir.constraints.boundary_constraints[0][0].domain = ConstraintDomain::EveryRow;
+            Ok(())
+        }
+    }
+
+    for target in [
+        Target::Winterfell,
+        Target::Cairo0,
+        Target::Cairo1,
+        Target::Masm,
+        Target::Solidity,
+        Target::Plonky3,
+    ] {
+        let options = CompilerOptions::default()
+            .with_targets([target])
+            .with_pass(EveryRow);
+        let result = Compiler::new(options).compile_source(COUNTER_AIR);
+
+        match result {
+            Err(CompileError::Codegen(_, err)) => {
+                assert!(matches!(err, CodegenError::InvalidConstraint(..)));
+                assert_eq!(err.constraint(), Some(&ConstraintLocation::boundary(0, 0)));
+            }
+            _ => panic!("expected code generation for {target} to fail"),
+        }
+    }
+}
+
+// MANIFEST
+// ================================================================================================
+
+fn binding(name: &str, index: usize, size: usize) -> Binding {
+    Binding {
+        name: name.to_string(),
+        index,
+        size,
+    }
+}
+
+#[test]
+fn manifest_describes_layout() {
+    let source = "
+    def LayoutAir
+    trace_columns:
+        main: [a, b[2]]
+        aux: [c]
+    public_inputs:
+        stack_inputs: [16]
+        stack_outputs: [4]
+    periodic_columns:
+        k0: [1, 0, 0, 0]
+    random_values:
+        alphas: [x, y[2]]
+    boundary_constraints:
+        enf a.first = 0
+        enf c.first = x
+        enf c.last = 1
+    integrity_constraints:
+        enf a' = a + k0 * b[0]
+        enf b[1]^2 - b[1] = 0
+        enf c' = c * (a + y[1])";
+    let output = Compiler::default().compile_source(source).unwrap();
+    let manifest = output.manifest();
+
+    assert_eq!(manifest.air_name, "LayoutAir");
+    assert_eq!(manifest.trace_segments.len(), 2);
+
+    let main = &manifest.trace_segments[0];
+    assert_eq!(main.width, 3);
+    assert_eq!(main.columns, vec![binding("a", 0, 1), binding("b", 1, 2)]);
+    assert_eq!(main.num_boundary_constraints, 1);
+    assert_eq!(
+        main.integrity_constraint_degrees,
+        vec![
+            ConstraintDegree {
+                base: 1,
+                cycles: vec![4]
+            },
+            ConstraintDegree {
+                base: 2,
+                cycles: vec![]
+            },
+        ]
+    );
+
+    let aux = &manifest.trace_segments[1];
+    assert_eq!(aux.width, 1);
+    assert_eq!(aux.columns, vec![binding("c", 0, 1)]);
+    assert_eq!(aux.num_boundary_constraints, 2);
+    assert_eq!(aux.integrity_constraint_degrees.len(), 1);
+    assert_eq!(manifest.num_transition_exemptions, 1);
+
+    assert_eq!(
+        manifest.public_inputs,
+        vec![
+            PublicInputLayout {
+                name: "stack_inputs".to_string(),
+                size: 16
+            },
+            PublicInputLayout {
+                name: "stack_outputs".to_string(),
+                size: 4
+            },
+        ]
+    );
+    assert_eq!(
+        manifest.random_values,
+        Some(RandomValuesLayout {
+            name: "alphas".to_string(),
+            size: 3,
+            bindings: vec![binding("x", 0, 1), binding("y", 1, 2)],
+        })
+    );
+    assert_eq!(
+        manifest.periodic_columns,
+        vec![PeriodicColumnLayout {
+            name: "k0".to_string(),
+            index: 0,
+            cycle_length: 4
+        }]
+    );
+
+    // the manifest round-trips through its JSON representation
+    assert_eq!(Manifest::from_json(&manifest.to_json()).unwrap(), manifest);
+}
+
+#[test]
+fn manifest_transition_exemptions() {
+    let output = Compiler::default()
+        .compile_file("tests/transition_exemptions/transition_exemptions.air")
+        .unwrap();
+    let manifest = output.manifest();
+
+    assert_eq!(manifest.version, MANIFEST_VERSION);
+    assert_eq!(manifest.num_transition_exemptions, 3);
+    assert!(manifest
+        .to_json()
+        .contains("\"num_transition_exemptions\": 3"));
+}
+
+#[test]
+fn manifest_without_random_values() {
+    let output = Compiler::default()
+        .compile_file("tests/binary/binary.air")
+        .unwrap();
+    let manifest = output.manifest();
+
+    assert_eq!(manifest.random_values, None);
+    assert!(manifest.periodic_columns.is_empty());
+    assert_eq!(
+        manifest.trace_segments[0].columns,
+        vec![binding("a", 0, 1), binding("b", 1, 1)]
+    );
+}
+
+// SPECIFICATION
+// ================================================================================================
+
+#[test]
+fn spec_markdown() {
+    let source = std::fs::read_to_string("tests/aux_trace/aux_trace.air").unwrap();
+    let output = Compiler::default().compile_source(&source).unwrap();
+    let spec = Specification::new(output.ir())
+        .with_source(&source)
+        .render(DocFormat::Markdown);
+
+    assert!(spec.starts_with("# AuxiliaryAir\n"));
+    assert!(spec.contains("| aux | 1 | `p1` | 1 |"));
+    assert!(spec.contains("| `stack_inputs` | 16 |"));
+    assert!(spec.contains("| `rand` | 0 | 2 |"));
+    assert!(spec.contains("| 2 | `p1 = rand[0]` | the first row | 1 | line 21 |"));
+    assert!(spec.contains(
+        "| 0 | `a' = b + a * b * c` | every frame of 2 consecutive rows | 3 | line 25 |"
+    ));
+    assert!(spec.contains(
+        "| 1 | `p1 = p1' * (c + rand[0])` | every frame of 2 consecutive rows | 2 | line 31 |"
+    ));
+    assert!(!spec.contains("## Periodic columns"));
+}
+
+#[test]
+fn spec_latex() {
+    let source = std::fs::read_to_string("tests/periodic_columns/periodic_columns.air").unwrap();
+    let output = Compiler::default().compile_source(&source).unwrap();
+    let spec = Specification::new(output.ir())
+        .with_source(&source)
+        .render(DocFormat::Latex);
+
+    assert!(spec.starts_with("\\section*{PeriodicColumnsAir}\n"));
+    assert!(spec.contains("\\# & Constraint & Domain & Degree & Source \\\\\n"));
+    assert!(spec.contains("$\\mathit{stack\\_inputs}$ & 16 \\\\\n"));
+    assert!(spec.contains("$\\mathit{k1}$ & 8 & $1, 1, 1, 1, 1, 1, 1, 0$ \\\\\n"));
+    assert!(spec.contains(
+        "1 & $\\mathit{k1} \\cdot (a' - a) = 0$ & every frame of 2 consecutive rows & \
+        1 (cycles 8) & line 18 \\\\\n"
+    ));
+}
+
+#[test]
+fn spec_source_lines_from_spans() {
+    // the section headers and statements are not laid out one per line
+    let source = "def LayoutAir
+trace_columns:
+    main: [a, b]
+public_inputs:
+    stack_inputs: [16]
+boundary_constraints :
+    enf a.first = 0
+integrity_constraints :
+    let x = a + b
+    enf a' = x
+    enf b' =
+        a
+";
+    let output = Compiler::default().compile_source(source).unwrap();
+    let spec = Specification::new(output.ir())
+        .with_source(source)
+        .render(DocFormat::Markdown);
+
+    assert!(spec.contains("| 0 | `a = 0` | the first row | 1 | line 7 |"));
+    assert!(spec.contains("| 0 | `a' = a + b` | every frame of 2 consecutive rows | 1 | line 10 |"));
+    assert!(spec.contains("| 1 | `b' = a` | every frame of 2 consecutive rows | 1 | line 11 |"));
+}
+
+#[test]
+fn spec_without_source() {
+    let output = Compiler::default()
+        .compile_file("tests/aux_trace/aux_trace.air")
+        .unwrap();
+    let spec = Specification::new(output.ir()).render(DocFormat::Markdown);
+
+    assert!(
+        spec.contains("| 3 | `p1 = 1` | the last row | 1 | statement 6 of boundary_constraints |")
+    );
+    assert!(
+        spec.contains("| 2 | `c = a + b` | every row | 1 | statement 3 of integrity_constraints |")
+    );
+}
+
+#[test]
+fn spec_format_from_str() {
+    assert_eq!("md".parse::<DocFormat>(), Ok(DocFormat::Markdown));
+    assert_eq!("LaTeX".parse::<DocFormat>(), Ok(DocFormat::Latex));
+    assert!("html".parse::<DocFormat>().is_err());
+}
+
+// BUILD HELPERS
+// ================================================================================================
+
+/// Points the build helpers at a temporary output directory, as cargo does for build scripts.
+fn set_out_dir() -> std::path::PathBuf {
+    let out_dir = std::env::temp_dir().join("air-script-build-tests");
+    std::fs::create_dir_all(&out_dir).unwrap();
+    std::env::set_var("OUT_DIR", &out_dir);
+    out_dir
+}
+
+#[test]
+fn build_compile_air() {
+    let out_dir = set_out_dir();
+    let path = air_script::build::compile_air("tests/binary/binary.air").unwrap();
+
+    assert_eq!(path, out_dir.join("binary.rs"));
+    let expected = Compiler::default()
+        .compile_file("tests/binary/binary.air")
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(path).unwrap(),
+        expected.get(Target::Winterfell).unwrap()
+    );
+}
+
+#[test]
+fn build_compile_air_from_include_path() {
+    let out_dir = set_out_dir();
+    let options = CompilerOptions::default().with_include_path("tests/system");
+    let paths = air_script::build::compile_air_with_options("system.air", options).unwrap();
+
+    assert_eq!(paths, vec![out_dir.join("system.rs")]);
+    let expected = Compiler::default()
+        .compile_file("tests/system/system.air")
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&paths[0]).unwrap(),
+        expected.get(Target::Winterfell).unwrap()
+    );
+}
+
+#[test]
+fn build_err_renders_source_location() {
+    set_out_dir();
+    let source_dir = std::env::temp_dir().join("air-script-build-tests-src");
+    std::fs::create_dir_all(&source_dir).unwrap();
+    let path = source_dir.join("broken.air");
+    std::fs::write(&path, "def Broken\ntrace_columns:\n    main: [a@]\n").unwrap();
+
+    let err = air_script::build::compile_air(&path).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "error: invalid character\n --> {}:3:13\n  |\n3 |     main: [a@]\n  |             ^\n",
+            path.display()
+        )
+    );
+}
@@ -1,13 +1,94 @@
 use winter_air::{Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, ProofOptions as WinterProofOptions, TransitionConstraintDegree, TraceInfo};
 use winter_math::fields::f64::BaseElement as Felt;
-use winter_math::{ExtensionOf, FieldElement};
+use winter_math::{ExtensionOf, FieldElement, ToElements};
 use winter_utils::collections::Vec;
-use winter_utils::{ByteWriter, Serializable};
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
 const A: Felt = Felt::ONE;
 const B: [Felt; 2] = [Felt::ZERO, Felt::ONE];
 const C: [[Felt; 2]; 2] = [[Felt::ONE, Felt::new(2)], [Felt::new(2), Felt::ZERO]];
 
+pub mod columns {
+    //! The indices of the columns of the execution trace, by their declared names.
+
+    /// The number of columns of the main trace.
+    pub const MAIN_WIDTH: usize = 4;
+    /// The number of columns of the auxiliary trace.
+    pub const AUX_WIDTH: usize = 3;
+
+    /// The index of the `a` column of the main trace.
+    pub const A: usize = 0;
+
+    /// The index of the `b` column of the main trace.
+    pub const B: usize = 1;
+
+    /// The index of the `c` column of the main trace.
+    pub const C: usize = 2;
+
+    /// The index of the `d` column of the main trace.
+    pub const D: usize = 3;
+
+    /// The index of the `e` column of the auxiliary trace.
+    pub const E: usize = 0;
+
+    /// The index of the `f` column of the auxiliary trace.
+    pub const F: usize = 1;
+
+    /// The index of the `g` column of the auxiliary trace.
+    pub const G: usize = 2;
+}
+
+/// A row of the main trace, whose columns are set by their declared names.
+#[derive(Debug, Clone, Copy)]
+pub struct MainTraceRow {
+    values: [Felt; columns::MAIN_WIDTH],
+}
+
+impl MainTraceRow {
+    /// Returns a row whose columns are all zero.
+    pub fn new() -> Self {
+        Self {
+            values: [Felt::ZERO; columns::MAIN_WIDTH],
+        }
+    }
+
+    /// Sets the value of the `a` column.
+    pub fn set_a(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::A] = value;
+        self
+    }
+
+    /// Sets the value of the `b` column.
+    pub fn set_b(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::B] = value;
+        self
+    }
+
+    /// Sets the value of the `c` column.
+    pub fn set_c(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::C] = value;
+        self
+    }
+
+    /// Sets the value of the `d` column.
+    pub fn set_d(&mut self, value: Felt) -> &mut Self {
+        self.values[columns::D] = value;
+        self
+    }
+
+    /// Returns the values of the columns of the row, in the order of the main trace.
+    pub fn values(&self) -> &[Felt; columns::MAIN_WIDTH] {
+        &self.values
+    }
+}
+
+impl Default for MainTraceRow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The public inputs of the AIR, laid out as elements in the order of declaration.
 pub struct PublicInputs {
     program_hash: [Felt; 4],
     stack_inputs: [Felt; 4],
@@ -16,9 +97,28 @@ pub struct PublicInputs {
 }
 
 impl PublicInputs {
+    pub const NUM_ELEMENTS: usize = 32;
     pub fn new(program_hash: [Felt; 4], stack_inputs: [Felt; 4], stack_outputs: [Felt; 20], overflow_addrs: [Felt; 4]) -> Self {
         Self { program_hash, stack_inputs, stack_outputs, overflow_addrs }
     }
+
+    /// Returns the public inputs laid out in the provided elements, or an error if the
+    /// number of elements does not match the sizes of the public inputs.
+    pub fn from_elements(elements: &[Felt]) -> Result<Self, String> {
+        if elements.len() != Self::NUM_ELEMENTS {
+            return Err(format!(
+                "expected {} public input elements, found {}",
+                Self::NUM_ELEMENTS,
+                elements.len()
+            ));
+        }
+        Ok(Self {
+            program_hash: elements[0..4].try_into().unwrap(),
+            stack_inputs: elements[4..8].try_into().unwrap(),
+            stack_outputs: elements[8..28].try_into().unwrap(),
+            overflow_addrs: elements[28..32].try_into().unwrap(),
+        })
+    }
 }
 
 impl Serializable for PublicInputs {
@@ -30,6 +130,26 @@ impl Serializable for PublicInputs {
     }
 }
 
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let elements = (0..Self::NUM_ELEMENTS)
+            .map(|_| Felt::read_from(source))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_elements(&elements).map_err(DeserializationError::InvalidValue)
+    }
+}
+
+impl ToElements<Felt> for PublicInputs {
+    fn to_elements(&self) -> Vec<Felt> {
+        let mut result = Vec::new();
+        result.extend_from_slice(&self.program_hash);
+        result.extend_from_slice(&self.stack_inputs);
+        result.extend_from_slice(&self.stack_outputs);
+        result.extend_from_slice(&self.overflow_addrs);
+        result
+    }
+}
+
 pub struct ConstantsAir {
     context: AirContext<Felt>,
     program_hash: [Felt; 4],
@@ -66,7 +186,7 @@ impl Air for ConstantsAir {
             num_aux_assertions,
             options,
         )
-        .set_num_transition_exemptions(2);
+        .set_num_transition_exemptions(1);
         Self { context, program_hash: public_inputs.program_hash, stack_inputs: public_inputs.stack_inputs, stack_outputs: public_inputs.stack_outputs, overflow_addrs: public_inputs.overflow_addrs }
     }
 
@@ -75,18 +195,19 @@ impl Air for ConstantsAir {
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Felt>> {
+        type E = Felt;
         let mut result = Vec::new();
-        result.push(Assertion::single(0, 0, A));
-        result.push(Assertion::single(1, 0, A + B[0] * C[0][1]));
-        result.push(Assertion::single(2, 0, (B[0] - C[1][1]) * A));
-        result.push(Assertion::single(3, 0, A + B[0] - B[1] + C[0][0] - C[0][1] + C[1][0] - C[1][1]));
+        result.push(Assertion::<E>::single(0, 0, A.into()));
+        result.push(Assertion::<E>::single(1, 0, A + B[0] * C[0][1].into()));
+        result.push(Assertion::<E>::single(2, 0, (B[0] - C[1][1]) * A.into()));
+        result.push(Assertion::<E>::single(3, 0, A + B[0] - B[1] + C[0][0] - C[0][1] + C[1][0] - C[1][1].into()));
         result
     }
 
     fn get_aux_assertions<E: FieldElement<BaseField = Felt>>(&self, aux_rand_elements: &AuxTraceRandElements<E>) -> Vec<Assertion<E>> {
         let mut result = Vec::new();
-        result.push(Assertion::single(0, 0, E::from(A) + E::from(B[0]) * E::from(C[0][1])));
-        result.push(Assertion::single(0, self.last_step(), E::from(A) - E::from(B[1]) * E::from(C[0][0])));
+        result.push(Assertion::<E>::single(0, 0, E::from(A) + E::from(B[0]) * E::from(C[0][1]).into()));
+        result.push(Assertion::<E>::single(0, self.last_step(), E::from(A) - E::from(B[1]) * E::from(C[0][0]).into()));
         result
     }
 
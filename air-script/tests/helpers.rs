@@ -1,13 +1,24 @@
 use codegen_winter::CodeGenerator;
 use ir::AirIR;
 use parser::parse;
-use std::fs;
+use std::{fmt, fs};
 
 #[derive(Debug)]
 pub enum TestError {
     IO(String),
     Parse(String),
     IR(String),
+    Codegen(String),
+}
+
+impl fmt::Display for TestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IO(msg) | Self::Parse(msg) | Self::IR(msg) | Self::Codegen(msg) => {
+                write!(f, "{msg}")
+            }
+        }
+    }
 }
 
 pub struct Test {
@@ -20,6 +31,32 @@ impl Test {
     }
 
     pub fn transpile(&self) -> Result<String, TestError> {
+        let ir = self.build_ir()?;
+
+        // generate Rust code targeting Winterfell
+        let codegen = CodeGenerator::new(&ir);
+        codegen.generate().map_err(|err| {
+            TestError::Codegen(format!(
+                "Failed to generate Winterfell code for the input air file at {} - {}",
+                &self.input_path, err
+            ))
+        })
+    }
+
+    pub fn transpile_prover(&self, air_module: &str) -> Result<String, TestError> {
+        let ir = self.build_ir()?;
+
+        // generate the Winterfell prover for the Air in the specified module
+        let codegen = CodeGenerator::new(&ir);
+        codegen.generate_prover(air_module).map_err(|err| {
+            TestError::Codegen(format!(
+                "Failed to generate the Winterfell prover for the input air file at {} - {}",
+                &self.input_path, err
+            ))
+        })
+    }
+
+    fn build_ir(&self) -> Result<AirIR, TestError> {
         // load source input from file
         let source = fs::read_to_string(&self.input_path).map_err(|err| {
             TestError::IO(format!(
@@ -36,15 +73,11 @@ impl Test {
             ))
         })?;
 
-        let ir = AirIR::new(parsed).map_err(|_| {
+        AirIR::new(parsed).map_err(|_| {
             TestError::IR(format!(
                 "Failed to convert the input air file at {} to IR representation",
                 &self.input_path
             ))
-        })?;
-
-        // generate Rust code targeting Winterfell
-        let codegen = CodeGenerator::new(&ir);
-        Ok(codegen.generate())
+        })
     }
 }
@@ -1,7 +1,7 @@
 use expect_test::expect_file;
 
 mod helpers;
-use helpers::Test;
+use helpers::{Test, TestError};
 
 // TESTS
 // ================================================================================================
@@ -36,6 +36,16 @@ fn periodic_columns() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn periodic_aux() {
+    let generated_air = Test::new("tests/periodic_aux/periodic_aux.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["periodic_aux/periodic_aux.rs"];
+    expected.assert_eq(&generated_air);
+}
+
 #[test]
 fn pub_inputs() {
     let generated_air = Test::new("tests/pub_inputs/pub_inputs.air".to_string())
@@ -76,6 +86,17 @@ fn constants() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn transition_exemptions() {
+    let generated_air =
+        Test::new("tests/transition_exemptions/transition_exemptions.air".to_string())
+            .transpile()
+            .unwrap();
+
+    let expected = expect_file!["transition_exemptions/transition_exemptions.rs"];
+    expected.assert_eq(&generated_air);
+}
+
 #[test]
 fn variables() {
     let generated_air = Test::new("tests/variables/variables.air".to_string())
@@ -96,6 +117,29 @@ fn trace_col_groups() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn trace_col_groups_columns() {
+    let generated_air = Test::new("tests/trace_col_groups/trace_col_groups.air".to_string())
+        .transpile()
+        .unwrap();
+
+    // the column indices are named after the declared columns and groups of each segment.
+    assert!(generated_air.contains("pub const MAIN_WIDTH: usize = 4;"));
+    assert!(generated_air.contains("pub const AUX_WIDTH: usize = 5;"));
+    assert!(generated_air.contains("pub const CLK: usize = 0;"));
+    assert!(generated_air.contains("pub const FMP_START: usize = 1;"));
+    assert!(generated_air.contains("pub const FMP_LEN: usize = 2;"));
+    assert!(generated_air.contains("pub const CTX: usize = 3;"));
+    assert!(generated_air.contains("pub const C_START: usize = 2;"));
+    assert!(generated_air.contains("pub const C_LEN: usize = 3;"));
+
+    // the row builder has a setter for each column and group of the main trace only.
+    assert!(generated_air.contains("pub fn set_clk(&mut self, value: Felt) -> &mut Self {"));
+    assert!(generated_air
+        .contains("pub fn set_fmp(&mut self, values: [Felt; columns::FMP_LEN]) -> &mut Self {"));
+    assert!(!generated_air.contains("pub fn set_c("));
+}
+
 #[test]
 fn indexed_trace_access() {
     let generated_air =
@@ -143,3 +187,58 @@ fn list_folding() {
     let expected = expect_file!["list_folding/list_folding.rs"];
     expected.assert_eq(&generated_air);
 }
+
+#[test]
+fn shared_subexpressions() {
+    let generated_air =
+        Test::new("tests/shared_subexpressions/shared_subexpressions.air".to_string())
+            .transpile()
+            .unwrap();
+
+    let expected = expect_file!["shared_subexpressions/shared_subexpressions.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn pub_inputs_typed() {
+    let generated_air = Test::new("tests/pub_inputs/pub_inputs.air".to_string())
+        .transpile()
+        .unwrap();
+
+    // the public inputs are arrays of their declared sizes, split from elements in the order of
+    // declaration.
+    assert!(generated_air.contains("    stack_outputs: [Felt; 20],\n"));
+    assert!(generated_air.contains("pub const NUM_ELEMENTS: usize = 32;"));
+    assert!(
+        generated_air.contains("pub fn from_elements(elements: &[Felt]) -> Result<Self, String> {")
+    );
+    assert!(generated_air.contains("stack_outputs: elements[8..28].try_into().unwrap(),"));
+    assert!(generated_air.contains("impl Deserializable for PublicInputs {"));
+    assert!(generated_air.contains(
+        "result.extend_from_slice(&self.program_hash);\n        \
+        result.extend_from_slice(&self.stack_inputs);\n        \
+        result.extend_from_slice(&self.stack_outputs);\n        \
+        result.extend_from_slice(&self.overflow_addrs);"
+    ));
+}
+
+#[test]
+fn pub_inputs_prover() {
+    let generated_prover = Test::new("tests/pub_inputs/pub_inputs.air".to_string())
+        .transpile_prover("pub_inputs")
+        .unwrap();
+
+    let expected = expect_file!["pub_inputs/pub_inputs_prover.rs"];
+    expected.assert_eq(&generated_prover);
+}
+
+#[test]
+fn aux_trace_prover() {
+    let err = Test::new("tests/aux_trace/aux_trace.air".to_string())
+        .transpile_prover("aux_trace")
+        .unwrap_err();
+    assert!(matches!(err, TestError::Codegen(_)));
+    assert!(err
+        .to_string()
+        .contains("auxiliary trace segments in the generated prover"));
+}
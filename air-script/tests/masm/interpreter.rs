@@ -0,0 +1,116 @@
+//! A minimal interpreter for the subset of Miden assembly emitted by the MASM code generator. It
+//! is used to check the generated procedures against values computed with Winterfell's field
+//! types, since the Miden VM is not a dependency of this crate.
+
+use std::collections::BTreeMap;
+use winter_math::{
+    fields::{f64::BaseElement as Felt, QuadExtension},
+    FieldElement,
+};
+
+type Ext2 = QuadExtension<Felt>;
+
+pub struct Interpreter {
+    constants: BTreeMap<String, Felt>,
+    procedures: BTreeMap<String, (usize, Vec<String>)>,
+}
+
+impl Interpreter {
+    /// Parses the constants and the exported procedures of a module.
+    pub fn new(source: &str) -> Self {
+        let mut constants = BTreeMap::new();
+        let mut procedures = BTreeMap::new();
+        let mut current: Option<(String, usize, Vec<String>)> = None;
+
+        for line in source.lines() {
+            let line = line.split('#').next().unwrap().trim();
+            for token in line.split_whitespace() {
+                if let Some(declaration) = token.strip_prefix("const.") {
+                    let (name, value) = declaration.split_once('=').unwrap();
+                    constants.insert(name.to_string(), Felt::new(value.parse().unwrap()));
+                } else if let Some(header) = token.strip_prefix("export.") {
+                    let (name, num_locals) = match header.split_once('.') {
+                        Some((name, num_locals)) => (name, num_locals.parse().unwrap()),
+                        None => (header, 0),
+                    };
+                    current = Some((name.to_string(), num_locals, Vec::new()));
+                } else if token == "end" {
+                    let (name, num_locals, body) = current.take().expect("end outside procedure");
+                    procedures.insert(name, (num_locals, body));
+                } else {
+                    let (_, _, body) = current.as_mut().expect("instruction outside procedure");
+                    body.push(token.to_string());
+                }
+            }
+        }
+
+        Self {
+            constants,
+            procedures,
+        }
+    }
+
+    /// Executes the named procedure on an empty stack with the provided memory, and returns the
+    /// extension elements left on the stack, from the top down.
+    pub fn call(&self, name: &str, memory: &BTreeMap<u32, Felt>) -> Vec<Ext2> {
+        let (num_locals, body) = &self.procedures[name];
+        let mut locals = vec![Felt::ZERO; *num_locals];
+        let mut stack: Vec<Felt> = Vec::new();
+
+        for instruction in body.iter() {
+            let (op, args) = match instruction.split_once('.') {
+                Some((op, args)) => (op, args),
+                None => (instruction.as_str(), ""),
+            };
+            let arg = || -> usize { args.parse().unwrap() };
+            match op {
+                "push" => {
+                    for value in args.split('.') {
+                        let value = match self.constants.get(value) {
+                            Some(constant) => *constant,
+                            None => Felt::new(value.parse().unwrap()),
+                        };
+                        stack.push(value);
+                    }
+                }
+                "mem_load" => stack.push(memory[&(arg() as u32)]),
+                "loc_load" => stack.push(locals[arg()]),
+                "loc_store" => locals[arg()] = stack.pop().unwrap(),
+                "dup" => stack.push(stack[stack.len() - 1 - arg()]),
+                "movup" => {
+                    let value = stack.remove(stack.len() - 1 - arg());
+                    stack.push(value);
+                }
+                "drop" => {
+                    stack.pop().unwrap();
+                }
+                "ext2add" | "ext2sub" | "ext2mul" => {
+                    let b = pop_ext2(&mut stack);
+                    let a = pop_ext2(&mut stack);
+                    let c = match op {
+                        "ext2add" => a + b,
+                        "ext2sub" => a - b,
+                        _ => a * b,
+                    };
+                    stack.push(c.to_base_elements()[0]);
+                    stack.push(c.to_base_elements()[1]);
+                }
+                _ => panic!("unsupported instruction {instruction}"),
+            }
+        }
+
+        assert_eq!(stack.len() % 2, 0, "the stack holds extension elements");
+        let mut result = Vec::new();
+        while !stack.is_empty() {
+            result.push(pop_ext2(&mut stack));
+        }
+        result
+    }
+}
+
+/// Pops the extension element on top of the stack, whose high coefficient is on top.
+fn pop_ext2(stack: &mut Vec<Felt>) -> Ext2 {
+    let a1 = stack.pop().unwrap();
+    let a0 = stack.pop().unwrap();
+    Ext2::new(a0, a1)
+}
@@ -0,0 +1,164 @@
+use air_script::{compiler::Target, Compiler, CompilerOptions};
+use common::{assert_linear_in_nested_variables, rand_ext2_elements, Ext2};
+use interpreter::Interpreter;
+use std::collections::BTreeMap;
+use winter_math::{fields::f64::BaseElement as Felt, FieldElement};
+
+#[path = "../common/mod.rs"]
+mod common;
+mod interpreter;
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the Miden assembly code generated for the provided AirScript source.
+fn generate_masm(source: &str) -> String {
+    let options = CompilerOptions::default().with_targets([Target::Masm]);
+    let output = Compiler::new(options).compile_source(source).unwrap();
+    output.get(Target::Masm).unwrap().to_string()
+}
+
+/// Writes the extension elements to memory from the specified address, two addresses per
+/// element with the low coefficient first.
+fn store_ext2(memory: &mut BTreeMap<u32, Felt>, address: u32, values: &[Ext2]) {
+    for (i, value) in values.iter().enumerate() {
+        let [a0, a1] = value.to_base_elements();
+        memory.insert(address + 2 * i as u32, a0);
+        memory.insert(address + 2 * i as u32 + 1, a1);
+    }
+}
+
+/// Writes the base field elements to memory from the specified address.
+fn store_felts(memory: &mut BTreeMap<u32, Felt>, address: u32, values: &[Felt]) {
+    for (i, value) in values.iter().enumerate() {
+        memory.insert(address + i as u32, *value);
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn aux_trace_matches_reference() {
+    let source = std::fs::read_to_string("tests/aux_trace/aux_trace.air").unwrap();
+    let code = generate_masm(&source);
+    let masm = Interpreter::new(&code);
+
+    // the memory layout of the AuxiliaryAir: two rows of 3 main and 2 aux columns, no periodic
+    // columns, 2 random values and 16 public inputs
+    assert!(code.contains("#   [0, 6): segment 0, current row\n"));
+    assert!(code.contains("#   [20, 24): random values\n"));
+    assert!(code.contains("#   [24, 40): public input stack_inputs\n"));
+
+    let main = rand_ext2_elements(1, 6);
+    let aux = rand_ext2_elements(2, 4);
+    let rand = rand_ext2_elements(3, 2);
+    let stack_inputs = (0..16).map(Felt::new).collect::<Vec<_>>();
+    let mut memory = BTreeMap::new();
+    store_ext2(&mut memory, 0, &main[..3]);
+    store_ext2(&mut memory, 6, &main[3..]);
+    store_ext2(&mut memory, 12, &aux[..2]);
+    store_ext2(&mut memory, 16, &aux[2..]);
+    store_ext2(&mut memory, 20, &rand);
+    store_felts(&mut memory, 24, &stack_inputs);
+
+    let [a, b, c, a_next, b_next, _] = main[..] else {
+        unreachable!()
+    };
+    let [p0, p1, p0_next, p1_next] = aux[..] else {
+        unreachable!()
+    };
+    assert_eq!(
+        masm.call("evaluate_transitions_0", &memory),
+        vec![a_next - (b + a * b * c), b_next - (c + a_next), c - (a + b)]
+    );
+    assert_eq!(
+        masm.call("evaluate_transitions_1", &memory),
+        vec![
+            p0_next - p0 * (a + rand[0] + b + rand[1]),
+            p1 - p1_next * (c + rand[0])
+        ]
+    );
+
+    // the assertions are sorted by step and then by column, and evaluated on the current row
+    assert_eq!(
+        masm.call("evaluate_boundaries_0", &memory),
+        vec![a - Ext2::ONE, b - Ext2::ONE]
+    );
+    assert_eq!(
+        masm.call("evaluate_boundaries_1", &memory),
+        vec![p0 - Ext2::ONE, p1 - rand[0], p0 - Ext2::ONE, p1 - Ext2::ONE]
+    );
+}
+
+#[test]
+fn shared_nodes_and_powers() {
+    let code = generate_masm(
+        "def SharedAir
+
+const K = [3, 18446744069414584320]
+
+trace_columns:
+    main: [a, b]
+
+public_inputs:
+    stack_inputs: [2]
+
+periodic_columns:
+    k0: [1, 0]
+
+boundary_constraints:
+    enf b.last = stack_inputs[1]
+    enf a.first = stack_inputs[0]
+
+integrity_constraints:
+    let x = a + b
+    enf a' = x^7 + K[1]
+    enf b' = x * x + k0 * K[0] + b^4",
+    );
+    let masm = Interpreter::new(&code);
+
+    // the sum is used by both constraints, so it is saved to a pair of locals
+    assert!(code.contains("export.evaluate_transitions_0.2\n"));
+    assert!(code.contains("const.K_1=18446744069414584320\n"));
+    assert_eq!(
+        code.matches("dup.1 dup.1 loc_store.1 loc_store.0").count(),
+        1
+    );
+
+    let frame = rand_ext2_elements(4, 4);
+    let periodic = rand_ext2_elements(5, 1);
+    let stack_inputs = [Felt::new(7), Felt::new(11)];
+    let mut memory = BTreeMap::new();
+    store_ext2(&mut memory, 0, &frame);
+    store_ext2(&mut memory, 8, &periodic);
+    store_felts(&mut memory, 10, &stack_inputs);
+
+    let [a, b, a_next, b_next] = frame[..] else {
+        unreachable!()
+    };
+    let x = a + b;
+    let k = [
+        Ext2::from(3u64),
+        Ext2::from(Felt::new(18446744069414584320)),
+    ];
+    assert_eq!(
+        masm.call("evaluate_transitions_0", &memory),
+        vec![
+            a_next - (x.exp(7) + k[1]),
+            b_next - (x * x + periodic[0] * k[0] + b.exp(4))
+        ]
+    );
+    assert_eq!(
+        masm.call("evaluate_boundaries_0", &memory),
+        vec![
+            a - Ext2::from(stack_inputs[0]),
+            b - Ext2::from(stack_inputs[1])
+        ]
+    );
+}
+
+#[test]
+fn nested_variables_emitted_once() {
+    assert_linear_in_nested_variables(Target::Masm);
+}
@@ -0,0 +1,25 @@
+use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+
+/// The BinaryAir AIR. Its public inputs are read from the public values of the builder:
+/// - `stack_inputs`: [0, 16)
+pub struct BinaryAir;
+
+impl<F> BaseAir<F> for BinaryAir {
+    fn width(&self) -> usize {
+        2
+    }
+}
+
+impl<AB: AirBuilderWithPublicValues> Air<AB> for BinaryAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let main_current: Vec<AB::Expr> = main.row_slice(0).iter().map(|&v| v.into()).collect();
+        let main_next: Vec<AB::Expr> = main.row_slice(1).iter().map(|&v| v.into()).collect();
+        let public_values: Vec<AB::Expr> = builder.public_values().iter().map(|&v| v.into()).collect();
+        builder.when_first_row().assert_zero(main_current[0].clone() - AB::Expr::from_canonical_u64(0));
+        builder.assert_zero(main_current[0].clone().exp_u64(2) - main_current[0].clone() - AB::Expr::from_canonical_u64(0));
+        builder.assert_zero(main_current[1].clone().exp_u64(2) - main_current[1].clone() - AB::Expr::from_canonical_u64(0));
+    }
+}
\ No newline at end of file
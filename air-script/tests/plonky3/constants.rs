@@ -0,0 +1,33 @@
+use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+
+const A: u64 = 2;
+const B: [u64; 2] = [1, 0];
+const C: [[u64; 2]; 2] = [[1, 2], [3, 4]];
+
+/// The ConstantsAir AIR. Its public inputs are read from the public values of the builder:
+/// - `stack_inputs`: [0, 2)
+/// - `stack_outputs`: [2, 4)
+pub struct ConstantsAir;
+
+impl<F> BaseAir<F> for ConstantsAir {
+    fn width(&self) -> usize {
+        3
+    }
+}
+
+impl<AB: AirBuilderWithPublicValues> Air<AB> for ConstantsAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let main_current: Vec<AB::Expr> = main.row_slice(0).iter().map(|&v| v.into()).collect();
+        let main_next: Vec<AB::Expr> = main.row_slice(1).iter().map(|&v| v.into()).collect();
+        let public_values: Vec<AB::Expr> = builder.public_values().iter().map(|&v| v.into()).collect();
+        builder.when_first_row().assert_zero(main_current[0].clone() - AB::Expr::from_canonical_u64(A));
+        builder.when_first_row().assert_zero(main_current[1].clone() - public_values[1].clone());
+        builder.when_last_row().assert_zero(main_current[2].clone() - (public_values[2].clone() + AB::Expr::from_canonical_u64(B[0])));
+        builder.assert_zero(main_current[1].clone().exp_u64(2) - main_current[1].clone() - AB::Expr::from_canonical_u64(0));
+        builder.when_transition().assert_zero(main_next[0].clone() - ((main_current[0].clone() + main_current[1].clone()).exp_u64(3) * AB::Expr::from_canonical_u64(C[1][0]) - main_current[2].clone()));
+        builder.when_transition().assert_zero(main_next[2].clone() - (main_current[2].clone() - (main_current[0].clone() - main_current[1].clone()) * AB::Expr::from_canonical_u64(A)));
+    }
+}
\ No newline at end of file
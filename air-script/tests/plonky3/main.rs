@@ -0,0 +1,79 @@
+use air_script::{
+    compiler::{CompileError, Target},
+    Compiler, CompilerOptions,
+};
+use common::assert_linear_in_nested_variables;
+use expect_test::expect_file;
+use ir::CodegenError;
+
+#[path = "../common/mod.rs"]
+mod common;
+
+// HELPERS
+// ================================================================================================
+
+/// Compiles the AIR at the specified path to a Plonky3 Air implementation.
+fn compile_plonky3(path: &str) -> Result<String, CompileError> {
+    let options = CompilerOptions::default().with_targets([Target::Plonky3]);
+    let output = Compiler::new(options).compile_file(path)?;
+    Ok(output.get(Target::Plonky3).unwrap().to_string())
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn binary() {
+    let generated_air = compile_plonky3("tests/binary/binary.air").unwrap();
+
+    let expected = expect_file!["binary.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn pub_inputs() {
+    let generated_air = compile_plonky3("tests/pub_inputs/pub_inputs.air").unwrap();
+
+    let expected = expect_file!["pub_inputs.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn constants_and_powers() {
+    let generated_air = compile_plonky3("tests/plonky3/constants.air").unwrap();
+
+    let expected = expect_file!["constants.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn aux_trace_is_unsupported() {
+    match compile_plonky3("tests/aux_trace/aux_trace.air") {
+        Err(CompileError::Codegen(Target::Plonky3, err)) => {
+            assert!(matches!(err, CodegenError::UnsupportedFeature(..)));
+            assert_eq!(
+                err.to_string(),
+                "auxiliary trace segments are not supported"
+            );
+        }
+        _ => panic!("expected code generation for Plonky3 to fail"),
+    }
+}
+
+#[test]
+fn transition_exemptions_are_unsupported() {
+    match compile_plonky3("tests/transition_exemptions/transition_exemptions.air") {
+        Err(CompileError::Codegen(Target::Plonky3, err)) => {
+            assert_eq!(
+                err.to_string(),
+                "multiple transition exemptions are not supported"
+            );
+        }
+        _ => panic!("expected code generation for Plonky3 to fail"),
+    }
+}
+
+#[test]
+fn nested_variables_bound_once() {
+    assert_linear_in_nested_variables(Target::Plonky3);
+}
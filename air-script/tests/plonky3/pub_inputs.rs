@@ -0,0 +1,34 @@
+use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+
+/// The PubInputsAir AIR. Its public inputs are read from the public values of the builder:
+/// - `program_hash`: [0, 4)
+/// - `stack_inputs`: [4, 8)
+/// - `stack_outputs`: [8, 28)
+/// - `overflow_addrs`: [28, 32)
+pub struct PubInputsAir;
+
+impl<F> BaseAir<F> for PubInputsAir {
+    fn width(&self) -> usize {
+        4
+    }
+}
+
+impl<AB: AirBuilderWithPublicValues> Air<AB> for PubInputsAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let main_current: Vec<AB::Expr> = main.row_slice(0).iter().map(|&v| v.into()).collect();
+        let main_next: Vec<AB::Expr> = main.row_slice(1).iter().map(|&v| v.into()).collect();
+        let public_values: Vec<AB::Expr> = builder.public_values().iter().map(|&v| v.into()).collect();
+        builder.when_first_row().assert_zero(main_current[0].clone() - public_values[4].clone());
+        builder.when_first_row().assert_zero(main_current[1].clone() - public_values[5].clone());
+        builder.when_first_row().assert_zero(main_current[2].clone() - public_values[6].clone());
+        builder.when_first_row().assert_zero(main_current[3].clone() - public_values[7].clone());
+        builder.when_last_row().assert_zero(main_current[0].clone() - public_values[8].clone());
+        builder.when_last_row().assert_zero(main_current[1].clone() - public_values[9].clone());
+        builder.when_last_row().assert_zero(main_current[2].clone() - public_values[10].clone());
+        builder.when_last_row().assert_zero(main_current[3].clone() - public_values[11].clone());
+        builder.when_transition().assert_zero(main_next[0].clone() - (main_current[1].clone() + main_current[2].clone()));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,119 @@
+use std::{env, fs};
+use std::marker::PhantomData;
+use winter_air::{FieldExtension, ProofOptions as WinterProofOptions};
+use winter_math::fields::f64::BaseElement as Felt;
+use winter_prover::crypto::hashers::Blake3_192;
+use winter_prover::crypto::{DefaultRandomCoin, ElementHasher};
+use winter_prover::{Prover, TraceTable};
+use winter_verifier::verify;
+use pub_inputs::{PubInputsAir, PublicInputs};
+use pub_inputs::columns::MAIN_WIDTH;
+
+mod pub_inputs;
+
+/// Builds the public inputs of the AIR from their values, laid out one after the other in the
+/// order in which they are declared.
+pub fn build_public_inputs(values: &[Felt]) -> PublicInputs {
+    PublicInputs::from_elements(values)
+        .unwrap_or_else(|err| panic!("invalid public values: {err}"))
+}
+
+/// Parses a sequence of whitespace-separated field elements.
+pub fn parse_values(data: &str) -> Vec<Felt> {
+    data.split_whitespace()
+        .map(|word| Felt::new(word.parse().expect("invalid field element")))
+        .collect()
+}
+
+/// Loads the main trace from a table of field elements with one row of the trace per line.
+pub fn load_trace(data: &str) -> TraceTable<Felt> {
+    let rows = data
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(parse_values)
+        .collect::<Vec<_>>();
+    assert!(
+        rows.len().is_power_of_two(),
+        "the trace length must be a power of two, found {}",
+        rows.len()
+    );
+
+    let mut columns = vec![Vec::with_capacity(rows.len()); MAIN_WIDTH];
+    for (i, row) in rows.iter().enumerate() {
+        assert_eq!(
+            row.len(),
+            MAIN_WIDTH,
+            "row {i} of the trace has {} columns, expected {MAIN_WIDTH}",
+            row.len()
+        );
+        for (column, value) in columns.iter_mut().zip(row) {
+            column.push(*value);
+        }
+    }
+    TraceTable::init(columns)
+}
+
+/// A Winterfell prover for the PubInputsAir AIR.
+pub struct PubInputsAirProver<H>
+where H: ElementHasher<BaseField = Felt>,
+{
+    options: WinterProofOptions,
+    public_values: Vec<Felt>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H> PubInputsAirProver<H>
+where H: ElementHasher<BaseField = Felt>,
+{
+    /// Returns a new prover with the provided options and public values.
+    pub fn new(options: WinterProofOptions, public_values: Vec<Felt>) -> Self {
+        Self {
+            options,
+            public_values,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+impl<H> Prover for PubInputsAirProver<H>
+where H: ElementHasher<BaseField = Felt>,
+{
+    type BaseField = Felt;
+    type Air = PubInputsAir;
+    type Trace = TraceTable<Felt>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<H>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> PublicInputs {
+        build_public_inputs(&self.public_values)
+    }
+
+    fn options(&self) -> &WinterProofOptions {
+        &self.options
+    }
+}
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    assert!(
+        args.len() >= 2,
+        "usage: {} <trace file> [public inputs file]...",
+        args[0]
+    );
+    let read = |path: &String| {
+        fs::read_to_string(path).unwrap_or_else(|err| panic!("failed to read {path}: {err}"))
+    };
+    let trace = load_trace(&read(&args[1]));
+    let public_values = args[2..]
+        .iter()
+        .flat_map(|path| parse_values(&read(path)))
+        .collect::<Vec<_>>();
+    let pub_inputs = build_public_inputs(&public_values);
+
+    let options = WinterProofOptions::new(27, 8, 16, FieldExtension::None, 8, 255);
+    let prover = PubInputsAirProver::<Blake3_192<Felt>>::new(options, public_values);
+    let proof = prover.prove(trace).expect("failed to generate the proof");
+    verify::<PubInputsAir, Blake3_192<Felt>, DefaultRandomCoin<Blake3_192<Felt>>>(proof, pub_inputs)
+        .expect("failed to verify the proof");
+    println!("The proof was verified");
+}
\ No newline at end of file
@@ -0,0 +1,482 @@
+//! A minimal interpreter for the subset of Solidity emitted by the Solidity code generator and
+//! used by `Goldilocks.sol`. It is used to check the generated library against values computed
+//! by Winterfell, since no Solidity compiler or EVM is available when running `cargo test`.
+//!
+//! Integers are represented by Goldilocks field elements, which is exact for the small integers
+//! manipulated by the generated code, and for `P - b` when it is reduced by `addmod`. `addmod`
+//! and `mulmod` must be called with the modulus `P`. The loop of `pow_g` is not supported, so it
+//! is implemented natively; every other function is interpreted from its source.
+
+use std::collections::BTreeMap;
+use winter_math::{
+    fields::{f64::BaseElement as Felt, QuadExtension},
+    FieldElement, StarkField,
+};
+
+// VALUES
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Val {
+    Felt(Felt),
+    /// A memory array.
+    Array(Vec<Val>),
+    /// An instance of the named struct, with its fields in the order of their declaration.
+    Struct(String, Vec<Val>),
+}
+
+impl Val {
+    pub fn felt(&self) -> Felt {
+        match self {
+            Val::Felt(value) => *value,
+            _ => panic!("expected a felt, found {self:?}"),
+        }
+    }
+
+    /// Returns an array holding the provided base field elements.
+    pub fn felts(values: &[Felt]) -> Self {
+        Val::Array(values.iter().map(|v| Val::Felt(*v)).collect())
+    }
+
+    /// Returns an `Ext2` struct holding the specified element of the quadratic extension.
+    pub fn ext2(value: QuadExtension<Felt>) -> Self {
+        let values = QuadExtension::<Felt>::as_base_elements(&[value])
+            .iter()
+            .map(|v| Val::Felt(*v))
+            .collect();
+        Val::Struct("Ext2".to_string(), values)
+    }
+
+    /// Returns an array holding the provided elements of the quadratic extension.
+    pub fn ext2s(values: &[QuadExtension<Felt>]) -> Self {
+        Val::Array(values.iter().map(|v| Val::ext2(*v)).collect())
+    }
+
+    fn index(&self) -> usize {
+        self.felt().as_int() as usize
+    }
+}
+
+// INTERPRETER
+// ================================================================================================
+
+struct Function {
+    params: Vec<String>,
+    body: Vec<String>,
+}
+
+pub struct Interpreter {
+    constants: BTreeMap<String, Val>,
+    functions: BTreeMap<String, Function>,
+    structs: BTreeMap<String, Vec<String>>,
+}
+
+impl Interpreter {
+    /// Parses the constants, structs and functions declared in the provided Solidity sources,
+    /// at file level or in libraries.
+    pub fn new(sources: &[&str]) -> Self {
+        let mut interpreter = Self {
+            constants: BTreeMap::new(),
+            functions: BTreeMap::new(),
+            structs: BTreeMap::new(),
+        };
+        for source in sources {
+            let tokens = tokenize(source);
+            interpreter.parse_items(&tokens);
+        }
+        interpreter
+    }
+
+    /// Returns an instance of the named struct with the provided fields.
+    pub fn instance(&self, name: &str, fields: Vec<Val>) -> Val {
+        assert_eq!(
+            self.structs[name].len(),
+            fields.len(),
+            "wrong number of fields for {name}"
+        );
+        Val::Struct(name.to_string(), fields)
+    }
+
+    /// Calls the named function with the provided arguments and returns its result.
+    pub fn call(&self, name: &str, args: Vec<Val>) -> Val {
+        if let Some(result) = builtin(name, &args) {
+            return result;
+        }
+        if let Some(fields) = self.structs.get(name) {
+            assert_eq!(
+                fields.len(),
+                args.len(),
+                "wrong number of fields for {name}"
+            );
+            return Val::Struct(name.to_string(), args);
+        }
+
+        let function = self
+            .functions
+            .get(name)
+            .unwrap_or_else(|| panic!("unknown function {name}"));
+        assert_eq!(
+            function.params.len(),
+            args.len(),
+            "wrong number of arguments for {name}"
+        );
+        let mut env = function
+            .params
+            .iter()
+            .cloned()
+            .zip(args)
+            .collect::<BTreeMap<_, _>>();
+
+        for statement in function.body.split(|token| token == ";") {
+            if let Some(result) = self.exec(statement, &mut env) {
+                return result;
+            }
+        }
+        panic!("{name} does not return");
+    }
+
+    // ITEMS
+    // --------------------------------------------------------------------------------------------
+
+    fn parse_items(&mut self, tokens: &[String]) {
+        let mut pos = 0;
+        while pos < tokens.len() {
+            match tokens[pos].as_str() {
+                "pragma" | "import" => {
+                    pos = find(tokens, pos, ";") + 1;
+                }
+                "library" => {
+                    let open = find(tokens, pos, "{");
+                    let close = matching(tokens, open);
+                    self.parse_items(&tokens[open + 1..close]);
+                    pos = close + 1;
+                }
+                "struct" => {
+                    let name = tokens[pos + 1].clone();
+                    let open = find(tokens, pos, "{");
+                    let close = matching(tokens, open);
+                    let fields = tokens[open + 1..close]
+                        .split(|token| token == ";")
+                        .filter_map(|field| field.last().cloned())
+                        .collect();
+                    self.structs.insert(name, fields);
+                    pos = close + 1;
+                }
+                "function" => {
+                    let name = tokens[pos + 1].clone();
+                    let close = matching(tokens, pos + 2);
+                    let params = tokens[pos + 3..close]
+                        .split(|token| token == ",")
+                        .filter_map(|param| param.last().cloned())
+                        .collect();
+                    let open = find(tokens, close, "{");
+                    let end = matching(tokens, open);
+                    let body = tokens[open + 1..end].to_vec();
+                    self.functions.insert(name, Function { params, body });
+                    pos = end + 1;
+                }
+                _ => {
+                    // a constant declaration, such as `uint256 internal constant NAME = value;`
+                    let end = find(tokens, pos, ";");
+                    let declaration = &tokens[pos..end];
+                    let eq = declaration
+                        .iter()
+                        .position(|token| token == "=")
+                        .expect("expected a constant declaration");
+                    assert!(declaration.contains(&"constant".to_string()));
+                    let value = self.eval(&declaration[eq + 1..], &BTreeMap::new());
+                    self.constants.insert(declaration[eq - 1].clone(), value);
+                    pos = end + 1;
+                }
+            }
+        }
+    }
+
+    // STATEMENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes a statement, and returns the returned value if it is a `return` statement.
+    fn exec(&self, statement: &[String], env: &mut BTreeMap<String, Val>) -> Option<Val> {
+        let eq = statement.iter().position(|token| token == "=");
+        match statement.first().map(String::as_str) {
+            None => {}
+            Some("return") => return Some(self.eval(&statement[1..], env)),
+            Some("require") => {
+                let comma = statement.iter().position(|token| token == ",").unwrap();
+                assert!(
+                    self.eval(&statement[2..comma], env) == Val::Felt(Felt::ONE),
+                    "requirement failed: {}",
+                    statement[comma + 1]
+                );
+            }
+            Some(_) if is_declaration(statement) => {
+                let name = match eq {
+                    Some(eq) => statement[eq - 1].clone(),
+                    None => statement.last().unwrap().clone(),
+                };
+                let value = match eq {
+                    Some(eq) => self.eval(&statement[eq + 1..], env),
+                    None => Val::Felt(Felt::ZERO),
+                };
+                env.insert(name, value);
+            }
+            Some(name) => {
+                let eq = eq.expect("expected an assignment");
+                let value = self.eval(&statement[eq + 1..], env);
+                if eq == 1 {
+                    env.insert(name.to_string(), value);
+                } else {
+                    // an element of a memory array, such as `v[0] = ...`
+                    assert_eq!(statement[1], "[");
+                    let index = self.eval(&statement[2..eq - 1], env).index();
+                    match env.get_mut(name) {
+                        Some(Val::Array(values)) => values[index] = value,
+                        _ => panic!("unknown array {name}"),
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // EXPRESSIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn eval(&self, tokens: &[String], env: &BTreeMap<String, Val>) -> Val {
+        let mut pos = 0;
+        let value = self.eval_comparison(tokens, &mut pos, env);
+        assert_eq!(pos, tokens.len(), "unexpected tokens in {tokens:?}");
+        value
+    }
+
+    fn eval_comparison(
+        &self,
+        tokens: &[String],
+        pos: &mut usize,
+        env: &BTreeMap<String, Val>,
+    ) -> Val {
+        let lhs = self.eval_sum(tokens, pos, env);
+        // `==` and `!=` are tokenized as two characters
+        let equal = match tokens.get(*pos).map(String::as_str) {
+            Some("=") => true,
+            Some("!") => false,
+            _ => return lhs,
+        };
+        *pos += 1;
+        expect(tokens, pos, "=");
+        let rhs = self.eval_sum(tokens, pos, env);
+        Val::Felt(Felt::from(((lhs == rhs) == equal) as u64))
+    }
+
+    fn eval_sum(&self, tokens: &[String], pos: &mut usize, env: &BTreeMap<String, Val>) -> Val {
+        let mut lhs = self.eval_product(tokens, pos, env);
+        while let Some(op) = tokens.get(*pos).filter(|t| *t == "+" || *t == "-").cloned() {
+            *pos += 1;
+            let rhs = self.eval_product(tokens, pos, env).felt();
+            lhs = match op.as_str() {
+                "+" => Val::Felt(lhs.felt() + rhs),
+                _ => Val::Felt(lhs.felt() - rhs),
+            };
+        }
+        lhs
+    }
+
+    fn eval_product(&self, tokens: &[String], pos: &mut usize, env: &BTreeMap<String, Val>) -> Val {
+        let mut lhs = self.eval_postfix(tokens, pos, env);
+        while let Some(op) = tokens.get(*pos).filter(|t| *t == "*" || *t == "/").cloned() {
+            *pos += 1;
+            let rhs = self.eval_postfix(tokens, pos, env).felt();
+            lhs = match op.as_str() {
+                "*" => Val::Felt(lhs.felt() * rhs),
+                _ => Val::Felt(Felt::new(lhs.felt().as_int() / rhs.as_int())),
+            };
+        }
+        lhs
+    }
+
+    fn eval_postfix(&self, tokens: &[String], pos: &mut usize, env: &BTreeMap<String, Val>) -> Val {
+        let token = tokens[*pos].clone();
+        *pos += 1;
+
+        let mut value = if token == "(" {
+            let value = self.eval_comparison(tokens, pos, env);
+            expect(tokens, pos, ")");
+            value
+        } else if token == "new" {
+            // `new T[](len)`, whose elements are all written before they are read
+            *pos += 3;
+            let len = self.eval_args(tokens, pos, env)[0].index();
+            Val::Array(vec![Val::Felt(Felt::ZERO); len])
+        } else if let Some(hex) = token.strip_prefix("0x") {
+            Val::Felt(Felt::new(u64::from_str_radix(hex, 16).unwrap()))
+        } else if token.starts_with('"') {
+            Val::Felt(Felt::ZERO)
+        } else if token.chars().next().unwrap().is_ascii_digit() {
+            Val::Felt(Felt::new(token.parse::<u64>().unwrap()))
+        } else if tokens.get(*pos).map(String::as_str) == Some("(") {
+            let args = self.eval_args(tokens, pos, env);
+            self.call(&token, args)
+        } else {
+            env.get(&token)
+                .or_else(|| self.constants.get(&token))
+                .unwrap_or_else(|| panic!("unknown identifier {token}"))
+                .clone()
+        };
+
+        loop {
+            match tokens.get(*pos).map(String::as_str) {
+                Some(".") => {
+                    let member = &tokens[*pos + 1];
+                    *pos += 2;
+                    value = match value {
+                        Val::Struct(name, values) => {
+                            let idx = self.structs[&name]
+                                .iter()
+                                .position(|f| f == member)
+                                .unwrap_or_else(|| panic!("unknown field {name}.{member}"));
+                            values[idx].clone()
+                        }
+                        value => panic!("expected a struct, found {value:?}"),
+                    };
+                }
+                Some("[") => {
+                    *pos += 1;
+                    let index = self.eval_sum(tokens, pos, env).index();
+                    expect(tokens, pos, "]");
+                    value = match value {
+                        Val::Array(values) => values
+                            .get(index)
+                            .unwrap_or_else(|| panic!("index {index} out of bounds"))
+                            .clone(),
+                        value => panic!("expected an array, found {value:?}"),
+                    };
+                }
+                _ => return value,
+            }
+        }
+    }
+
+    /// Evaluates a parenthesized list of arguments.
+    fn eval_args(
+        &self,
+        tokens: &[String],
+        pos: &mut usize,
+        env: &BTreeMap<String, Val>,
+    ) -> Vec<Val> {
+        expect(tokens, pos, "(");
+        let mut args = Vec::new();
+        while tokens[*pos] != ")" {
+            args.push(self.eval_comparison(tokens, pos, env));
+            if tokens[*pos] == "," {
+                *pos += 1;
+            }
+        }
+        *pos += 1;
+        args
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Implements `addmod`, `mulmod` and `pow_g`.
+fn builtin(name: &str, args: &[Val]) -> Option<Val> {
+    let result = match name {
+        "addmod" | "mulmod" => {
+            assert_eq!(args[2].felt(), Felt::ZERO, "the modulus must be P");
+            if name == "addmod" {
+                args[0].felt() + args[1].felt()
+            } else {
+                args[0].felt() * args[1].felt()
+            }
+        }
+        "pow_g" => args[0].felt().exp(args[1].felt().as_int()),
+        _ => return None,
+    };
+    Some(Val::Felt(result))
+}
+
+/// Returns true if the statement declares a local variable, such as `uint256 x = ...` or
+/// `Ext2[] memory w = ...`.
+fn is_declaration(statement: &[String]) -> bool {
+    match statement.get(1).map(String::as_str) {
+        Some("[") => statement.get(2).map(String::as_str) == Some("]"),
+        Some(token) => token.chars().next().unwrap().is_ascii_alphabetic(),
+        None => false,
+    }
+}
+
+/// Returns the position of the first occurrence of the token from `pos`.
+fn find(tokens: &[String], pos: usize, token: &str) -> usize {
+    pos + tokens[pos..]
+        .iter()
+        .position(|t| t == token)
+        .unwrap_or_else(|| panic!("expected {token}"))
+}
+
+/// Returns the position of the bracket closing the one at `open`.
+fn matching(tokens: &[String], open: usize) -> usize {
+    let close = match tokens[open].as_str() {
+        "(" => ")",
+        "{" => "}",
+        token => panic!("{token} is not an opening bracket"),
+    };
+    let mut depth = 0;
+    for (pos, token) in tokens.iter().enumerate().skip(open) {
+        if *token == tokens[open] {
+            depth += 1;
+        } else if token == close {
+            depth -= 1;
+            if depth == 0 {
+                return pos;
+            }
+        }
+    }
+    panic!("unmatched {}", tokens[open]);
+}
+
+/// Splits the source into tokens, dropping comments. String literals are single tokens.
+fn tokenize(source: &str) -> Vec<String> {
+    let code = source
+        .lines()
+        .map(|line| line.split("//").next().unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut tokens = Vec::new();
+    let mut chars = code.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            let mut token = String::new();
+            while let Some(&c) = chars
+                .peek()
+                .filter(|c| c.is_ascii_alphanumeric() || **c == '_')
+            {
+                token.push(c);
+                chars.next();
+            }
+            tokens.push(token);
+        } else if c == '"' {
+            let mut token = String::new();
+            token.push(chars.next().unwrap());
+            for c in chars.by_ref() {
+                token.push(c);
+                if c == '"' {
+                    break;
+                }
+            }
+            tokens.push(token);
+        } else {
+            tokens.push(c.to_string());
+            chars.next();
+        }
+    }
+    tokens
+}
+
+fn expect(tokens: &[String], pos: &mut usize, token: &str) {
+    assert_eq!(tokens[*pos], token, "expected {token}");
+    *pos += 1;
+}
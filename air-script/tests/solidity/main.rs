@@ -0,0 +1,390 @@
+use air_script::{compiler::Target, CompileOutput, Compiler, CompilerOptions};
+use common::{
+    air_context, assert_linear_in_nested_variables, coefficients, rand_elements,
+    rand_ext2_elements, Ext2,
+};
+use interpreter::{Interpreter, Val};
+use winter_air::{Assertion, BoundaryConstraints, TransitionConstraints};
+use winter_math::{fft, fields::f64::BaseElement as Felt, polynom, FieldElement, StarkField};
+
+#[path = "../common/mod.rs"]
+mod common;
+mod interpreter;
+
+/// The field arithmetic imported by the generated library.
+const GOLDILOCKS_LIBRARY: &str = include_str!("../../../codegen/solidity/src/Goldilocks.sol");
+
+// HELPERS
+// ================================================================================================
+
+/// Compiles the AIR at the specified path to Solidity.
+fn compile_solidity(path: &str) -> CompileOutput {
+    let options = CompilerOptions::default().with_targets([Target::Solidity]);
+    Compiler::new(options).compile_file(path).unwrap()
+}
+
+/// Returns an interpreter for the provided Solidity code and the library it imports.
+fn interpreter(code: &str) -> Interpreter {
+    Interpreter::new(&[GOLDILOCKS_LIBRARY, code])
+}
+
+fn felt(value: usize) -> Val {
+    Val::Felt(Felt::new(value as u64))
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn evaluate_constraints_matches_winterfell() {
+    let output = compile_solidity("tests/aux_trace/aux_trace.air");
+    let (code, ir) = (output.get(Target::Solidity).unwrap(), output.ir());
+    let solidity = interpreter(code);
+
+    let trace_length = 16;
+    let context = air_context(ir, trace_length);
+    let blowup_factor = context.ce_domain_size() / trace_length;
+    let g = Felt::get_root_of_unity(trace_length.trailing_zeros());
+
+    // the OOD frame, the random values and the composition coefficients
+    let main = rand_elements(1, 6);
+    let aux = rand_ext2_elements(2, 4);
+    let rand = rand_ext2_elements(3, 2);
+    let stack_inputs = rand_elements(4, 16);
+    let (transition_a, transition_b, transition_coeffs) = coefficients(5, 5);
+    let (boundary_a, boundary_b, boundary_coeffs) = coefficients(7, 6);
+    let x = rand_elements(9, 1)[0];
+
+    // the integrity constraints of the AuxiliaryAir, evaluated by hand and merged by Winterfell
+    let [a, b, c, a_next, b_next, _] = main.as_slice() else {
+        unreachable!()
+    };
+    let [p0, p1, p0_next, p1_next] = aux.as_slice() else {
+        unreachable!()
+    };
+    let main_evaluations = [
+        *a_next - (*b + *a * *b * *c),
+        *b_next - (*c + *a_next),
+        *c - (*a + *b),
+    ]
+    .map(Ext2::from);
+    let aux_evaluations = [
+        *p0_next - *p0 * (Ext2::from(*a) + rand[0] + Ext2::from(*b) + rand[1]),
+        *p1 - *p1_next * (Ext2::from(*c) + rand[0]),
+    ];
+    let transitions = TransitionConstraints::<Ext2>::new(&context, &transition_coeffs);
+    let x_e = Ext2::from(x);
+    let mut expected =
+        transitions.combine_evaluations::<Ext2>(&main_evaluations, &aux_evaluations, x_e);
+
+    // the boundary constraints of the AuxiliaryAir, evaluated by Winterfell on the current row
+    let last_step = trace_length - 1;
+    let main_assertions = vec![
+        Assertion::single(0, 0, Felt::ONE),
+        Assertion::single(1, 0, Felt::ONE),
+    ];
+    let aux_assertions = vec![
+        Assertion::single(0, 0, Ext2::ONE),
+        Assertion::single(0, last_step, Ext2::ONE),
+        Assertion::single(1, 0, rand[0]),
+        Assertion::single(1, last_step, Ext2::ONE),
+    ];
+    let boundaries =
+        BoundaryConstraints::new(&context, main_assertions, aux_assertions, &boundary_coeffs);
+    let main_row = main[..3].iter().map(|v| Ext2::from(*v)).collect::<Vec<_>>();
+    for group in boundaries.main_constraints() {
+        expected += group.evaluate_at(&main_row, x_e, x_e.exp(group.degree_adjustment()));
+    }
+    for group in boundaries.aux_constraints() {
+        expected += group.evaluate_at(&aux[..2], x_e, x_e.exp(group.degree_adjustment()));
+    }
+
+    // the same constraints, evaluated and merged by the generated library
+    let frame_0 = solidity.instance(
+        "EvaluationFrame",
+        vec![Val::felts(&main[..3]), Val::felts(&main[3..])],
+    );
+    let frame_1 = solidity.instance(
+        "AuxEvaluationFrame",
+        vec![Val::ext2s(&aux[..2]), Val::ext2s(&aux[2..])],
+    );
+    let coeffs = solidity.instance(
+        "CompositionCoefficients",
+        vec![
+            Val::felts(&transition_a),
+            Val::felts(&transition_b),
+            Val::felts(&boundary_a),
+            Val::felts(&boundary_b),
+        ],
+    );
+    let result = solidity.call(
+        "evaluate_constraints",
+        vec![
+            frame_0,
+            frame_1,
+            Val::felts(&stack_inputs),
+            Val::ext2s(&rand),
+            coeffs,
+            felt(trace_length),
+            felt(blowup_factor),
+            Val::Felt(g),
+            Val::Felt(x),
+        ],
+    );
+    assert_eq!(result, Val::ext2(expected));
+}
+
+#[test]
+fn periodic_transitions_match_winterfell() {
+    let output = compile_solidity("tests/periodic_columns/periodic_columns.air");
+    let (code, ir) = (output.get(Target::Solidity).unwrap(), output.ir());
+    let solidity = interpreter(code);
+
+    // constraints using periodic columns are merged with the degree of their cycles
+    assert!(code.contains("        // Merge evaluation degree 1, cycles 4\n"));
+    assert!(code.contains(
+        "        xp = pow_g(x, target_degree - (1 * (trace_length - 1) + trace_length / 8 * 7));\n"
+    ));
+
+    let trace_length = 32;
+    let context = air_context(ir, trace_length);
+    let blowup_factor = context.ce_domain_size() / trace_length;
+    let g = Felt::get_root_of_unity(trace_length.trailing_zeros());
+
+    // on the trace domain, the periodic columns take the values of their cycles
+    let k0 = [1, 0, 0, 0];
+    let k1 = [1, 1, 1, 1, 1, 1, 1, 0];
+    for step in [0, 3, 7, 13] {
+        let x = g.exp(step as u64);
+        let row = solidity.call(
+            "evaluate_periodic_columns",
+            vec![felt(trace_length), Val::Felt(x)],
+        );
+        let expected = [k0[step % 4], k1[step % 8]].map(Felt::new);
+        assert_eq!(row, Val::felts(&expected));
+    }
+
+    // at the OOD point, they are evaluated from their interpolated polynomials
+    let x = rand_elements(1, 1)[0];
+    let periodic_values = [&k0[..], &k1[..]].map(|values| {
+        let mut poly = values.iter().map(|v| Felt::new(*v)).collect::<Vec<_>>();
+        let inv_twiddles = fft::get_inv_twiddles::<Felt>(poly.len());
+        fft::interpolate_poly(&mut poly, &inv_twiddles);
+        polynom::eval(&poly, x.exp((trace_length / values.len()) as u64))
+    });
+    let periodic_row = solidity.call(
+        "evaluate_periodic_columns",
+        vec![felt(trace_length), Val::Felt(x)],
+    );
+    assert_eq!(periodic_row, Val::felts(&periodic_values));
+
+    // k0 * (b + c) = 0 and k1 * (a' - a) = 0, merged by Winterfell
+    let frame = rand_elements(2, 6);
+    let [a, b, c, a_next, _, _] = frame.as_slice() else {
+        unreachable!()
+    };
+    let evaluations = [
+        periodic_values[0] * (*b + *c),
+        periodic_values[1] * (*a_next - *a),
+    ]
+    .map(Ext2::from);
+    let (coeffs_a, coeffs_b, coeffs) = coefficients(3, 2);
+    let transitions = TransitionConstraints::<Ext2>::new(&context, &coeffs);
+    let expected = transitions.combine_evaluations::<Ext2>(&evaluations, &[], Ext2::from(x));
+
+    let frame_0 = solidity.instance(
+        "EvaluationFrame",
+        vec![Val::felts(&frame[..3]), Val::felts(&frame[3..])],
+    );
+    let t_evaluations = solidity.call("evaluate_transitions_0", vec![frame_0, periodic_row]);
+    let coeffs = solidity.instance(
+        "CompositionCoefficients",
+        vec![
+            Val::felts(&coeffs_a),
+            Val::felts(&coeffs_b),
+            Val::felts(&[]),
+            Val::felts(&[]),
+        ],
+    );
+    let result = solidity.call(
+        "merge_transitions_0",
+        vec![
+            felt(trace_length),
+            felt(blowup_factor),
+            coeffs,
+            t_evaluations,
+            Val::Felt(g),
+            Val::Felt(x),
+        ],
+    );
+    assert_eq!(Ext2::from(result.felt()), expected);
+}
+
+#[test]
+fn transition_exemptions_match_winterfell() {
+    let output = compile_solidity("tests/transition_exemptions/transition_exemptions.air");
+    let (code, ir) = (output.get(Target::Solidity).unwrap(), output.ir());
+    let solidity = interpreter(code);
+
+    // the last 3 rows are exempted from the transition constraints
+    assert_eq!(ir.num_transition_exemptions(), 3);
+    assert!(
+        code.contains("        uint256 target_degree = composition_degree + trace_length - 3;\n")
+    );
+
+    let trace_length = 16;
+    let context = air_context(ir, trace_length);
+    let blowup_factor = context.ce_domain_size() / trace_length;
+    let g = Felt::get_root_of_unity(trace_length.trailing_zeros());
+
+    // the OOD frame and the composition coefficients
+    let main = rand_elements(1, 4);
+    let stack_inputs = rand_elements(2, 16);
+    let (transition_a, transition_b, transition_coeffs) = coefficients(3, 2);
+    let (boundary_a, boundary_b, boundary_coeffs) = coefficients(5, 2);
+    let x = rand_elements(7, 1)[0];
+
+    // clk' = clk + 1 and a^2 - a = 0, merged by Winterfell
+    let [clk, a, clk_next, _] = main.as_slice() else {
+        unreachable!()
+    };
+    let evaluations = [*clk_next - (*clk + Felt::ONE), *a * *a - *a].map(Ext2::from);
+    let transitions = TransitionConstraints::<Ext2>::new(&context, &transition_coeffs);
+    let x_e = Ext2::from(x);
+    let mut expected = transitions.combine_evaluations::<Ext2>(&evaluations, &[], x_e);
+
+    // the boundary constraint on the last row applies to the last step which is not exempted
+    let last_step = trace_length - 3;
+    let assertions = vec![
+        Assertion::single(0, 0, Felt::ZERO),
+        Assertion::single(1, last_step, Felt::ONE),
+    ];
+    let boundaries = BoundaryConstraints::new(&context, assertions, vec![], &boundary_coeffs);
+    let main_row = main[..2].iter().map(|v| Ext2::from(*v)).collect::<Vec<_>>();
+    for group in boundaries.main_constraints() {
+        expected += group.evaluate_at(&main_row, x_e, x_e.exp(group.degree_adjustment()));
+    }
+
+    // the same constraints, evaluated and merged by the generated library
+    let frame_0 = solidity.instance(
+        "EvaluationFrame",
+        vec![Val::felts(&main[..2]), Val::felts(&main[2..])],
+    );
+    let coeffs = solidity.instance(
+        "CompositionCoefficients",
+        vec![
+            Val::felts(&transition_a),
+            Val::felts(&transition_b),
+            Val::felts(&boundary_a),
+            Val::felts(&boundary_b),
+        ],
+    );
+    let result = solidity.call(
+        "evaluate_constraints",
+        vec![
+            frame_0,
+            Val::felts(&stack_inputs),
+            coeffs,
+            felt(trace_length),
+            felt(blowup_factor),
+            Val::Felt(g),
+            Val::Felt(x),
+        ],
+    );
+    assert_eq!(Ext2::from(result.felt()), expected);
+}
+
+#[test]
+fn goldilocks_library_matches_winterfell() {
+    let solidity = interpreter("");
+    let values = rand_ext2_elements(11, 2);
+    let (a, b) = (values[0], values[1]);
+    let [c, d] = rand_elements(12, 2)[..] else {
+        unreachable!()
+    };
+
+    let call = |name: &str, args: Vec<Val>| solidity.call(name, args);
+    let (vc, vd) = (Val::Felt(c), Val::Felt(d));
+    assert_eq!(
+        call("add_g", vec![vc.clone(), vd.clone()]),
+        Val::Felt(c + d)
+    );
+    assert_eq!(
+        call("sub_g", vec![vc.clone(), vd.clone()]),
+        Val::Felt(c - d)
+    );
+    assert_eq!(
+        call("mul_g", vec![vc.clone(), vd.clone()]),
+        Val::Felt(c * d)
+    );
+    assert_eq!(call("inv_g", vec![vc.clone()]), Val::Felt(c.inv()));
+    assert_eq!(call("div_g", vec![vc, vd]), Val::Felt(c / d));
+
+    let (va, vb) = (Val::ext2(a), Val::ext2(b));
+    assert_eq!(
+        call("add_e", vec![va.clone(), vb.clone()]),
+        Val::ext2(a + b)
+    );
+    assert_eq!(
+        call("sub_e", vec![va.clone(), vb.clone()]),
+        Val::ext2(a - b)
+    );
+    assert_eq!(
+        call("mul_e", vec![va.clone(), vb.clone()]),
+        Val::ext2(a * b)
+    );
+    assert_eq!(
+        call("mul_eg", vec![va.clone(), Val::Felt(c)]),
+        Val::ext2(a * Ext2::from(c))
+    );
+    assert_eq!(call("inv_e", vec![va.clone()]), Val::ext2(a.inv()));
+    assert_eq!(call("div_e", vec![va, vb]), Val::ext2(a / b));
+}
+
+#[test]
+fn named_constants_and_layout() {
+    let output = compile_solidity("tests/constants/constants.air");
+    let code = output.get(Target::Solidity).unwrap();
+    assert!(code.contains("    uint256 internal constant A = 1;\n"));
+    assert!(code.contains(
+        "    uint256 internal constant B_0 = 0;\n    uint256 internal constant B_1 = 1;\n"
+    ));
+
+    // evaluate the main boundary constraints against a row of the trace
+    let solidity = interpreter(code);
+    let row = rand_elements(3, 4);
+    let frame = solidity.instance("EvaluationFrame", vec![Val::felts(&row), Val::felts(&row)]);
+    let mut args = vec![frame];
+    for len in [4, 4, 20, 4] {
+        args.push(Val::felts(&rand_elements(len, len as usize)));
+    }
+    let b_evaluations = solidity.call("evaluate_boundaries_0", args);
+
+    // a = A, b = A + B[0] * C[0][1], c = (B[0] - C[1][1]) * A, and
+    // d = A + B[0] - B[1] + C[0][0] - C[0][1] + C[1][0] - C[1][1]
+    let expected = [1, 1, 0, 1]
+        .iter()
+        .zip(row)
+        .map(|(value, cell)| cell - Felt::new(*value))
+        .collect::<Vec<_>>();
+    assert_eq!(b_evaluations, Val::felts(&expected));
+
+    // the layout is computed from the constraints
+    let output = compile_solidity("tests/aux_trace/aux_trace.air");
+    let code = output.get(Target::Solidity).unwrap();
+    for constant in [
+        "MAIN_SEGMENT_WIDTH = 3",
+        "AUX_TRACE_WIDTH = 2",
+        "NUM_RANDOM_VALUES = 2",
+        "NUM_TRANSITION_CONSTRAINTS = 5",
+        "NUM_ASSERTIONS = 6",
+        "CE_BLOWUP_FACTOR = 2",
+    ] {
+        assert!(code.contains(&format!("\n    uint256 internal constant {constant};\n")));
+    }
+}
+
+#[test]
+fn nested_variables_emitted_once() {
+    assert_linear_in_nested_variables(Target::Solidity);
+}
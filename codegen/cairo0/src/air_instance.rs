@@ -0,0 +1,78 @@
+use ir::AirLayout;
+use ir::CodegenError;
+use ir::ConstantBinding;
+use ir::layout::constant_elements;
+
+/// Generate the layout constants of the AIR and its `get_air_instance` function
+///
+/// Every value of the `AirInstance` which is determined by the AIR is taken from its [AirLayout],
+/// so that the verifier cannot be configured with a layout which disagrees with the constraints.
+/// The values which depend on the proof, such as the domain generators, are computed from the
+/// proof context.
+///
+/// # Errors
+/// Returns an error if one of the named constants has the name of a layout constant.
+pub fn air_instance(segment_widths: &[u16], layout: &AirLayout, constants: &[ConstantBinding]) -> Result<String, CodegenError>
+{
+  let aux_segment_widths = &segment_widths[1..];
+  let layout_constants = [
+    ("MAIN_SEGMENT_WIDTH", layout.main_segment_width as usize),
+    ("AUX_TRACE_WIDTH", layout.aux_trace_width as usize),
+    ("NUM_AUX_SEGMENTS", layout.num_aux_segments),
+    ("NUM_TRANSITION_CONSTRAINTS", layout.num_transition_constraints),
+    ("NUM_ASSERTIONS", layout.num_assertions),
+    ("CE_BLOWUP_FACTOR", layout.ce_blowup_factor),
+    ("EVAL_FRAME_SIZE", layout.eval_frame_size),
+  ];
+  for (name, _) in constant_elements(constants)? {
+    if layout_constants.iter().any(|(layout_name, _)| *layout_name == name) {
+      return Err(CodegenError::name_conflict(name));
+    }
+  }
+
+  let mut s = "\n// AIR INSTANCE\n".to_string();
+  s += "// ===============================================\n";
+  for (name, value) in layout_constants {
+    s += &format!("const {name} = {value};\n");
+  }
+
+  s += "\nfunc get_air_instance{range_check_ptr}(\n";
+  s += "  proof: StarkProof*,\n";
+  s += "  pub_inputs: PublicInputs*,\n";
+  s += "  options: ProofOptions,\n";
+  s += ") -> AirInstance {\n";
+  s += "  alloc_locals;\n";
+
+  // AirScript declares all the random values for the first aux segment
+  s += "  let (aux_segment_widths: felt*) = alloc();\n";
+  s += "  let (aux_segment_rands: felt*) = alloc();\n";
+  for (i, width) in aux_segment_widths.iter().enumerate() {
+    let rands = if i == 0 { layout.num_random_values } else { 0 };
+    s += &format!("  assert aux_segment_widths[{i}] = {width};\n");
+    s += &format!("  assert aux_segment_rands[{i}] = {rands};\n");
+  }
+
+  s += "\n  // Generators of the trace domain and of the LDE domain\n";
+  s += "  let (power) = pow(2, TWO_ADICITY - proof.context.log_trace_length);\n";
+  s += "  local trace_domain_generator = pow_g(TWO_ADIC_ROOT_OF_UNITY, power);\n";
+  s += "  let (power) = pow(2, TWO_ADICITY - proof.context.log_trace_length - options.log_blowup_factor);\n";
+  s += "  local lde_domain_generator = pow_g(TWO_ADIC_ROOT_OF_UNITY, power);\n";
+
+  s += "\n  return AirInstance(\n";
+  s += "    main_segment_width=MAIN_SEGMENT_WIDTH,\n";
+  s += "    aux_trace_width=AUX_TRACE_WIDTH,\n";
+  s += "    aux_segment_widths=aux_segment_widths,\n";
+  s += "    aux_segment_rands=aux_segment_rands,\n";
+  s += "    num_aux_segments=NUM_AUX_SEGMENTS,\n";
+  s += "    context=proof.context,\n";
+  s += "    num_transition_constraints=NUM_TRANSITION_CONSTRAINTS,\n";
+  s += "    num_assertions=NUM_ASSERTIONS,\n";
+  s += "    ce_blowup_factor=CE_BLOWUP_FACTOR,\n";
+  s += "    eval_frame_size=EVAL_FRAME_SIZE,\n";
+  s += "    trace_domain_generator=trace_domain_generator,\n";
+  s += "    lde_domain_generator=lde_domain_generator,\n";
+  s += "    pub_inputs=pub_inputs,\n";
+  s += "  );\n";
+  s += "}\n";
+  Ok(s)
+}
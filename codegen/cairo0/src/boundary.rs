@@ -2,10 +2,18 @@ use ir::constraints::AlgebraicGraph;
 use ir::constraints::ConstraintRoot;
 use ir::constraints::ConstraintDomain;
 use ir::PublicInput;
+use ir::constraints::Operation;
+use ir::NodeIndex;
+use ir::Value;
+use ir::CodegenError;
+use ir::ConstraintLocation;
 
 
+use std::collections::BTreeMap;
+
 use super::showvalue;
 use showvalue::str;
+use showvalue::ext2_element;
 
 // Evaluate boundary constraints for one segment
 pub fn evaluate_boundaries(
@@ -13,13 +21,10 @@ pub fn evaluate_boundaries(
   public_inputs: &Vec<PublicInput>,
   segment: usize, 
   boundary_constraints: &Vec<ConstraintRoot>,
-) -> (
-  String, 
-  Vec<usize>,  // constraint degrees
-  usize,       // maximum degree
-  Vec<ConstraintDomain>)
+  extension: bool,
+) -> Result<String, CodegenError>
 {
-  let mut counter : i32 = 0;
+  let mut emitted = BTreeMap::new();
   let mut s = "".to_string();
 /*
   s = s + 
@@ -83,34 +88,175 @@ pub fn evaluate_boundaries(
         
   // boundary constraints
   s = s + "// BOUNDARY CONSTRAINTS\n\n";
-  let mut boundary_degrees: Vec<usize> = Vec::new();
-  let mut boundary_domain: Vec<ConstraintDomain> = Vec::new();
 
-  let mut print_constraint = |i:usize, w: &ConstraintRoot| -> String {
+  let mut print_constraint = |i:usize, w: &ConstraintRoot| -> Result<String, CodegenError> {
     //s = s + "    // #" + &i.to_string() + ": root node " + &w.index.0.to_string() + " Domain: " + &w.domain.to_string() + "\n";
     let domain = &w.domain;
-    let mut s = "  // ".to_string() + &str(&graph,&w.index,domain) + "\n";
-    let r = "v".to_string() + &counter.to_string(); counter = counter + 1;
-    let eval = &showvalue::ascairo(&graph,&r, &w.index, domain, &mut counter);
-    s = s.clone() + &eval + "  assert b_evaluations[" + &i.to_string() + "] = " + &r + ";\n";
+    let mut s = "  // ".to_string() + &str(&graph,&w.index,domain)? + "\n";
+    let r = showvalue::nodename(&w.index, domain);
+    let eval = &showvalue::ascairo(&graph, &w.index, domain, extension, &mut emitted)?;
+    s = s.clone() + &eval + &showvalue::store("b_evaluations", i, &r, emitted[&r], extension);
     let degree = &graph.degree(&w.index).base();
     s = s.clone() + "  // deg = " + &degree.to_string() + ", Domain: " + &w.domain.to_string() + "\n\n";
-    boundary_degrees.push(*degree);
-    boundary_domain.push(w.domain); 
-    s
+    Ok(s)
   };
 
   for (index, bcon) in boundary_constraints.iter().enumerate() {
-    s = s + &print_constraint(index,&bcon);
-  }
-
-  let mut boundary_maxdeg : usize = 0;
-  for w in boundary_degrees.iter() {
-    boundary_maxdeg = boundary_maxdeg.max(*w);
+    let location = ConstraintLocation::boundary(segment as u8, index);
+    s = s + &print_constraint(index,&bcon).map_err(|err| err.in_constraint(location))?;
   }
 
   s = s + "\n  return ();\n";
   s = s + "}\n";
 
-  return (s, boundary_degrees,boundary_maxdeg, boundary_domain);
+  return Ok(s);
+}
+
+// Merge boundary constraint evaluations for one segment
+//
+// This follows Winterfell's BoundaryConstraints: every boundary constraint is a single-step
+// assertion, so they are grouped by step (the first row or the last step) and divided by
+// (x - g^step). All groups share the same degree adjustment, which depends only on the trace
+// length and not on the degree of the constraint expression. The composition coefficients are
+// assigned in the order in which Winterfell sorts assertions: by step, then by column.
+//
+// In a proof over the extension, the OOD point, the coefficients and the evaluations are
+// extension elements, with the coefficients and the evaluations stored as two consecutive felts.
+pub fn merge_boundaries(
+  graph: &AlgebraicGraph,
+  segment: usize,
+  boundary_constraints: &[ConstraintRoot],
+  extension: bool,
+) -> Result<String, CodegenError>
+{
+  let seg = segment.to_string();
+  let mut s = "func merge_boundary_".to_string() + &seg + "{range_check_ptr}(\n";
+  s += "  trace_length: felt,\n";
+  s += "  blowup_factor: felt,\n";
+  s += "  coeffs_boundary_a: felt*,\n";
+  s += "  coeffs_boundary_b: felt*, \n";
+  s += "  b_evaluations: felt*, \n";
+  s += "  trace_domain_generator: felt, \n";
+  s += "  last_step: felt, \n";
+  let (ret, ty, zero) = if extension { ("Ext2", ": Ext2", "Ext2(a0=0, a1=0)") } else { ("felt", "", "0") };
+  s += &format!("  x: {ret}, \n");
+  s += &format!(") -> {ret} {{\n");
+  s += "  alloc_locals;\n";
+
+  s += "  // Evaluate degree adjustment\n";
+  s += "  let composition_degree = trace_length * blowup_factor - 1;\n";
+  s += "  let trace_poly_degree = trace_length - 1;\n";
+  s += "  let divisor_degree = 1;\n";
+  s += "  let target_degree = composition_degree + divisor_degree;\n";
+  s += "  let degree_adjustment = target_degree - trace_poly_degree;\n";
+  if extension {
+    s += "  local xp: Ext2 = pow_e(x, degree_adjustment);\n";
+  } else {
+    s += "  local xp = pow_g(x, degree_adjustment);\n";
+  }
+
+  s += "  // Evaluate divisors\n";
+  s += "  let g = trace_domain_generator;\n";
+  if extension {
+    s += "  local first_z: Ext2 = sub_e(x, Ext2(a0=1, a1=0));\n";
+    s += "  let v1 = pow_g(g, last_step);\n";
+    s += "  local last_z: Ext2 = sub_e(x, Ext2(a0=v1, a1=0));\n";
+  } else {
+    s += "  local first_z = sub_g(x, 1);\n";
+    s += "  let v1 = pow_g(g, last_step);\n";
+    s += "  local last_z = sub_g(x, v1);\n";
+  }
+
+  s += "\n";
+  s += &format!("  local first_sum_0{ty} = {zero};\n");
+  s += &format!("  local last_sum_0{ty} = {zero};\n");
+
+  let coeff_indices = coefficient_order(graph, segment, boundary_constraints)?;
+  let mut first_counter = 0;
+  let mut last_counter = 0;
+  for (i, bcon) in boundary_constraints.iter().enumerate() {
+    let j = coeff_indices[i];
+    s += &format!("\n  // Include boundary {i}\n");
+    let v3 = if extension {
+      let (coeff_a, coeff_b) = (ext2_element("coeffs_boundary_a", j), ext2_element("coeffs_boundary_b", j));
+      s += &format!("  local v1_{i}: Ext2 = mul_e({coeff_b}, xp);\n");
+      s += &format!("  local v2_{i}: Ext2 = add_e({coeff_a}, v1_{i});\n");
+      s += &format!("  local v3_{i}: Ext2 = mul_e(v2_{i}, {});\n", ext2_element("b_evaluations", i));
+      format!("v3_{i}")
+    } else {
+      s += &format!("  let v1 = mul_g(coeffs_boundary_b[{j}], xp);\n");
+      s += &format!("  let v2 = add_g(coeffs_boundary_a[{j}], v1);\n");
+      s += &format!("  let v3 = mul_g(v2, b_evaluations[{i}]);\n");
+      "v3".to_string()
+    };
+    let add = if extension { "add_e" } else { "add_g" };
+    match bcon.domain {
+      ConstraintDomain::FirstRow => {
+        s += &format!("  local first_sum_{}{ty} = {add}(first_sum_{first_counter}, {v3});\n", first_counter + 1);
+        first_counter += 1;
+      },
+      ConstraintDomain::LastRow => {
+        s += &format!("  local last_sum_{}{ty} = {add}(last_sum_{last_counter}, {v3});\n", last_counter + 1);
+        last_counter += 1;
+      },
+      domain => {
+        let location = ConstraintLocation::boundary(segment as u8, i);
+        return Err(invalid_domain(domain).in_constraint(location));
+      }
+    }
+  }
+
+  s += &format!("  let first_sum = first_sum_{first_counter};\n");
+  s += &format!("  let last_sum = last_sum_{last_counter};\n");
+  s += "\n";
+  if extension {
+    s += "  local first: Ext2 = div_e(first_sum, first_z);\n";
+    s += "  local last: Ext2 = div_e(last_sum, last_z);\n";
+    s += "  local combined: Ext2 = add_e(first, last);\n";
+  } else {
+    s += "  let first = div_g(first_sum, first_z);\n";
+    s += "  let last = div_g(last_sum, last_z);\n";
+    s += "  let combined = add_g(first, last);\n";
+  }
+  s += "  return combined;\n";
+  s += "}\n";
+  Ok(s)
+}
+
+/// Returns, for each boundary constraint, the index of its composition coefficients. Winterfell
+/// sorts the assertions of a segment by step and then by column before assigning coefficients.
+fn coefficient_order(graph: &AlgebraicGraph, segment: usize, boundary_constraints: &[ConstraintRoot]) -> Result<Vec<usize>, CodegenError> {
+  let keys = boundary_constraints
+    .iter()
+    .enumerate()
+    .map(|(i, bcon)| {
+      let step = match bcon.domain {
+        ConstraintDomain::FirstRow => 0,
+        _ => 1,
+      };
+      let column = boundary_column(graph, &bcon.index)
+        .map_err(|err| err.in_constraint(ConstraintLocation::boundary(segment as u8, i)))?;
+      Ok((step, column))
+    })
+    .collect::<Result<Vec<_>, CodegenError>>()?;
+
+  let mut sorted = keys.clone();
+  sorted.sort();
+  Ok(keys.iter().map(|key| sorted.iter().position(|k| k == key).unwrap()).collect())
+}
+
+/// Returns the column constrained by the boundary constraint with the specified root.
+fn boundary_column(graph: &AlgebraicGraph, index: &NodeIndex) -> Result<usize, CodegenError> {
+  match graph.node(index).op() {
+    Operation::Sub(lhs, _) => match graph.node(lhs).op() {
+      Operation::Value(Value::TraceElement(trace_access)) => Ok(trace_access.col_idx()),
+      _ => Err(CodegenError::invalid_constraint("expected a trace column on the left-hand side")),
+    },
+    _ => Err(CodegenError::invalid_constraint("expected the subtraction of an expression from a trace column")),
+  }
+}
+
+/// Error for a boundary constraint which applies neither to the first nor to the last row
+fn invalid_domain(domain: ConstraintDomain) -> CodegenError {
+  CodegenError::invalid_constraint(format!("boundary constraints must apply to the first or last row, not to {domain}"))
 }
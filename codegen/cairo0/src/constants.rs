@@ -0,0 +1,24 @@
+use ir::CodegenError;
+use ir::ConstantBinding;
+use ir::layout::constant_elements;
+
+/// Generate declarations for the named constants
+///
+/// Every constant is emitted as a Cairo `const` declaration, with vector and matrix constants
+/// unrolled into one `const` per element by [constant_elements].
+///
+/// # Errors
+/// Returns an error if two of the unrolled constants have the same name.
+pub fn constants(constants: &[ConstantBinding]) -> Result<String, CodegenError>
+{
+  if constants.is_empty() {
+    return Ok("".to_string());
+  }
+
+  let mut s = "\n// CONSTANTS\n".to_string();
+  s += "// ===============================================\n";
+  for (name, value) in constant_elements(constants)? {
+    s += &format!("const {name} = {value};\n");
+  }
+  Ok(s)
+}
@@ -0,0 +1,154 @@
+use ir::constraints::ConstraintRoot;
+use ir::PublicInput;
+
+/// Generate the combined constraint evaluation entry point
+///
+/// The generated `evaluate_constraints` function evaluates and merges the transition and
+/// boundary constraints of every segment at the OOD point `x`, and returns the value of the
+/// constraint composition polynomial expected by the stark_verifier.
+///
+/// The composition coefficients are laid out as in `ConstraintCompositionCoefficients`:
+/// the coefficients for the main segment come first, followed by those of each aux segment.
+///
+/// An AIR with aux segments is verified as a proof over the quadratic extension, as in Winterfell:
+/// the OOD point, the frames of every segment, the random values and the coefficients are
+/// extension elements, with each element stored as two consecutive felts, and the AIR evaluates
+/// to an `Ext2`.
+pub fn evaluate_constraints(
+  nsegments: usize,
+  public_inputs: &[PublicInput],
+  integrity_constraints: &[Vec<ConstraintRoot>],
+  boundary_constraints: &[Vec<ConstraintRoot>],
+  num_transition_exemptions: usize,
+) -> String
+{
+  let mut s = "\n// CONSTRAINT EVALUATION\n".to_string();
+  s += "// ===============================================\n";
+  s += "func evaluate_constraints{range_check_ptr}(\n";
+  for segment in 0..nsegments {
+    s += &format!("  frame_{segment}: EvaluationFrame,\n");
+  }
+  for (name, _size) in public_inputs.iter() {
+    s += &format!("  {name}: felt*,\n");
+  }
+  if nsegments > 1 {
+    s += "  rand: felt*,\n";
+  }
+  s += "  coeffs_transition_a: felt*,\n";
+  s += "  coeffs_transition_b: felt*,\n";
+  s += "  coeffs_boundary_a: felt*,\n";
+  s += "  coeffs_boundary_b: felt*,\n";
+  s += "  trace_length: felt,\n";
+  s += "  blowup_factor: felt,\n";
+  s += "  trace_domain_generator: felt,\n";
+  let extension = nsegments > 1;
+  let ret = if extension { "Ext2" } else { "felt" };
+  s += &format!("  x: {ret},\n");
+  s += &format!(") -> {ret} {{\n");
+  s += "  alloc_locals;\n";
+
+  // The transition divisor excludes the exempted last rows, so its degree is trace_length minus
+  // the number of exemptions
+  s += "  let composition_degree = trace_length * blowup_factor - 1;\n";
+  s += &format!("  local transition_target_degree = composition_degree + trace_length - {num_transition_exemptions};\n");
+
+  // The last step of the trace, as in the `last_step()` of the Winterfell Air, which is the last
+  // row not exempted from transition constraints
+  s += &format!("  let last_step = trace_length - {num_transition_exemptions};\n");
+
+  // Boundary constraints are evaluated on the current row of the OOD frame
+  s += "\n  // Boundary frames\n";
+  for segment in 0..nsegments {
+    s += &format!(
+      "  let b_frame_{segment} = EvaluationFrame(frame_{segment}.current_len, frame_{segment}.current, frame_{segment}.current_len, frame_{segment}.current);\n"
+    );
+  }
+
+  // Periodic values are computed from the OOD point rather than supplied by the caller
+  s += "\n  // Periodic columns\n";
+  s += "  let (periodic_row: felt*) = alloc();\n";
+  s += "  evaluate_periodic_columns(periodic_row, trace_length, x);\n";
+
+  // Aux segments are evaluated against the main frame as well as their own
+  let frames = |prefix: &str, segment: usize| -> Vec<String> {
+    if segment == 0 {
+      vec![format!("{prefix}frame_0")]
+    } else {
+      vec![format!("{prefix}frame_0"), format!("{prefix}frame_{segment}")]
+    }
+  };
+
+  // Coefficients of later segments follow those of the earlier ones, and each coefficient of a
+  // proof over the extension takes two felts
+  let offset = |n: usize| -> String {
+    let n = if extension { 2 * n } else { n };
+    if n == 0 { "".to_string() } else { format!(" + {n}") }
+  };
+
+  if extension {
+    s += "\n  local sum_0: Ext2 = Ext2(a0=0, a1=0);\n";
+  } else {
+    s += "\n  local sum_0 = 0;\n";
+  }
+  let mut transition_offset = 0;
+  let mut boundary_offset = 0;
+  for segment in 0..nsegments {
+    let mut transition_args = frames("", segment);
+    transition_args.push(format!("t_evaluations_{segment}"));
+    transition_args.push("periodic_row".to_string());
+
+    let mut boundary_args = frames("b_", segment);
+    boundary_args.push(format!("b_evaluations_{segment}"));
+    for (name, _size) in public_inputs.iter() {
+      boundary_args.push(name.clone());
+    }
+
+    if segment > 0 {
+      transition_args.push("rand".to_string());
+      boundary_args.push("rand".to_string());
+    }
+
+    s += &format!("\n  // Segment {segment}\n");
+    s += &format!("  let (t_evaluations_{segment}: felt*) = alloc();\n");
+    s += &format!("  evaluate_transition_{segment}({});\n", transition_args.join(", "));
+    let ty = if extension { ": Ext2" } else { "" };
+    s += &format!("  local t_{segment}{ty} = merge_transitions_{segment}(\n");
+    s += "    trace_length,\n";
+    s += "    transition_target_degree,\n";
+    s += &format!("    coeffs_transition_a{},\n", offset(transition_offset));
+    s += &format!("    coeffs_transition_b{},\n", offset(transition_offset));
+    s += &format!("    t_evaluations_{segment},\n");
+    s += "    x,\n";
+    s += "    trace_domain_generator,\n";
+    s += "  );\n";
+
+    s += &format!("  let (b_evaluations_{segment}: felt*) = alloc();\n");
+    s += &format!("  evaluate_boundary_{segment}({});\n", boundary_args.join(", "));
+    s += &format!("  local b_{segment}{ty} = merge_boundary_{segment}(\n");
+    s += "    trace_length,\n";
+    s += "    blowup_factor,\n";
+    s += &format!("    coeffs_boundary_a{},\n", offset(boundary_offset));
+    s += &format!("    coeffs_boundary_b{},\n", offset(boundary_offset));
+    s += &format!("    b_evaluations_{segment},\n");
+    s += "    trace_domain_generator,\n";
+    s += "    last_step,\n";
+    s += "    x,\n";
+    s += "  );\n";
+
+    let next = segment + 1;
+    if !extension {
+      s += &format!("  local segment_sum_{segment} = add_g(t_{segment}, b_{segment});\n");
+      s += &format!("  local sum_{next} = add_g(sum_{segment}, segment_sum_{segment});\n");
+    } else {
+      s += &format!("  local segment_sum_{segment}: Ext2 = add_e(t_{segment}, b_{segment});\n");
+      s += &format!("  local sum_{next}: Ext2 = add_e(sum_{segment}, segment_sum_{segment});\n");
+    }
+
+    transition_offset += integrity_constraints[segment].len();
+    boundary_offset += boundary_constraints[segment].len();
+  }
+
+  s += &format!("\n  return sum_{nsegments};\n");
+  s += "}\n";
+  s
+}
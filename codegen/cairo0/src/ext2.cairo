@@ -0,0 +1,100 @@
+from math_goldilocks import PG, add_g, sub_g, mul_g, inv_g
+
+// An element a0 + a1 * phi of the quadratic extension of the Goldilocks field, where phi is a
+// root of x^2 - x + 2. This is the extension used by Winterfell's QuadExtension<BaseElement>,
+// so phi^2 = phi - 2.
+struct Ext2 {
+    a0: felt,
+    a1: felt,
+}
+
+func add_e{range_check_ptr}(a: Ext2, b: Ext2) -> Ext2 {
+    alloc_locals;
+    local r0 = add_g(a.a0, b.a0);
+    local r1 = add_g(a.a1, b.a1);
+    return Ext2(a0=r0, a1=r1);
+}
+
+func sub_e{range_check_ptr}(a: Ext2, b: Ext2) -> Ext2 {
+    alloc_locals;
+    local r0 = sub_g(a.a0, b.a0);
+    local r1 = sub_g(a.a1, b.a1);
+    return Ext2(a0=r0, a1=r1);
+}
+
+// (a0 + a1 * phi) * (b0 + b1 * phi) = (a0 * b0 - 2 * a1 * b1) + (a0 * b1 + a1 * b0 + a1 * b1) * phi
+func mul_e{range_check_ptr}(a: Ext2, b: Ext2) -> Ext2 {
+    alloc_locals;
+    local a0b0 = mul_g(a.a0, b.a0);
+    local a1b1 = mul_g(a.a1, b.a1);
+    local a1b1_double = add_g(a1b1, a1b1);
+    local r0 = sub_g(a0b0, a1b1_double);
+
+    local a_sum = add_g(a.a0, a.a1);
+    local b_sum = add_g(b.a0, b.a1);
+    local sum_product = mul_g(a_sum, b_sum);
+    local r1 = sub_g(sum_product, a0b0);
+    return Ext2(a0=r0, a1=r1);
+}
+
+// multiply an extension element by a base field element
+func mul_eg{range_check_ptr}(a: Ext2, b: felt) -> Ext2 {
+    alloc_locals;
+    local r0 = mul_g(a.a0, b);
+    local r1 = mul_g(a.a1, b);
+    return Ext2(a0=r0, a1=r1);
+}
+
+// The inverse is the conjugate (the image of the Frobenius map) divided by the norm, which is in
+// the base field.
+func inv_e{range_check_ptr}(a: Ext2) -> Ext2 {
+    alloc_locals;
+    local c0 = add_g(a.a0, a.a1);
+    local c1 = sub_g(0, a.a1);
+    local conjugate: Ext2 = Ext2(a0=c0, a1=c1);
+
+    local norm: Ext2 = mul_e(a, conjugate);
+    assert norm.a1 = 0;
+    local norm_inv = inv_g(norm.a0);
+    return mul_eg(conjugate, norm_inv);
+}
+
+func div_e{range_check_ptr}(a: Ext2, b: Ext2) -> Ext2 {
+    alloc_locals;
+    local b_inv: Ext2 = inv_e(b);
+    return mul_e(a, b_inv);
+}
+
+func pow_e_loop{range_check_ptr}(base: Ext2, exp, res: Ext2) -> Ext2 {
+    alloc_locals;
+    if (exp == 0) {
+        return res;
+    }
+
+    local base_square: Ext2 = mul_e(base, base);
+
+    let bit = [range_check_ptr];
+    let range_check_ptr = range_check_ptr + 1;
+
+    %{ ids.bit = (ids.exp % ids.PG) & 1 %}
+    if (bit == 1) {
+        // odd case
+        let tmp = exp - 1;
+        let new_exp = tmp / 2;
+        local r: Ext2 = mul_e(base, res);
+        return pow_e_loop(base_square, new_exp, r);
+    } else {
+        // even case
+        let new_exp = exp / 2;
+        return pow_e_loop(base_square, new_exp, res);
+    }
+}
+
+// Returns base ** exp, for 0 <= exp < 2**63.
+func pow_e{range_check_ptr}(base: Ext2, exp) -> Ext2 {
+    if (exp == 0) {
+        return Ext2(a0=1, a1=0);
+    }
+
+    return pow_e_loop(base, exp, Ext2(a0=1, a1=0));
+}
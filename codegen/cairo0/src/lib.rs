@@ -1,19 +1,22 @@
 use ir::AirIR;
+use ir::AirLayout;
+use ir::CodegenError;
 use ir::ConstantBinding;
 use ir::PeriodicColumn;
 use ir::PublicInput;
 
 use ir::constraints::AlgebraicGraph;
 use ir::constraints::ConstraintRoot;
-use ir::constraints::ConstraintDomain;
+
+use std::collections::BTreeMap;
 
 mod showvalue;
 mod transition;
 mod boundary;
-
-use std::fs::File;
-use std::io::prelude::*;
-use std::path::Path;
+mod constants;
+mod evaluate;
+mod periodic;
+mod air_instance;
 
 
 // GENERATE verifier for proof as Cairo v0.4
@@ -22,15 +25,14 @@ use std::path::Path;
 pub struct CodeGenerator {
   air_name: String,
   segment_widths: Vec<u16>,
-  #[allow(unused)]
   constants: Vec<ConstantBinding>,
-  #[allow(unused)]
   public_inputs: Vec<PublicInput>,
-  #[allow(unused)]
   periodic_columns: Vec<PeriodicColumn>,
   boundary_constraints: Vec<Vec<ConstraintRoot>>,
   integrity_constraints: Vec<Vec<ConstraintRoot>>,
   graph: AlgebraicGraph,
+  num_transition_exemptions: usize,
+  layout: AirLayout,
 }
 
 impl CodeGenerator {
@@ -48,37 +50,17 @@ impl CodeGenerator {
       boundary_constraints: _ir.constraints.boundary_constraints.clone(), //Constraints
       integrity_constraints: _ir.constraints.integrity_constraints.clone(), //Constraints
       graph: _ir.constraints.graph.clone(),
+      num_transition_exemptions: _ir.num_transition_exemptions(),
+      layout: _ir.layout(),
     }
   }
 
 
   /// Returns a string of Cairo code implementing Cairo0
-  pub fn generate(&self) -> String {
-
-//========== HACK HACK =================================
-//
-// We need to get the PublicInput into a file where the test harness can find it
-// It should be based on the air_name but for the moment i'm using example.public
-// in the current directoy
-//
-//======================================================
-    { 
-      let mut x = "".to_string();
-      for (name,xsize) in self.public_inputs.iter() {
-        x = x + &name + " " + &xsize.to_string() + "\n";
-        
-      };
-      let path = Path::new("example.public");
-      let display = path.display();
-      let mut file = match File::create(&path) {
-        Err(why) => panic!("couldn't create{}: {}", display, why),
-        Ok(file) => file,
-      };
-      match file.write_all(x.as_bytes()) {
-        Err(why) => panic!("couldn't write to {}: {}", display, why),
-        Ok(_) => println!("successfully wrote to {}", display),
-      }
-    };
+  ///
+  /// # Errors
+  /// Returns an error if a constraint uses a feature which the Cairo0 verifier does not support.
+  pub fn generate(&self) -> Result<String, CodegenError> {
 
     // header
     let mut s = 
@@ -86,7 +68,15 @@ impl CodeGenerator {
      ;
      s = s + "from starkware.cairo.common.alloc import alloc\n";
      s = s + "from starkware.cairo.common.memcpy import memcpy\n";
-     s = s + "from math_goldilocks import add_g, sub_g, mul_g, pow_g, div_g\n";
+     s += "from starkware.cairo.common.pow import pow\n";
+     s += "from stark_verifier.air.air_instance import AirInstance\n";
+     s += "from stark_verifier.air.pub_inputs import PublicInputs\n";
+     s += "from stark_verifier.air.stark_proof import ProofOptions, StarkProof\n";
+     s += "from stark_verifier.parameters import TWO_ADIC_ROOT_OF_UNITY, TWO_ADICITY\n";
+     s = s + "from math_goldilocks import add_g, sub_g, mul_g, pow_g, div_g, inv_g\n";
+     if self.segment_widths.len() > 1 {
+       s += "from ext2 import Ext2, add_e, sub_e, mul_e, mul_eg, div_e, pow_e\n";
+     }
      s = s + "\n";
 
      s = s +
@@ -98,19 +88,24 @@ impl CodeGenerator {
        "}\n"
      ;
 
+     s = s + &constants::constants(&self.constants)?;
+
+     // An AIR with aux segments is verified as a proof over the quadratic extension
+     let extension = self.segment_widths.len() > 1;
+
      // Each segment
      for (segment, w) in self.segment_widths.iter().enumerate() {
        s = s + "\n// SEGMENT " + &segment.to_string() + " size " + &w.to_string() + "\n" + 
          "// ===============================================\n"
        ;
-       let (st,transition_degrees, transition_maxdeg) = 
-         transition::evaluate_transitions(&self.graph, segment,&self.integrity_constraints[segment])
+       let (st,transition_degrees) = 
+         transition::evaluate_transitions(&self.graph, segment,&self.integrity_constraints[segment], extension)?
        ; 
        s = s + &st;
 
 
-       let (sb,boundary_degrees, boundary_maxdeg, boundary_domain) = 
-         boundary::evaluate_boundaries(&self.graph,  &self.public_inputs, segment,&self.boundary_constraints[segment])
+       let sb = 
+         boundary::evaluate_boundaries(&self.graph,  &self.public_inputs, segment,&self.boundary_constraints[segment], extension)?
        ; 
        s = s + &sb;
 
@@ -121,174 +116,124 @@ impl CodeGenerator {
        s = s + "  coeffs_transition_a: felt*,\n";
        s = s + "  coeffs_transition_b: felt*, \n";
        s = s + "  t_evaluations: felt*, \n";
-       s = s + "  x: felt, \n";
+       // In a proof over the extension, the OOD point, the coefficients and the evaluations are
+       // extension elements, with the coefficients and the evaluations stored as two felts
+       if extension {
+         s += "  x: Ext2, \n";
+       } else {
+         s = s + "  x: felt, \n";
+       }
        s = s + "  trace_domain_generator: felt, \n";
-       s = s + ") -> felt {\n";
-       s = s + "  alloc_locals;\n";
-       s = s + "  local sum_0 = 0;\n";
+       if extension {
+         s += ") -> Ext2 {\n";
+         s += "  alloc_locals;\n";
+         s += "  local sum_0: Ext2 = Ext2(a0=0, a1=0);\n";
+       } else {
+         s = s + ") -> felt {\n";
+         s = s + "  alloc_locals;\n";
+         s = s + "  local sum_0 = 0;\n";
+       }
 
        s = s + "  // Evaluate transition divisor\n";
-       s = s + "  // Airscript only handles boundary constraints on one row\n";
-       s = s + "  // So the number of 'exemptions' forming the divisor for transitions is always 1\n";
+       s = s + "  // The transitions are not enforced on the last " + &self.num_transition_exemptions.to_string() + " row(s)\n";
+       s = s + "  // So each of these 'exemptions' is a factor of the divisor for transitions\n";
        s = s + "\n";
        s = s + "  let g = trace_domain_generator;\n";
-       s = s + "  let v1  = pow_g(x, trace_length);\n";
-       s = s + "  let numerator = v1 - 1;\n";
-       s = s + "  let v2 = pow_g(g, trace_length - 1);\n";
-       s = s + "  let denominator = sub_g(x, v2);\n";
-       s = s + "  let z = div_g(numerator, denominator);\n";
-       s = s + "  %{\n";
-       s = s + "    print('CAIRO transition divisor z = ',ids.z)\n";
-       s = s + "  %}\n";
-
-       let mut counter = 0;
-       for deg in 0 .. (transition_maxdeg+1) {
-         let mut ntrans = 0;
-         for trdeg in transition_degrees.iter() {
-           if deg == *trdeg { ntrans = ntrans + 1; }
+       if extension {
+         s += "  local v1: Ext2 = pow_e(x, trace_length);\n";
+         s += "  local numerator: Ext2 = sub_e(v1, Ext2(a0=1, a1=0));\n";
+         s += "  let v2 = pow_g(g, trace_length - 1);\n";
+         s += "  local d1: Ext2 = sub_e(x, Ext2(a0=v2, a1=0));\n";
+         for i in 2 .. (self.num_transition_exemptions+1) {
+           s += &format!("  let e{i} = pow_g(g, trace_length - {i});\n");
+           s += &format!("  local f{i}: Ext2 = sub_e(x, Ext2(a0=e{i}, a1=0));\n");
+           s += &format!("  local d{i}: Ext2 = mul_e(d{}, f{i});\n", i - 1);
          }
-
-         if ntrans > 0 {
-           s = s + "\n  // Merge degree "+ &deg.to_string() +"\n";
-           s = s + "  let evaluation_degree = "+&deg.to_string() +" * (trace_length - 1);\n";
-           s = s + "  let degree_adjustment = target_degree - evaluation_degree;\n";
-           s = s + "  let xp = pow_g(x, degree_adjustment);\n";
-           for (tr, trdeg) in transition_degrees.iter().enumerate() {
-             if deg == *trdeg {
-               let trno = &tr.to_string();
-               s = s + "\n  // Include transition " + &trno + "\n";
-               s = s + "  let v1 = mul_g(coeffs_transition_b["+&trno+"],  xp);\n";
-               s = s + "  let v2 = add_g(coeffs_transition_a["+ &trno +"], v1);\n";
-               s = s + "  let v3 = mul_g(v2, t_evaluations["+&trno+"]);\n";
-               s = s + "  local sum_"+&(counter+1).to_string() +" = add_g(sum_"+&counter.to_string()+",v3);\n";
-               counter = counter + 1;
-             }
+         s += &format!("  local z: Ext2 = div_e(numerator, d{});\n", self.num_transition_exemptions);
+       } else {
+         s = s + "  let v1  = pow_g(x, trace_length);\n";
+         s = s + "  let numerator = v1 - 1;\n";
+         s = s + "  let v2 = pow_g(g, trace_length - 1);\n";
+         if self.num_transition_exemptions == 1 {
+           s = s + "  let denominator = sub_g(x, v2);\n";
+         } else {
+           s = s + "  let d1 = sub_g(x, v2);\n";
+           for i in 2 .. (self.num_transition_exemptions+1) {
+             let prev = (i-1).to_string();
+             let cur = i.to_string();
+             s = s + "  let e" + &cur + " = pow_g(g, trace_length - " + &cur + ");\n";
+             s = s + "  let f" + &cur + " = sub_g(x, e" + &cur + ");\n";
+             s = s + "  let d" + &cur + " = mul_g(d" + &prev + ", f" + &cur + ");\n";
            }
+           s = s + "  let denominator = d" + &self.num_transition_exemptions.to_string() + ";\n";
          }
+         s = s + "  let z = div_g(numerator, denominator);\n";
        }
 
-       s = s + "\n  return div_g(sum_"+&counter.to_string()+",z);\n";
-       s = s + "}\n";
-
-// WARNING: THIS CODE ONLY HANDLES BOUNDARIES ON MAIN SEGMENT
-// AUX SEGMENT REQUIRES SLIGHTLY DIFFERENT CALCULATION
-// USES TRACE LENGTH INSTEAD OF PUBLIC INPUT STEPS
-       s = s + "func merge_boundary_" + &segment.to_string() + "{range_check_ptr}(\n";
-       s = s + "  trace_length: felt,\n";
-       s = s + "  blowup_factor: felt,\n";
-       s = s + "  coeffs_boundary_a: felt*,\n";
-       s = s + "  coeffs_boundary_b: felt*, \n";
-       s = s + "  b_evaluations: felt*, \n";
-       s = s + "  trace_domain_generator: felt, \n";
-       s = s + "  npub_steps: felt, \n";
-       s = s + "  x: felt, \n";
-       s = s + ") -> felt {\n";
-       s = s + "  alloc_locals;\n";
-       s = s + "  %{\n";
-       s = s + "    print('CAIRO OOD evaluation x = ',ids.x)\n";
-       s = s + "  %}\n";
-
-
-       s = s + "  // Evaluate boundary divisor\n";
-       s = s + "  let g = trace_domain_generator;\n";
-
-       s = s + "  let composition_degree = trace_length * blowup_factor - 1;\n";
-
-       s = s + "  let trace_poly_degree = trace_length  - 1;\n";
-       s = s + "  let divisor_degree = 1;\n";
-       s = s + "  let target_degree =  composition_degree + divisor_degree;\n";
-       s = s + " %{\n";
-       s = s + "    print('CAIRO Trace length ', ids.trace_poly_degree)\n";
-       s = s + "    print('CAIRO blowup factor ', ids.blowup_factor)\n";
-       s = s + "    print('CAIRO Composition degree ', ids.composition_degree)\n";
-       s = s + "    print('CAIRO Divisor degree ', ids.divisor_degree)\n";
-       s = s + "    print('CAIRO Target degree ', ids.target_degree)\n";
-       s = s + " %}\n";
- 
-
-       s = s + "  // Evaluate divisor\n";
-       s = s + "  let first_z = sub_g(x, 1);\n";
-
-       s = s + "  let v1 = sub_g(trace_length, 1);\n";
-       s = s + "  let v2 = pow_g(g, v1);\n";
-       s = s + "  let last_z = sub_g(x, v2);\n"; // (x - g^(trace_length-1))
-       s = s + "  %{\n";
-       s = s + "    print('CAIRO DIVISOR first ',ids.first_z)\n";
-       s = s + "    print('CAIRO DIVISOR last  ',ids.last_z)\n";
-       s = s + "  %}\n";
+       // As in Winterfell, the constraints are grouped by their evaluation degree, which depends
+       // on their degree and on the cycles of the periodic columns they use
+       let mut groups: BTreeMap<(usize, Vec<usize>), Vec<usize>> = BTreeMap::new();
+       for (tr, trdeg) in transition_degrees.iter().enumerate() {
+         groups.entry((trdeg.base(), trdeg.cycles().to_vec())).or_default().push(tr);
+       }
 
-       s = s + "\n";
-       s = s + "  local first_sum_0 = 0;\n";
-       s = s + "  local last_sum_0 = 0;\n";
-       let mut first_counter = 0;
-       let mut last_counter = 0;
-       for deg in 0 ..(boundary_maxdeg+1) {
-         let mut ntrans = 0;
-         for trdeg in boundary_degrees.iter() {
-           if deg == *trdeg { ntrans = ntrans + 1; }
+       let mut counter = 0;
+       for (group_index, ((deg, cycles), group)) in groups.into_iter().enumerate() {
+         s = s + "\n  // Merge degree "+ &deg.to_string();
+         if !cycles.is_empty() {
+           s = s + ", cycles " + &cycles.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ");
          }
-
-         if ntrans > 0 {
-           s = s + "\n  // Merge degree "+ &deg.to_string() +"\n";
-           s = s + "  let evaluation_degree = "+&deg.to_string() +" * (trace_length - 1);\n";
-           s = s + "  let degree_adjustment = target_degree - evaluation_degree;\n";
-       s = s + "  %{\n";
-       s = s + "    print('CAIRO evaluation degree ', ids.evaluation_degree)\n";
-       s = s + "    print('CAIRO degree adjustment ', ids.degree_adjustment)\n";
-       s = s + "  %}\n";
+         s = s + "\n";
+         // Each periodic column of n/cycle cycles adds (n/cycle)*(cycle-1) to the degree
+         let mut evaluation_degree = deg.to_string() + " * (trace_length - 1)";
+         for (i, cycle) in cycles.iter().enumerate() {
+           s = s + "  let num_cycles_" + &i.to_string() + " = div_g(trace_length, " + &cycle.to_string() + ");\n";
+           evaluation_degree = evaluation_degree + " + num_cycles_" + &i.to_string() + " * " + &(cycle - 1).to_string();
+         }
+         s = s + "  let evaluation_degree = " + &evaluation_degree + ";\n";
+         s = s + "  let degree_adjustment = target_degree - evaluation_degree;\n";
+         let xp = "xp_".to_string() + &group_index.to_string();
+         if extension {
+           s += &format!("  local {xp}: Ext2 = pow_e(x, degree_adjustment);\n");
+         } else {
            s = s + "  let xp = pow_g(x, degree_adjustment);\n";
-           for (tr, trdeg) in boundary_degrees.iter().enumerate() {
-             if deg == *trdeg {
-               let trno = &tr.to_string();
-               s = s + "\n  // Include boundary " + &trno + "\n";
-               s = s + "  let v1 = mul_g(coeffs_boundary_b["+&trno+"],  xp);\n";
-               s = s + "  let v2 = add_g(coeffs_boundary_a["+ &trno +"], v1);\n";
-               s = s + "  let v3 = mul_g(v2, b_evaluations["+&trno+"]);\n";
-       //s = s + "  %{\n";
-       //s = s + "    print('CAIRO numerator component ', ids.v3)\n";
-       //s = s + "  %}\n";
-
-               match boundary_domain[tr] {
-                 ConstraintDomain::FirstRow => {
-                    s = s + "  local first_sum_"+&(first_counter+1).to_string() +" = add_g(first_sum_"+&first_counter.to_string()+",v3);\n";
-                    first_counter = first_counter + 1;
-                 },
-                 ConstraintDomain::LastRow => {
-                    s = s + "  local last_sum_"+&(last_counter+1).to_string() +" = add_g(last_sum_"+&last_counter.to_string()+",v3);\n";
-                    last_counter = last_counter + 1;
-                 },
-                 _ => { panic!("Bad Boundary Constraint Domain"); }
-               }
-             }
+         }
+         for tr in group {
+           let trno = &tr.to_string();
+           s = s + "\n  // Include transition " + &trno + "\n";
+           if extension {
+             let coeff_a = showvalue::ext2_element("coeffs_transition_a", tr);
+             let coeff_b = showvalue::ext2_element("coeffs_transition_b", tr);
+             let evaluation = showvalue::ext2_element("t_evaluations", tr);
+             s += &format!("  local v1_{tr}: Ext2 = mul_e({coeff_b}, {xp});\n");
+             s += &format!("  local v2_{tr}: Ext2 = add_e({coeff_a}, v1_{tr});\n");
+             s += &format!("  local v3_{tr}: Ext2 = mul_e(v2_{tr}, {evaluation});\n");
+             s += &format!("  local sum_{}: Ext2 = add_e(sum_{counter}, v3_{tr});\n", counter + 1);
+           } else {
+             s = s + "  let v1 = mul_g(coeffs_transition_b["+&trno+"],  xp);\n";
+             s = s + "  let v2 = add_g(coeffs_transition_a["+ &trno +"], v1);\n";
+             s = s + "  let v3 = mul_g(v2, t_evaluations["+&trno+"]);\n";
+             s = s + "  local sum_"+&(counter+1).to_string() +" = add_g(sum_"+&counter.to_string()+",v3);\n";
            }
+           counter = counter + 1;
          }
        }
 
-       s = s + "let first_sum = first_sum_"+&first_counter.to_string()+";\n";
-       s = s + "let last_sum = last_sum_"+&last_counter.to_string()+";\n";
-       s = s + "  %{\n";
-       s = s + "    print('CAIRO final numerator first', ids.first_sum)\n";
-       s = s + "    print('CAIRO final numerator  last', ids.last_sum)\n";
-       s = s + "  %}\n";
-       s = s + "\n";
-       s = s + "  let first = div_g(first_sum,first_z);\n";
-       s = s + "  let last = div_g(last_sum,last_z);\n";
-       s = s + "  %{\n";
-       s = s + "    print('CAIRO quotient first', ids.first)\n";
-       s = s + "    print('CAIRO quotient last', ids.last)\n";
-       s = s + "  %}\n";
-       s = s + "  let combined = add_g(first,last);\n";
-       s = s + "  %{\n";
-       s = s + "    print('CAIRO combined ', ids.combined)\n";
-       s = s + "  %}\n";
-       s = s + "  return combined;\n";
+       if extension {
+         s += &format!("\n  return div_e(sum_{counter}, z);\n");
+       } else {
+         s = s + "\n  return div_g(sum_"+&counter.to_string()+",z);\n";
+       }
        s = s + "}\n";
 
+       s = s + &boundary::merge_boundaries(&self.graph, segment, &self.boundary_constraints[segment], extension)?;
 
      } // segments
 
-     s = s + "\n// PUT CONSTRAINT EVALUATION FUNCTION HERE\n";
+     s = s + &periodic::evaluate_periodic_columns(&self.periodic_columns, extension);
+     s = s + &evaluate::evaluate_constraints(self.segment_widths.len(), &self.public_inputs, &self.integrity_constraints, &self.boundary_constraints, self.num_transition_exemptions);
+     s += &air_instance::air_instance(&self.segment_widths, &self.layout, &self.constants)?;
 
-     return s + "\n";
+     return Ok(s + "\n");
   } // generate
 } // CodeGenerator
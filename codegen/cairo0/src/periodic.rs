@@ -0,0 +1,65 @@
+use ir::PeriodicColumn;
+use winter_math::{fft, fields::f64::BaseElement, StarkField};
+
+/// Generate the evaluation of the periodic columns at the OOD point
+///
+/// Each periodic column is interpolated at compile time into a polynomial over the domain of
+/// its cycle, exactly as Winterfell does. The generated `evaluate_periodic_columns` function
+/// evaluates each polynomial at `x^(trace_length / cycle_len)` and writes the results to
+/// `periodic_row`, so the verifier never has to trust periodic values supplied by the prover.
+///
+/// In a proof over the extension, the OOD point and the periodic values are extension elements,
+/// with each value stored as two consecutive felts.
+pub fn evaluate_periodic_columns(periodic_columns: &[PeriodicColumn], extension: bool) -> String
+{
+  let mut s = "\n// PERIODIC COLUMNS\n".to_string();
+  s += "// ===============================================\n";
+  s += "func evaluate_periodic_columns{range_check_ptr}(\n";
+  s += "  periodic_row: felt*,\n";
+  s += "  trace_length: felt,\n";
+  s += &format!("  x: {},\n", if extension { "Ext2" } else { "felt" });
+  s += ") {\n";
+  s += "  alloc_locals;\n";
+
+  for (col, values) in periodic_columns.iter().enumerate() {
+    let cycle_len = values.len();
+    let coeffs = interpolate(values);
+
+    s += &format!("\n  // Periodic column {col}: cycle length {cycle_len}\n");
+    s += &format!("  let num_cycles_{col} = div_g(trace_length, {cycle_len});\n");
+
+    // Horner evaluation, starting from the leading coefficient
+    let last = coeffs.len() - 1;
+    if extension {
+      s += &format!("  local z_{col}: Ext2 = pow_e(x, num_cycles_{col});\n");
+      s += &format!("  local p_{col}_{last}: Ext2 = Ext2(a0={}, a1=0);\n", coeffs[last]);
+      for i in (0..last).rev() {
+        s += &format!("  local t_{col}_{i}: Ext2 = mul_e(p_{col}_{}, z_{col});\n", i + 1);
+        s += &format!("  local p_{col}_{i}: Ext2 = add_e(t_{col}_{i}, Ext2(a0={}, a1=0));\n", coeffs[i]);
+      }
+      s += &format!("  assert periodic_row[{}] = p_{col}_0.a0;\n", 2 * col);
+      s += &format!("  assert periodic_row[{}] = p_{col}_0.a1;\n", 2 * col + 1);
+    } else {
+      s += &format!("  local z_{col} = pow_g(x, num_cycles_{col});\n");
+      s += &format!("  local p_{col}_{last} = {};\n", coeffs[last]);
+      for i in (0..last).rev() {
+        s += &format!("  let t = mul_g(p_{col}_{}, z_{col});\n", i + 1);
+        s += &format!("  local p_{col}_{i} = add_g(t, {});\n", coeffs[i]);
+      }
+      s += &format!("  assert periodic_row[{col}] = p_{col}_0;\n");
+    }
+  }
+
+  s += "\n  return ();\n";
+  s += "}\n";
+  s
+}
+
+/// Returns the coefficients of the polynomial interpolated from the values of a periodic column
+/// over the domain of its cycle.
+fn interpolate(values: &[u64]) -> Vec<u64> {
+  let mut poly = values.iter().map(|v| BaseElement::new(*v)).collect::<Vec<_>>();
+  let inv_twiddles = fft::get_inv_twiddles::<BaseElement>(poly.len());
+  fft::interpolate_poly(&mut poly, &inv_twiddles);
+  poly.iter().map(|c| c.as_int()).collect()
+}
@@ -1,73 +1,232 @@
+use ir::AccessType;
+use ir::CodegenError;
 use ir::Value;
 use ir::constraints::AlgebraicGraph;
 use ir::NodeIndex;
 use ir::constraints::Operation;
 use ir::constraints::ConstraintDomain;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use ir::layout::matrix_element;
+use ir::layout::vector_element;
 
 /// Cairo display of AlgebraicGraph::Value
-pub fn showvalue(domain: &ConstraintDomain, x: &Value) -> String {
-  match x {
-    Value::BoundConstant(_) => "BoundConstant".to_string(),
-    Value::InlineConstant(v) => v.to_string(),
-    Value::TraceElement(ita) => {
-      let trace_segment = &ita.trace_segment().to_string();
-      let offset = &ita.row_offset();
-      let colidx = &ita.col_idx().to_string();
-      match domain {
-        ConstraintDomain::FirstRow =>
-          "first_".to_string() +trace_segment+"["+ &colidx + "]",
-        ConstraintDomain::LastRow =>
-          "last_".to_string() +trace_segment+"["+ &colidx + "]",
-        _ =>
-        if *offset == 0 {
-          "cur_".to_string() +trace_segment+"["+ &colidx + "]"
-        } else {
-          "nxt_".to_string()+trace_segment+"[" + &colidx + "]"
-        }
+pub fn showvalue(domain: &ConstraintDomain, x: &Value) -> Result<String, CodegenError> {
+  let s = match x {
+    Value::BoundConstant(symbol_access) => {
+      let name = symbol_access.name();
+      match symbol_access.access_type() {
+        AccessType::Default => name.to_string(),
+        AccessType::Vector(idx) => vector_element(name, *idx),
+        AccessType::Matrix(row_idx, col_idx) => matrix_element(name, *row_idx, *col_idx),
+        AccessType::Slice(_) => return Err(CodegenError::unsupported_feature("slices of constants")),
       }
-    }
+    },
+    Value::InlineConstant(v) => v.to_string(),
+    Value::TraceElement(ita) => trace_row(domain, ita.trace_segment(), ita.row_offset())? + "[" + &ita.col_idx().to_string() + "]",
     Value::PeriodicColumn(index, _length) => "periodic_row[".to_string() + &index.to_string() + "]",
     Value::PublicInput(s, index) => s.to_string()+"[" + &index.to_string() + "]",
     Value::RandomValue(x) => "rand[".to_string() + &x.to_string() + "]",
+  };
+  Ok(s)
+}
+
+/// Name of the row of a trace segment in the current Cairo function
+///
+/// The evaluation frames only hold the current and the next rows.
+fn trace_row(domain: &ConstraintDomain, trace_segment: u8, offset: usize) -> Result<String, CodegenError> {
+  match (domain, offset) {
+    (ConstraintDomain::FirstRow, _) => Ok(format!("first_{trace_segment}")),
+    (ConstraintDomain::LastRow, _) => Ok(format!("last_{trace_segment}")),
+    (_, 0) => Ok(format!("cur_{trace_segment}")),
+    (_, 1) => Ok(format!("nxt_{trace_segment}")),
+    _ => Err(CodegenError::unsupported_feature("row offsets greater than 1")),
   }
 }
 
-pub fn binop(graph:&AlgebraicGraph, r:&str, op:&str, a: &NodeIndex, b:&NodeIndex, domain: &ConstraintDomain, counter: &mut i32) -> String {
-  let va = "v".to_string() +&counter.to_string(); *counter = *counter + 1;
-  let vb = "v".to_string() +&counter.to_string(); *counter = *counter + 1;
-  let sa = ascairo(graph, &va, a, domain, counter);
-  let sb = ascairo(graph, &vb, b, domain, counter);
-  sa + &sb + &format!("  let {} = {}_g({}, {});\n", r, op, va, vb)
+/// Field of the value of a node of the AlgebraicGraph
+///
+/// An AIR with aux segments is verified as a proof over the quadratic extension, in which the OOD
+/// frames of every segment, the periodic values and the random values are elements of the
+/// extension, and so is any node which depends on them. Constants and public inputs are in the
+/// base field, and so is everything in an AIR without aux segments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElemType {
+  Base,
+  Ext,
 }
 
+/// Cairo display of a leaf of the AlgebraicGraph, and the field of its value
+///
+/// An extension element is stored as two consecutive felts, so in a proof over the extension the
+/// frames, the periodic values and the random values hold two felts per column or value.
+fn leaf(domain: &ConstraintDomain, x: &Value, extension: bool) -> Result<(String, ElemType), CodegenError> {
+  match x {
+    Value::TraceElement(ita) if extension => {
+      let row = trace_row(domain, ita.trace_segment(), ita.row_offset())?;
+      Ok((ext2_element(&row, ita.col_idx()), ElemType::Ext))
+    },
+    Value::PeriodicColumn(index, _length) if extension => Ok((ext2_element("periodic_row", *index), ElemType::Ext)),
+    Value::RandomValue(x) => Ok((ext2_element("rand", *x), ElemType::Ext)),
+    _ => Ok((showvalue(domain, x)?, ElemType::Base)),
+  }
+}
+
+/// Extension element stored at the specified index of an array of extension elements
+pub fn ext2_element(array: &str, i: usize) -> String {
+  format!("Ext2(a0={array}[{}], a1={array}[{}])", 2 * i, 2 * i + 1)
+}
+
+/// Extension element holding a base field element
+fn lift(x: &str) -> String {
+  format!("Ext2(a0={x}, a1=0)")
+}
+
+/// Cairo assertion storing the value of a constraint in an array of evaluations
+///
+/// In a proof over the extension, the constraints evaluate to extension elements, which are
+/// stored as two consecutive felts.
+pub fn store(array: &str, i: usize, r: &str, elem_type: ElemType, extension: bool) -> String {
+  match (extension, elem_type) {
+    (false, _) => format!("  assert {array}[{i}] = {r};\n"),
+    (_, ElemType::Ext) => format!("  assert {array}[{}] = {r}.a0;\n  assert {array}[{}] = {r}.a1;\n", 2 * i, 2 * i + 1),
+    (_, ElemType::Base) => format!("  assert {array}[{}] = {r};\n  assert {array}[{}] = 0;\n", 2 * i, 2 * i + 1),
+  }
+}
+
+/// Local name bound to a node of the AlgebraicGraph
+///
+/// Trace elements render differently on the first and last rows, so nodes used by boundary
+/// constraints are named after their domain as well as their index.
+pub fn nodename(w: &NodeIndex, domain: &ConstraintDomain) -> String {
+  match domain {
+    ConstraintDomain::FirstRow => format!("first_v{}", w.0),
+    ConstraintDomain::LastRow => format!("last_v{}", w.0),
+    _ => format!("v{}", w.0),
+  }
+}
 
 /// Cairo evaluation of Air constraint from AlgenbraicGraph
-pub fn ascairo(graph: &AlgebraicGraph, r:&str, w: &NodeIndex, domain: &ConstraintDomain, counter: &mut i32) -> String {
-  let op = &graph.node(w).op;
-  match op {
-    Operation::Value(x) => "  let ".to_string() + r + " = " + &showvalue(domain, x) + ";\n",
-    Operation::Add(a, b) => binop(graph, r, "add", a, b, domain, counter),
-    Operation::Sub(a, b) => binop(graph, r, "sub", a, b, domain, counter),
-    Operation::Mul(a, b) => binop(graph, r, "mul", a, b, domain, counter),
-    Operation::Exp(a, j) => 
-      {
-        let va = "v".to_string() +&counter.to_string(); *counter = *counter + 1;
-        let sa = ascairo(graph, &va, a, domain, counter);
-           sa + &format!("  let {} = pow_g({}, {});\n", r, va, j)
+///
+/// Each node is bound to a single local and emitted once: nodes already in `emitted` are
+/// referenced rather than re-evaluated. The graph only adds a node after its children, so
+/// emitting the reachable nodes in index order is a topological order.
+///
+/// `emitted` records the field of every local, so that each operation is evaluated with the
+/// base field functions of `math_goldilocks` when both operands are base field elements, and
+/// with the `ext2` functions otherwise.
+pub fn ascairo(graph: &AlgebraicGraph, w: &NodeIndex, domain: &ConstraintDomain, extension: bool, emitted: &mut BTreeMap<String, ElemType>) -> Result<String, CodegenError> {
+  let mut reachable = BTreeSet::new();
+  let mut stack = vec![*w];
+  while let Some(index) = stack.pop() {
+    if emitted.contains_key(&nodename(&index, domain)) || !reachable.insert(index.0) {
+      continue;
+    }
+    match graph.node(&index).op() {
+      Operation::Value(_) => {},
+      Operation::Add(a, b) | Operation::Sub(a, b) | Operation::Mul(a, b) => {
+        stack.push(*a);
+        stack.push(*b);
+      },
+      Operation::Exp(a, _) => stack.push(*a),
+    }
+  }
+
+  let mut s = "".to_string();
+  for index in reachable.into_iter().map(NodeIndex) {
+    let r = nodename(&index, domain);
+    let operand = |x: &NodeIndex| {
+      let name = nodename(x, domain);
+      let elem_type = emitted[&name];
+      (name, elem_type)
+    };
+    let (line, elem_type) = match graph.node(&index).op() {
+      Operation::Value(x) => match leaf(domain, x, extension)? {
+        (value, ElemType::Base) => (format!("  let {r} = {value};\n"), ElemType::Base),
+        (value, ElemType::Ext) => (format!("  local {r}: Ext2 = {value};\n"), ElemType::Ext),
       },
+      Operation::Add(a, b) => binop(&r, "add", operand(a), operand(b)),
+      Operation::Sub(a, b) => binop(&r, "sub", operand(a), operand(b)),
+      Operation::Mul(a, b) => match (operand(a), operand(b)) {
+        ((a, ElemType::Ext), (b, ElemType::Base)) | ((b, ElemType::Base), (a, ElemType::Ext)) =>
+          (format!("  local {r}: Ext2 = mul_eg({a}, {b});\n"), ElemType::Ext),
+        (a, b) => binop(&r, "mul", a, b),
+      },
+      Operation::Exp(a, j) => {
+        let (a, elem_type) = operand(a);
+        (power(&r, &a, *j, elem_type), elem_type)
+      },
+    };
+    s += &line;
+    emitted.insert(r, elem_type);
   }
+  Ok(s)
 }
 
-/// Human readable costraint display
-pub fn str(graph:&AlgebraicGraph,  w: &NodeIndex, domain: &ConstraintDomain ) -> String {
-  let op = &graph.node(w).op;
-  match op {
-    Operation::Value(x) =>  showvalue(domain, x),
-    Operation::Add(a, b) => "(".to_string()  + &str(graph,a,domain) + " + " + &str(graph,b,domain) + ")",
-    Operation::Sub(a, b) => "(".to_string()  + &str(graph,a,domain) + " - " + &str(graph,b,domain) + ")", 
-    Operation::Mul(a, b) => "(".to_string()  + &str(graph,a,domain) + " * " + &str(graph,b,domain) + ")",
-    Operation::Exp(a, j) => "(".to_string()  + &str(graph,a,domain) + " ^ " + &j.to_string() + ")", 
+/// Binary operation in the base field if both operands are base field elements, or in the
+/// extension otherwise, with a base field operand lifted to the extension.
+fn binop(r: &str, op: &str, (a, a_type): (String, ElemType), (b, b_type): (String, ElemType)) -> (String, ElemType) {
+  match (a_type, b_type) {
+    (ElemType::Base, ElemType::Base) => (format!("  local {r} = {op}_g({a}, {b});\n"), ElemType::Base),
+    (ElemType::Ext, ElemType::Ext) => (format!("  local {r}: Ext2 = {op}_e({a}, {b});\n"), ElemType::Ext),
+    (ElemType::Base, ElemType::Ext) => (format!("  local {r}: Ext2 = {op}_e({}, {b});\n", lift(&a)), ElemType::Ext),
+    (ElemType::Ext, ElemType::Base) => (format!("  local {r}: Ext2 = {op}_e({a}, {});\n", lift(&b)), ElemType::Ext),
   }
 }
 
+/// Cairo evaluation of a power with a constant exponent
+///
+/// The exponent is known at compile time, so instead of calling the loop of `pow_g`, the power
+/// is unrolled into left-to-right square-and-multiply steps: x^7 takes the 4 multiplications
+/// x^2, x^3, x^6 and x^7. Each intermediate power of `a` is bound to a local named after its
+/// exponent, and the last one to `r`.
+fn power(r: &str, a: &str, exponent: usize, elem_type: ElemType) -> String {
+  let (mul, ty) = match elem_type {
+    ElemType::Base => ("mul_g", ""),
+    ElemType::Ext => ("mul_e", ": Ext2"),
+  };
+  match (exponent, elem_type) {
+    (0, ElemType::Base) => return format!("  let {r} = 1;\n"),
+    (0, ElemType::Ext) => return format!("  local {r}: Ext2 = {};\n", lift("1")),
+    (1, _) => return format!("  let {r} = {a};\n"),
+    _ => {},
+  }
+
+  let mut s = "".to_string();
+  let mut acc = a.to_string();
+  let mut k = 1;
+  let msb = usize::BITS - 1 - exponent.leading_zeros();
+  for bit in (0..msb).rev() {
+    k *= 2;
+    let square = if k == exponent { r.to_string() } else { format!("{r}_{k}") };
+    s += &format!("  local {square}{ty} = {mul}({acc}, {acc});\n");
+    acc = square;
+    if exponent >> bit & 1 == 1 {
+      k += 1;
+      let product = if k == exponent { r.to_string() } else { format!("{r}_{k}") };
+      s += &format!("  local {product}{ty} = {mul}({acc}, {a});\n");
+      acc = product;
+    }
+  }
+  s
+}
 
+/// Human readable display of the operation of a constraint node
+///
+/// The operands which are not leaves are written as the locals they are bound to by `ascairo`
+/// rather than expanded, so that the display of a constraint is as small as its operation.
+pub fn str(graph:&AlgebraicGraph,  w: &NodeIndex, domain: &ConstraintDomain ) -> Result<String, CodegenError> {
+  let operand = |x: &NodeIndex| match graph.node(x).op() {
+    Operation::Value(v) => showvalue(domain, v),
+    _ => Ok(nodename(x, domain)),
+  };
+  let s = match graph.node(w).op() {
+    Operation::Value(x) =>  showvalue(domain, x)?,
+    Operation::Add(a, b) => operand(a)? + " + " + &operand(b)?,
+    Operation::Sub(a, b) => operand(a)? + " - " + &operand(b)?,
+    Operation::Mul(a, b) => operand(a)? + " * " + &operand(b)?,
+    Operation::Exp(a, j) => operand(a)? + " ^ " + &j.to_string(),
+  };
+  Ok(nodename(w, domain) + " = " + &s)
+}
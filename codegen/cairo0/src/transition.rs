@@ -1,5 +1,10 @@
 use ir::constraints::AlgebraicGraph;
 use ir::constraints::ConstraintRoot;
+use ir::CodegenError;
+use ir::IntegrityConstraintDegree;
+use ir::ConstraintLocation;
+
+use std::collections::BTreeMap;
 
 use super::showvalue;
 use showvalue::str;
@@ -9,20 +14,20 @@ pub fn evaluate_transitions(
   graph: &AlgebraicGraph, 
   segment: usize, 
   integrity_constraints: &Vec<ConstraintRoot>,
-) -> (
+  extension: bool,
+) -> Result<(
   String, 
-  Vec<usize>,  // constraint degrees
-  usize        // maximum degree
-)
+  Vec<IntegrityConstraintDegree>  // constraint degrees
+), CodegenError>
 {
-  let mut counter : i32 = 0;
+  let mut emitted = BTreeMap::new();
   let mut s = "".to_string();
     if segment == 0 { // MAIN
       s = s + 
       "func evaluate_transition_" + &segment.to_string() + "{range_check_ptr} (\n" + 
       "  frame_0: EvaluationFrame,\n" + 
       "  t_evaluations: felt*,\n" + 
-      "  periodic_row: felt*,\n" +                       // periodic value vector FIXME: DESIGN FAULT!
+      "  periodic_row: felt*,\n" +
       ") {\n" + 
       "  alloc_locals;\n" + 
       "  let cur_0 = frame_0.current;\n" + 
@@ -34,7 +39,7 @@ pub fn evaluate_transitions(
         "  frame_0: EvaluationFrame,\n" + 
         "  frame_1: EvaluationFrame,\n" + 
         "  t_evaluations: felt*,\n" + 
-        "  periodic_row: felt*,\n" +                       // periodic value vector FIXME: DESIGN FAULT!
+        "  periodic_row: felt*,\n" +
         "  rand: felt*,\n" + 
         ") {\n" + 
         "  alloc_locals;\n" + 
@@ -46,7 +51,7 @@ pub fn evaluate_transitions(
     }
   ;
 
-  let mut transition_degrees: Vec<usize> = Vec::new();
+  let mut transition_degrees: Vec<IntegrityConstraintDegree> = Vec::new();
  
   // transition constraints
   s = s + "// TRANSITION CONSTRAINTS\n\n";
@@ -55,23 +60,20 @@ pub fn evaluate_transitions(
   for (i, w) in vc.iter().enumerate() {
     let domain = &w.domain; 
     //s = s + "    // #" + &i.to_string() + ": root node " + &w.index.0.to_string() + " Domain: " + &w.domain.to_string() + "\n";
-    s = s + "  // " + &str(&graph,&w.index,domain) + "\n";
-    let r = "v".to_string() + &counter.to_string(); counter = counter + 1;
-    let eval = &showvalue::ascairo(&graph,&r, &w.index, domain, &mut counter);
-    s = s + &eval + "  assert t_evaluations[" + &i.to_string() + "] = " + &r + ";\n";
-    let degree = &graph.degree(&w.index).base();
-    s = s + "  // deg = " + &degree.to_string() + "\n\n";
-    transition_degrees.push(*degree);
-  }
-  let mut transition_maxdeg : usize = 0;
-  for w in transition_degrees.iter() {
-    transition_maxdeg = transition_maxdeg.max(*w);
+    let location = ConstraintLocation::integrity(segment as u8, i);
+    s = s + "  // " + &str(&graph,&w.index,domain).map_err(|err| err.in_constraint(location))? + "\n";
+    let r = showvalue::nodename(&w.index, domain);
+    let eval = &showvalue::ascairo(&graph, &w.index, domain, extension, &mut emitted).map_err(|err| err.in_constraint(location))?;
+    s = s + &eval + &showvalue::store("t_evaluations", i, &r, emitted[&r], extension);
+    let degree = graph.degree(&w.index);
+    s = s + "  // deg = " + &degree.base().to_string() + "\n\n";
+    transition_degrees.push(degree);
   }
 
   s = s + "\n  return ();\n";
   s = s + "}\n\n";
 
-  return (s, transition_degrees,transition_maxdeg);
+  return Ok((s, transition_degrees));
 }
 
 
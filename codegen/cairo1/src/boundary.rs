@@ -0,0 +1,196 @@
+use super::expression::{evaluate_constraint, scale, segment_type, ElemType};
+use ir::{
+    constraints::{AlgebraicGraph, ConstraintDomain, ConstraintRoot, Operation},
+    CodegenError, ConstraintLocation, NodeIndex, PublicInput, Value,
+};
+use std::collections::BTreeMap;
+
+/// Returns the `evaluate_boundary_{segment}` function, which evaluates the boundary constraints
+/// of a trace segment and returns their evaluations in the order of the constraints.
+///
+/// The constraints are evaluated against a frame whose current row is the row of the OOD frame,
+/// as in Winterfell, so constraints on the first and on the last row read the same values and
+/// only differ by their divisor.
+///
+/// # Errors
+/// Returns an error naming the constraint if it uses a feature which is not supported by the
+/// Cairo 1 verifier.
+pub fn evaluate_boundaries(
+    graph: &AlgebraicGraph,
+    public_inputs: &[PublicInput],
+    segment: usize,
+    boundary_constraints: &[ConstraintRoot],
+) -> Result<String, CodegenError> {
+    let ty = segment_type(segment).cairo_type();
+
+    let mut code = format!("pub fn evaluate_boundary_{segment}(\n");
+    code += "    frame_0: EvaluationFrame,\n";
+    if segment > 0 {
+        code += &format!("    frame_{segment}: AuxEvaluationFrame,\n");
+    }
+    for (name, _) in public_inputs.iter() {
+        code += &format!("    {name}: Span<u64>,\n");
+    }
+    if segment > 0 {
+        code += "    rand: Span<Ext2>,\n";
+    }
+    code += &format!(") -> Array<{ty}> {{\n");
+    code += "    let first_0 = frame_0.current;\n";
+    code += "    let last_0 = frame_0.next;\n";
+    if segment > 0 {
+        code += &format!("    let first_{segment} = frame_{segment}.current;\n");
+        code += &format!("    let last_{segment} = frame_{segment}.next;\n");
+    }
+    code += &format!("    let mut b_evaluations: Array<{ty}> = ArrayTrait::new();\n\n");
+
+    let mut emitted = BTreeMap::new();
+    for (i, constraint) in boundary_constraints.iter().enumerate() {
+        let location = ConstraintLocation::boundary(segment as u8, i);
+        code += &evaluate_constraint(graph, constraint, segment, "b_evaluations", &mut emitted)
+            .map_err(|err| err.in_constraint(location))?;
+    }
+
+    code += "    b_evaluations\n";
+    code += "}\n\n";
+    Ok(code)
+}
+
+/// Returns the `merge_boundary_{segment}` function, which merges the evaluations of the boundary
+/// constraints of a trace segment into their contribution to the constraint composition
+/// polynomial at the OOD point `x`.
+///
+/// This follows Winterfell's `BoundaryConstraints`: every boundary constraint is a single-step
+/// assertion, so they are grouped by step (the first row or the last step) and divided by
+/// `x - g^step`. All groups share the same degree adjustment, which depends only on the trace
+/// length. The composition coefficients are assigned in the order in which Winterfell sorts
+/// assertions: by step, then by column.
+///
+/// # Errors
+/// Returns an error naming the constraint if it is not an assertion against a single column on
+/// the first or last row.
+pub fn merge_boundaries(
+    graph: &AlgebraicGraph,
+    segment: usize,
+    boundary_constraints: &[ConstraintRoot],
+) -> Result<String, CodegenError> {
+    let elem_type = segment_type(segment);
+    let ty = elem_type.cairo_type();
+    let (add, _) = elem_type.add_mul();
+
+    let mut code = format!("pub fn merge_boundary_{segment}(\n");
+    code += "    trace_length: u64,\n";
+    code += "    blowup_factor: u64,\n";
+    code += "    coeffs_boundary_a: Span<u64>,\n";
+    code += "    coeffs_boundary_b: Span<u64>,\n";
+    code += &format!("    b_evaluations: Span<{ty}>,\n");
+    code += "    trace_domain_generator: u64,\n";
+    code += "    last_step: u64,\n";
+    code += "    x: u64,\n";
+    code += &format!(") -> {ty} {{\n");
+
+    code += "    // Evaluate the degree adjustment\n";
+    code += "    let composition_degree = trace_length * blowup_factor - 1;\n";
+    code += "    let trace_poly_degree = trace_length - 1;\n";
+    code += "    let divisor_degree: u64 = 1;\n";
+    code += "    let target_degree = composition_degree + divisor_degree;\n";
+    code += "    let degree_adjustment = target_degree - trace_poly_degree;\n";
+    code += "    let xp = pow_g(x, degree_adjustment);\n\n";
+
+    code += "    // Evaluate the divisors\n";
+    code += "    let first_z = sub_g(x, 1);\n";
+    code += "    let last_z = sub_g(x, pow_g(trace_domain_generator, last_step));\n\n";
+
+    code += &format!("    let first_sum: {ty} = {};\n", elem_type.zero());
+    code += &format!("    let last_sum: {ty} = {};\n", elem_type.zero());
+
+    let coeff_indices = coefficient_order(graph, segment, boundary_constraints)?;
+    for (i, constraint) in boundary_constraints.iter().enumerate() {
+        let sum = match constraint.domain {
+            ConstraintDomain::FirstRow => "first_sum",
+            ConstraintDomain::LastRow => "last_sum",
+            domain => {
+                let location = ConstraintLocation::boundary(segment as u8, i);
+                return Err(invalid_domain(domain).in_constraint(location));
+            }
+        };
+        let j = coeff_indices[i];
+        code += &format!("\n    // Include boundary {i}\n");
+        code += &format!("    let v1 = mul_g(*coeffs_boundary_b.at({j}), xp);\n");
+        code += &format!("    let v2 = add_g(*coeffs_boundary_a.at({j}), v1);\n");
+        code += &format!(
+            "    let v3 = {};\n",
+            scale(elem_type, "v2", i, "b_evaluations")
+        );
+        code += &format!("    let {sum} = {add}({sum}, v3);\n");
+    }
+
+    code += "\n";
+    match elem_type {
+        ElemType::Base => {
+            code += "    let first = div_g(first_sum, first_z);\n";
+            code += "    let last = div_g(last_sum, last_z);\n";
+        }
+        ElemType::Ext => {
+            code += "    let first = mul_eg(first_sum, inv_g(first_z));\n";
+            code += "    let last = mul_eg(last_sum, inv_g(last_z));\n";
+        }
+    }
+    code += &format!("    {add}(first, last)\n");
+    code += "}\n";
+    Ok(code)
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns, for each boundary constraint, the index of its composition coefficients. Winterfell
+/// sorts the assertions of a segment by step and then by column before assigning coefficients.
+fn coefficient_order(
+    graph: &AlgebraicGraph,
+    segment: usize,
+    boundary_constraints: &[ConstraintRoot],
+) -> Result<Vec<usize>, CodegenError> {
+    let keys = boundary_constraints
+        .iter()
+        .enumerate()
+        .map(|(i, constraint)| {
+            let step = match constraint.domain {
+                ConstraintDomain::FirstRow => 0,
+                _ => 1,
+            };
+            let column = boundary_column(graph, &constraint.index)
+                .map_err(|err| err.in_constraint(ConstraintLocation::boundary(segment as u8, i)))?;
+            Ok((step, column))
+        })
+        .collect::<Result<Vec<_>, CodegenError>>()?;
+
+    let mut sorted = keys.clone();
+    sorted.sort();
+    Ok(keys
+        .iter()
+        .map(|key| sorted.iter().position(|k| k == key).unwrap())
+        .collect())
+}
+
+/// Returns the column constrained by the boundary constraint with the specified root.
+fn boundary_column(graph: &AlgebraicGraph, index: &NodeIndex) -> Result<usize, CodegenError> {
+    match graph.node(index).op() {
+        Operation::Sub(lhs, _) => match graph.node(lhs).op() {
+            Operation::Value(Value::TraceElement(trace_access)) => Ok(trace_access.col_idx()),
+            _ => Err(CodegenError::invalid_constraint(
+                "expected a trace column on the left-hand side",
+            )),
+        },
+        _ => Err(CodegenError::invalid_constraint(
+            "expected the subtraction of an expression from a trace column",
+        )),
+    }
+}
+
+/// Returns the error for a boundary constraint which applies neither to the first nor to the
+/// last row.
+fn invalid_domain(domain: ConstraintDomain) -> CodegenError {
+    CodegenError::invalid_constraint(format!(
+        "boundary constraints must apply to the first or last row, not to {domain}"
+    ))
+}
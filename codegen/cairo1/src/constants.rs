@@ -0,0 +1,21 @@
+use ir::{layout::constant_elements, CodegenError, ConstantBinding};
+
+/// Returns the declarations of the named constants.
+///
+/// Every constant is declared as a Cairo `const` item, with vector and matrix constants unrolled
+/// into one `const` per element by [constant_elements].
+///
+/// # Errors
+/// Returns an error if two of the unrolled constants have the same name.
+pub fn constants(constants: &[ConstantBinding]) -> Result<String, CodegenError> {
+    if constants.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut code = "\n// CONSTANTS\n".to_string();
+    code += "// ===============================================\n";
+    for (name, value) in constant_elements(constants)? {
+        code += &format!("pub const {name}: u64 = {value};\n");
+    }
+    Ok(code)
+}
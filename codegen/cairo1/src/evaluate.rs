@@ -0,0 +1,139 @@
+use super::expression::{lift, segment_type};
+use ir::{constraints::ConstraintRoot, PublicInput};
+
+/// Returns the `evaluate_constraints` entry point, which evaluates and merges the transition and
+/// boundary constraints of every segment at the OOD point `x`, and returns the value of the
+/// constraint composition polynomial expected by the STARK verifier.
+///
+/// The composition coefficients are laid out as in `ConstraintCompositionCoefficients`: the
+/// coefficients of the main segment come first, followed by those of each aux segment, and the
+/// coefficients of each segment are passed to its merge functions as a slice.
+///
+/// The aux segments and the random values are in the quadratic extension, so an AIR with aux
+/// segments evaluates to an `Ext2`.
+pub fn evaluate_constraints(
+    nsegments: usize,
+    public_inputs: &[PublicInput],
+    integrity_constraints: &[Vec<ConstraintRoot>],
+    boundary_constraints: &[Vec<ConstraintRoot>],
+    num_transition_exemptions: usize,
+) -> String {
+    let mut code = "\n// CONSTRAINT EVALUATION\n".to_string();
+    code += "// ===============================================\n";
+    code += "pub fn evaluate_constraints(\n";
+    code += "    frame_0: EvaluationFrame,\n";
+    for segment in 1..nsegments {
+        code += &format!("    frame_{segment}: AuxEvaluationFrame,\n");
+    }
+    for (name, _) in public_inputs.iter() {
+        code += &format!("    {name}: Span<u64>,\n");
+    }
+    if nsegments > 1 {
+        code += "    rand: Span<Ext2>,\n";
+    }
+    code += "    coeffs_transition_a: Span<u64>,\n";
+    code += "    coeffs_transition_b: Span<u64>,\n";
+    code += "    coeffs_boundary_a: Span<u64>,\n";
+    code += "    coeffs_boundary_b: Span<u64>,\n";
+    code += "    trace_length: u64,\n";
+    code += "    blowup_factor: u64,\n";
+    code += "    trace_domain_generator: u64,\n";
+    code += "    x: u64,\n";
+    let ty = segment_type(nsegments - 1).cairo_type();
+    code += &format!(") -> {ty} {{\n");
+
+    // The transition divisor exempts the last rows, so its degree is the trace length minus the
+    // number of exemptions, and the last step is the last row which is not exempted
+    code += "    let composition_degree = trace_length * blowup_factor - 1;\n";
+    code += &format!(
+        "    let transition_target_degree = composition_degree + trace_length - {num_transition_exemptions};\n"
+    );
+    code += &format!("    let last_step = trace_length - {num_transition_exemptions};\n");
+
+    // Boundary constraints are evaluated on the current row of the OOD frame
+    code += "\n    // Boundary frames\n";
+    for segment in 0..nsegments {
+        let frame = if segment == 0 {
+            "EvaluationFrame"
+        } else {
+            "AuxEvaluationFrame"
+        };
+        code += &format!(
+            "    let b_frame_{segment} = {frame} {{ current: frame_{segment}.current, next: frame_{segment}.current }};\n"
+        );
+    }
+
+    // Periodic values are computed from the OOD point rather than supplied by the caller
+    code += "\n    // Periodic columns\n";
+    code += "    let periodic_row = evaluate_periodic_columns(trace_length, x).span();\n";
+
+    let mut transition_offset = 0;
+    let mut boundary_offset = 0;
+    for segment in 0..nsegments {
+        let num_transitions = integrity_constraints[segment].len();
+        let num_assertions = boundary_constraints[segment].len();
+
+        // Aux segments are evaluated against the main frame as well as their own
+        let mut transition_args = vec!["frame_0".to_string()];
+        let mut boundary_args = vec!["b_frame_0".to_string()];
+        if segment > 0 {
+            transition_args.push(format!("frame_{segment}"));
+            boundary_args.push(format!("b_frame_{segment}"));
+        }
+        transition_args.push("periodic_row".to_string());
+        boundary_args.extend(public_inputs.iter().map(|(name, _)| name.clone()));
+        if segment > 0 {
+            transition_args.push("rand".to_string());
+            boundary_args.push("rand".to_string());
+        }
+
+        code += &format!("\n    // Segment {segment}\n");
+        code += &format!(
+            "    let t_evaluations_{segment} = evaluate_transition_{segment}({});\n",
+            transition_args.join(", ")
+        );
+        code += &format!("    let t_{segment} = merge_transitions_{segment}(\n");
+        code += "        trace_length,\n";
+        code += "        transition_target_degree,\n";
+        code += &format!(
+            "        coeffs_transition_a.slice({transition_offset}, {num_transitions}),\n"
+        );
+        code += &format!(
+            "        coeffs_transition_b.slice({transition_offset}, {num_transitions}),\n"
+        );
+        code += &format!("        t_evaluations_{segment}.span(),\n");
+        code += "        x,\n";
+        code += "        trace_domain_generator,\n";
+        code += "    );\n";
+
+        code += &format!(
+            "    let b_evaluations_{segment} = evaluate_boundary_{segment}({});\n",
+            boundary_args.join(", ")
+        );
+        code += &format!("    let b_{segment} = merge_boundary_{segment}(\n");
+        code += "        trace_length,\n";
+        code += "        blowup_factor,\n";
+        code += &format!("        coeffs_boundary_a.slice({boundary_offset}, {num_assertions}),\n");
+        code += &format!("        coeffs_boundary_b.slice({boundary_offset}, {num_assertions}),\n");
+        code += &format!("        b_evaluations_{segment}.span(),\n");
+        code += "        trace_domain_generator,\n";
+        code += "        last_step,\n";
+        code += "        x,\n";
+        code += "    );\n";
+
+        // The base field sum of the main segment is lifted to the extension if there are aux
+        // segments
+        code += &match (segment, nsegments) {
+            (0, 1) => "    let sum = add_g(t_0, b_0);\n".to_string(),
+            (0, _) => format!("    let sum = {};\n", lift("add_g(t_0, b_0)")),
+            _ => format!("    let sum = add_e(sum, add_e(t_{segment}, b_{segment}));\n"),
+        };
+
+        transition_offset += num_transitions;
+        boundary_offset += num_assertions;
+    }
+
+    code += "\n    sum\n";
+    code += "}\n";
+    code
+}
@@ -0,0 +1,333 @@
+use ir::{
+    constraints::{AlgebraicGraph, ConstraintDomain, ConstraintRoot, Operation},
+    layout::{felt, matrix_element, vector_element},
+    AccessType, CodegenError, NodeIndex, Value,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+// ELEMENT TYPES
+// ================================================================================================
+
+/// The field of the value of a node of the [AlgebraicGraph].
+///
+/// As in Winterfell, the aux trace columns and the random values are elements of the quadratic
+/// extension, and so is any node which depends on them. Everything else is in the base field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElemType {
+    Base,
+    Ext,
+}
+
+impl ElemType {
+    /// Returns the Cairo type of the values of this field.
+    pub fn cairo_type(&self) -> &'static str {
+        match self {
+            Self::Base => "u64",
+            Self::Ext => "Ext2",
+        }
+    }
+
+    /// Returns the Cairo functions adding and multiplying two values of this field.
+    pub fn add_mul(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Base => ("add_g", "mul_g"),
+            Self::Ext => ("add_e", "mul_e"),
+        }
+    }
+
+    /// Returns the Cairo expression for the zero of this field.
+    pub fn zero(&self) -> String {
+        match self {
+            Self::Base => "0".to_string(),
+            Self::Ext => lift("0"),
+        }
+    }
+}
+
+/// Returns the Cairo expression of the extension element holding the provided base field
+/// element.
+pub fn lift(value: &str) -> String {
+    format!("Ext2 {{ a0: {value}, a1: 0 }}")
+}
+
+// CONSTRAINT EVALUATION
+// ================================================================================================
+
+/// Returns the Cairo statements which evaluate the node at the specified index and bind its value
+/// to the name returned by [nodename].
+///
+/// Each node is bound once: the nodes which are already in `emitted` are referenced rather than
+/// evaluated again. The graph only adds a node after its children, so evaluating the reachable
+/// nodes in index order is a topological order. `emitted` records the field of every binding, so
+/// that each operation uses the base field functions of `math_goldilocks` when both operands are
+/// base field elements, and the functions of `ext2` otherwise.
+///
+/// # Errors
+/// Returns an error if the evaluation uses a feature which is not supported by the Cairo 1
+/// verifier.
+pub fn evaluate(
+    graph: &AlgebraicGraph,
+    index: &NodeIndex,
+    domain: ConstraintDomain,
+    emitted: &mut BTreeMap<String, ElemType>,
+) -> Result<String, CodegenError> {
+    let mut reachable = BTreeSet::new();
+    let mut stack = vec![*index];
+    while let Some(index) = stack.pop() {
+        if emitted.contains_key(&nodename(&index, domain)) || !reachable.insert(index.0) {
+            continue;
+        }
+        match graph.node(&index).op() {
+            Operation::Value(_) => {}
+            Operation::Add(lhs, rhs) | Operation::Sub(lhs, rhs) | Operation::Mul(lhs, rhs) => {
+                stack.push(*lhs);
+                stack.push(*rhs);
+            }
+            Operation::Exp(lhs, _) => stack.push(*lhs),
+        }
+    }
+
+    let mut code = String::new();
+    for index in reachable.into_iter().map(NodeIndex) {
+        let name = nodename(&index, domain);
+        let operand = |index: &NodeIndex| {
+            let name = nodename(index, domain);
+            let elem_type = emitted[&name];
+            (name, elem_type)
+        };
+        let (statements, elem_type) = match graph.node(&index).op() {
+            Operation::Value(value) => {
+                let (value, elem_type) = leaf(domain, value)?;
+                let ty = elem_type.cairo_type();
+                (format!("    let {name}: {ty} = {value};\n"), elem_type)
+            }
+            Operation::Add(lhs, rhs) => binop(&name, "add", operand(lhs), operand(rhs)),
+            Operation::Sub(lhs, rhs) => binop(&name, "sub", operand(lhs), operand(rhs)),
+            Operation::Mul(lhs, rhs) => match (operand(lhs), operand(rhs)) {
+                ((lhs, ElemType::Ext), (rhs, ElemType::Base))
+                | ((rhs, ElemType::Base), (lhs, ElemType::Ext)) => (
+                    format!("    let {name} = mul_eg({lhs}, {rhs});\n"),
+                    ElemType::Ext,
+                ),
+                (lhs, rhs) => binop(&name, "mul", lhs, rhs),
+            },
+            Operation::Exp(lhs, exponent) => {
+                let (lhs, elem_type) = operand(lhs);
+                (power(&name, &lhs, *exponent, elem_type), elem_type)
+            }
+        };
+        code += &statements;
+        emitted.insert(name, elem_type);
+    }
+    Ok(code)
+}
+
+/// Returns the name of the binding which holds the value of the specified node.
+///
+/// Trace elements are read from different rows on the first and last rows, so the nodes of
+/// boundary constraints are named after their domain as well as their index.
+pub fn nodename(index: &NodeIndex, domain: ConstraintDomain) -> String {
+    match domain {
+        ConstraintDomain::FirstRow => format!("first_v{}", index.0),
+        ConstraintDomain::LastRow => format!("last_v{}", index.0),
+        _ => format!("v{}", index.0),
+    }
+}
+
+/// Returns a human-readable representation of the operation of the specified node, which is
+/// emitted as a comment above the evaluation of each constraint.
+///
+/// The operands which are not leaves are written as the bindings holding their values rather
+/// than expanded, so that the comment is as small as the operation itself.
+pub fn display(
+    graph: &AlgebraicGraph,
+    index: &NodeIndex,
+    domain: ConstraintDomain,
+) -> Result<String, CodegenError> {
+    let operand = |index: &NodeIndex| match graph.node(index).op() {
+        Operation::Value(value) => Ok(leaf(domain, value)?.0),
+        _ => Ok(nodename(index, domain)),
+    };
+    let result = match graph.node(index).op() {
+        Operation::Value(value) => leaf(domain, value)?.0,
+        Operation::Add(lhs, rhs) => format!("{} + {}", operand(lhs)?, operand(rhs)?),
+        Operation::Sub(lhs, rhs) => format!("{} - {}", operand(lhs)?, operand(rhs)?),
+        Operation::Mul(lhs, rhs) => format!("{} * {}", operand(lhs)?, operand(rhs)?),
+        Operation::Exp(lhs, exponent) => format!("{} ^ {exponent}", operand(lhs)?),
+    };
+    Ok(format!("{} = {result}", nodename(index, domain)))
+}
+
+// CONSTRAINTS
+// ================================================================================================
+
+/// Returns the field of the evaluations of the constraints of the specified segment.
+pub fn segment_type(segment: usize) -> ElemType {
+    if segment == 0 {
+        ElemType::Base
+    } else {
+        ElemType::Ext
+    }
+}
+
+/// Returns the Cairo expression multiplying the base field `coeff` by the evaluation at the
+/// specified index of the `evaluations` span.
+pub fn scale(elem_type: ElemType, coeff: &str, index: usize, evaluations: &str) -> String {
+    match elem_type {
+        ElemType::Base => format!("mul_g({coeff}, *{evaluations}.at({index}))"),
+        ElemType::Ext => format!("mul_eg(*{evaluations}.at({index}), {coeff})"),
+    }
+}
+
+/// Returns the evaluation of a constraint of the specified segment, which is appended to the
+/// named array of evaluations. The evaluations of aux segments are extension elements, so a
+/// constraint which only depends on the main trace is lifted to the extension.
+pub fn evaluate_constraint(
+    graph: &AlgebraicGraph,
+    constraint: &ConstraintRoot,
+    segment: usize,
+    array: &str,
+    emitted: &mut BTreeMap<String, ElemType>,
+) -> Result<String, CodegenError> {
+    let domain = constraint.domain;
+    let mut code = format!("    // {}\n", display(graph, &constraint.index, domain)?);
+    code += &evaluate(graph, &constraint.index, domain, emitted)?;
+    let name = nodename(&constraint.index, domain);
+    match (segment_type(segment), emitted[&name]) {
+        (ElemType::Ext, ElemType::Base) => {
+            code += &format!("    {array}.append({});\n", lift(&name));
+        }
+        _ => code += &format!("    {array}.append({name});\n"),
+    }
+    let degree = graph.degree(&constraint.index);
+    code += &format!("    // deg = {}, domain: {domain}\n\n", degree.base());
+    Ok(code)
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the Cairo expression of a leaf of the [AlgebraicGraph] and the field of its value.
+fn leaf(domain: ConstraintDomain, value: &Value) -> Result<(String, ElemType), CodegenError> {
+    let result = match value {
+        Value::BoundConstant(symbol_access) => {
+            let name = symbol_access.name();
+            let name = match symbol_access.access_type() {
+                AccessType::Default => name.to_string(),
+                AccessType::Vector(idx) => vector_element(name, *idx),
+                AccessType::Matrix(row_idx, col_idx) => matrix_element(name, *row_idx, *col_idx),
+                AccessType::Slice(_) => {
+                    return Err(CodegenError::unsupported_feature("slices of constants"))
+                }
+            };
+            (name, ElemType::Base)
+        }
+        Value::InlineConstant(value) => (felt(*value).to_string(), ElemType::Base),
+        Value::TraceElement(trace_access) => {
+            let row = trace_row(
+                domain,
+                trace_access.trace_segment(),
+                trace_access.row_offset(),
+            )?;
+            let elem_type = if trace_access.trace_segment() == 0 {
+                ElemType::Base
+            } else {
+                ElemType::Ext
+            };
+            (format!("*{row}.at({})", trace_access.col_idx()), elem_type)
+        }
+        Value::PeriodicColumn(idx, _) => (format!("*periodic_row.at({idx})"), ElemType::Base),
+        Value::PublicInput(name, idx) => (format!("*{name}.at({idx})"), ElemType::Base),
+        Value::RandomValue(idx) => (format!("*rand.at({idx})"), ElemType::Ext),
+    };
+    Ok(result)
+}
+
+/// Returns the name of the span holding the row of a trace segment which is read at the specified
+/// offset. The evaluation frames only hold the current and the next rows.
+fn trace_row(
+    domain: ConstraintDomain,
+    trace_segment: u8,
+    offset: usize,
+) -> Result<String, CodegenError> {
+    match (domain, offset) {
+        (ConstraintDomain::FirstRow, _) => Ok(format!("first_{trace_segment}")),
+        (ConstraintDomain::LastRow, _) => Ok(format!("last_{trace_segment}")),
+        (_, 0) => Ok(format!("cur_{trace_segment}")),
+        (_, 1) => Ok(format!("nxt_{trace_segment}")),
+        _ => Err(CodegenError::unsupported_feature(
+            "row offsets greater than 1",
+        )),
+    }
+}
+
+/// Returns the binding of a binary operation, evaluated in the base field if both operands are
+/// base field elements, or in the extension otherwise, with a base field operand lifted to the
+/// extension.
+fn binop(
+    name: &str,
+    op: &str,
+    (lhs, lhs_type): (String, ElemType),
+    (rhs, rhs_type): (String, ElemType),
+) -> (String, ElemType) {
+    match (lhs_type, rhs_type) {
+        (ElemType::Base, ElemType::Base) => (
+            format!("    let {name} = {op}_g({lhs}, {rhs});\n"),
+            ElemType::Base,
+        ),
+        (ElemType::Ext, ElemType::Ext) => (
+            format!("    let {name} = {op}_e({lhs}, {rhs});\n"),
+            ElemType::Ext,
+        ),
+        (ElemType::Base, ElemType::Ext) => (
+            format!("    let {name} = {op}_e({}, {rhs});\n", lift(&lhs)),
+            ElemType::Ext,
+        ),
+        (ElemType::Ext, ElemType::Base) => (
+            format!("    let {name} = {op}_e({lhs}, {});\n", lift(&rhs)),
+            ElemType::Ext,
+        ),
+    }
+}
+
+/// Returns the bindings which evaluate a power with a constant exponent.
+///
+/// The power is unrolled into left-to-right square-and-multiply steps, so x^7 takes the 4
+/// multiplications x^2, x^3, x^6 and x^7. Each intermediate power is bound to a name derived
+/// from its exponent, and the last one to `name`.
+fn power(name: &str, base: &str, exponent: usize, elem_type: ElemType) -> String {
+    let ty = elem_type.cairo_type();
+    let (_, mul) = elem_type.add_mul();
+    match (exponent, elem_type) {
+        (0, ElemType::Base) => return format!("    let {name}: {ty} = 1;\n"),
+        (0, ElemType::Ext) => return format!("    let {name}: {ty} = {};\n", lift("1")),
+        (1, _) => return format!("    let {name}: {ty} = {base};\n"),
+        _ => {}
+    }
+
+    let mut code = String::new();
+    let mut acc = base.to_string();
+    let mut k = 1;
+    let msb = usize::BITS - 1 - exponent.leading_zeros();
+    for bit in (0..msb).rev() {
+        k *= 2;
+        let square = if k == exponent {
+            name.to_string()
+        } else {
+            format!("{name}_{k}")
+        };
+        code += &format!("    let {square} = {mul}({acc}, {acc});\n");
+        acc = square;
+        if exponent >> bit & 1 == 1 {
+            k += 1;
+            let product = if k == exponent {
+                name.to_string()
+            } else {
+                format!("{name}_{k}")
+            };
+            code += &format!("    let {product} = {mul}({acc}, {base});\n");
+            acc = product;
+        }
+    }
+    code
+}
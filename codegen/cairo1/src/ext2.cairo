@@ -0,0 +1,75 @@
+use super::math_goldilocks::{add_g, sub_g, mul_g, inv_g};
+
+// An element a0 + a1 * phi of the quadratic extension of the Goldilocks field, where phi is a
+// root of x^2 - x + 2. This is the extension used by Winterfell's QuadExtension<BaseElement>,
+// so phi^2 = phi - 2.
+#[derive(Copy, Drop, PartialEq, Debug)]
+pub struct Ext2 {
+    pub a0: u64,
+    pub a1: u64,
+}
+
+pub fn add_e(a: Ext2, b: Ext2) -> Ext2 {
+    let r0 = add_g(a.a0, b.a0);
+    let r1 = add_g(a.a1, b.a1);
+    Ext2 { a0: r0, a1: r1 }
+}
+
+pub fn sub_e(a: Ext2, b: Ext2) -> Ext2 {
+    let r0 = sub_g(a.a0, b.a0);
+    let r1 = sub_g(a.a1, b.a1);
+    Ext2 { a0: r0, a1: r1 }
+}
+
+// (a0 + a1 * phi) * (b0 + b1 * phi) = (a0 * b0 - 2 * a1 * b1) + (a0 * b1 + a1 * b0 + a1 * b1) * phi
+pub fn mul_e(a: Ext2, b: Ext2) -> Ext2 {
+    let a0b0 = mul_g(a.a0, b.a0);
+    let a1b1 = mul_g(a.a1, b.a1);
+    let a1b1_double = add_g(a1b1, a1b1);
+    let r0 = sub_g(a0b0, a1b1_double);
+
+    let a_sum = add_g(a.a0, a.a1);
+    let b_sum = add_g(b.a0, b.a1);
+    let sum_product = mul_g(a_sum, b_sum);
+    let r1 = sub_g(sum_product, a0b0);
+    Ext2 { a0: r0, a1: r1 }
+}
+
+// multiply an extension element by a base field element
+pub fn mul_eg(a: Ext2, b: u64) -> Ext2 {
+    let r0 = mul_g(a.a0, b);
+    let r1 = mul_g(a.a1, b);
+    Ext2 { a0: r0, a1: r1 }
+}
+
+// The inverse is the conjugate (the image of the Frobenius map) divided by the norm, which is in
+// the base field.
+pub fn inv_e(a: Ext2) -> Ext2 {
+    let c0 = add_g(a.a0, a.a1);
+    let c1 = sub_g(0, a.a1);
+    let conjugate = Ext2 { a0: c0, a1: c1 };
+
+    let norm = mul_e(a, conjugate);
+    let norm_inv = inv_g(norm.a0);
+    mul_eg(conjugate, norm_inv)
+}
+
+pub fn div_e(a: Ext2, b: Ext2) -> Ext2 {
+    let b_inv = inv_e(b);
+    mul_e(a, b_inv)
+}
+
+// Returns base ** exp, computed with square-and-multiply.
+pub fn pow_e(base: Ext2, exp: u64) -> Ext2 {
+    let mut result = Ext2 { a0: 1, a1: 0 };
+    let mut base = base;
+    let mut exp = exp;
+    while exp != 0 {
+        if exp % 2 == 1 {
+            result = mul_e(result, base);
+        }
+        base = mul_e(base, base);
+        exp = exp / 2;
+    };
+    result
+}
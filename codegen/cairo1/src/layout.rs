@@ -0,0 +1,41 @@
+use ir::AirLayout;
+
+/// Returns the declarations of the layout constants of the AIR, which are computed from the
+/// constraints by [AirLayout].
+pub fn layout(layout: &AirLayout) -> String {
+    let mut code = "\n// LAYOUT\n".to_string();
+    code += "// ===============================================\n";
+    code += &format!(
+        "pub const MAIN_SEGMENT_WIDTH: u32 = {};\n",
+        layout.main_segment_width
+    );
+    code += &format!(
+        "pub const AUX_TRACE_WIDTH: u32 = {};\n",
+        layout.aux_trace_width
+    );
+    code += &format!(
+        "pub const NUM_AUX_SEGMENTS: u32 = {};\n",
+        layout.num_aux_segments
+    );
+    code += &format!(
+        "pub const NUM_RANDOM_VALUES: u32 = {};\n",
+        layout.num_random_values
+    );
+    code += &format!(
+        "pub const NUM_TRANSITION_CONSTRAINTS: u32 = {};\n",
+        layout.num_transition_constraints
+    );
+    code += &format!(
+        "pub const NUM_ASSERTIONS: u32 = {};\n",
+        layout.num_assertions
+    );
+    code += &format!(
+        "pub const CE_BLOWUP_FACTOR: u32 = {};\n",
+        layout.ce_blowup_factor
+    );
+    code += &format!(
+        "pub const EVAL_FRAME_SIZE: u32 = {};\n",
+        layout.eval_frame_size
+    );
+    code
+}
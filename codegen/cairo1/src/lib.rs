@@ -0,0 +1,107 @@
+use ir::{constraints::ConstraintRoot, AirIR, CodegenError};
+
+mod boundary;
+mod constants;
+mod evaluate;
+mod expression;
+mod layout;
+mod periodic;
+mod transition;
+
+// GENERATE CAIRO 1 CONSTRAINT EVALUATION
+// ================================================================================================
+
+/// CodeGenerator is used to generate the Cairo 1 functions which evaluate the constraints of the
+/// AirIR used to build the CodeGenerator at the OOD point, for use by a Cairo 1 STARK verifier.
+///
+/// The generated module evaluates the constraints over the Goldilocks field and its quadratic
+/// extension with the functions of the `math_goldilocks` and `ext2` modules shipped with this
+/// crate, which are expected to be siblings of the generated module. The evaluation frames are
+/// passed as typed spans, and no hints are used.
+pub struct CodeGenerator<'a> {
+    ir: &'a AirIR,
+}
+
+impl<'a> CodeGenerator<'a> {
+    // --- CONSTRUCTOR ----------------------------------------------------------------------------
+
+    /// Returns a new [CodeGenerator] for the provided AirIR.
+    pub fn new(ir: &'a AirIR) -> Self {
+        Self { ir }
+    }
+
+    /// Returns a string of Cairo 1 code evaluating the constraints of the AirIR with which this
+    /// [CodeGenerator] was instantiated.
+    ///
+    /// # Errors
+    /// Returns an error if a constraint uses a feature which the Cairo 1 verifier does not
+    /// support.
+    pub fn generate(&self) -> Result<String, CodegenError> {
+        let ir = self.ir;
+        let segment_widths = ir.trace_segment_widths();
+        let nsegments = segment_widths.len();
+        let graph = ir.constraint_graph();
+        let segments = 0..nsegments as u8;
+        let integrity_constraints = segments
+            .clone()
+            .map(|segment| ir.integrity_constraints(segment).to_vec())
+            .collect::<Vec<Vec<ConstraintRoot>>>();
+        let boundary_constraints = segments
+            .map(|segment| ir.boundary_constraints(segment).to_vec())
+            .collect::<Vec<Vec<ConstraintRoot>>>();
+
+        let mut code = format!("// Air name {}, {nsegments} segments\n", ir.air_name());
+        code += "use core::array::{ArrayTrait, SpanTrait};\n";
+        code += "use super::math_goldilocks::{add_g, sub_g, mul_g, pow_g, div_g, inv_g};\n";
+        if nsegments > 1 {
+            code += "use super::ext2::{Ext2, add_e, sub_e, mul_e, mul_eg};\n";
+        }
+
+        code += "\n#[derive(Copy, Drop)]\n";
+        code += "pub struct EvaluationFrame {\n";
+        code += "    pub current: Span<u64>,\n";
+        code += "    pub next: Span<u64>,\n";
+        code += "}\n";
+        if nsegments > 1 {
+            code += "\n#[derive(Copy, Drop)]\n";
+            code += "pub struct AuxEvaluationFrame {\n";
+            code += "    pub current: Span<Ext2>,\n";
+            code += "    pub next: Span<Ext2>,\n";
+            code += "}\n";
+        }
+
+        code += &constants::constants(ir.constants())?;
+
+        for (segment, width) in segment_widths.iter().enumerate() {
+            code += &format!("\n// SEGMENT {segment} size {width}\n");
+            code += "// ===============================================\n";
+            code +=
+                &transition::evaluate_transitions(graph, segment, &integrity_constraints[segment])?;
+            code += &boundary::evaluate_boundaries(
+                graph,
+                ir.public_inputs(),
+                segment,
+                &boundary_constraints[segment],
+            )?;
+            code += &transition::merge_transitions(
+                graph,
+                segment,
+                &integrity_constraints[segment],
+                ir.num_transition_exemptions(),
+            );
+            code += "\n";
+            code += &boundary::merge_boundaries(graph, segment, &boundary_constraints[segment])?;
+        }
+
+        code += &periodic::evaluate_periodic_columns(ir.periodic_columns());
+        code += &evaluate::evaluate_constraints(
+            nsegments,
+            ir.public_inputs(),
+            &integrity_constraints,
+            &boundary_constraints,
+            ir.num_transition_exemptions(),
+        );
+        code += &layout::layout(&ir.layout());
+        Ok(code)
+    }
+}
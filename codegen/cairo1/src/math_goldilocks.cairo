@@ -0,0 +1,62 @@
+// Arithmetic in the Goldilocks field, with modulus 2^64 - 2^32 + 1.
+//
+// A field element is represented by its canonical value, a u64 smaller than the modulus. Every
+// operation is computed with the integer arithmetic of the Cairo 1 core library, so, unlike
+// math_goldilocks.cairo for Cairo 0, no hints are needed and every result is checked by the
+// Sierra program itself.
+
+// 2^64 - 2^32 + 1
+pub const PG: u64 = 18446744069414584321;
+const PG_WIDE: u128 = 18446744069414584321;
+
+// reduces a u128 modulo PG
+fn reduce(a: u128) -> u64 {
+    let r: u128 = a % PG_WIDE;
+    r.try_into().unwrap()
+}
+
+pub fn add_g(a: u64, b: u64) -> u64 {
+    let a: u128 = a.into();
+    let b: u128 = b.into();
+    reduce(a + b)
+}
+
+pub fn sub_g(a: u64, b: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        // a < b < PG, so the result is smaller than PG
+        (PG - b) + a
+    }
+}
+
+pub fn mul_g(a: u64, b: u64) -> u64 {
+    let a: u128 = a.into();
+    let b: u128 = b.into();
+    reduce(a * b)
+}
+
+// Returns base ** exp, computed with square-and-multiply.
+pub fn pow_g(base: u64, exp: u64) -> u64 {
+    let mut result: u64 = 1;
+    let mut base = base;
+    let mut exp = exp;
+    while exp != 0 {
+        if exp % 2 == 1 {
+            result = mul_g(result, base);
+        }
+        base = mul_g(base, base);
+        exp = exp / 2;
+    };
+    result
+}
+
+// The inverse of a non-zero element, by Fermat's little theorem.
+pub fn inv_g(a: u64) -> u64 {
+    assert(a != 0, 'inverse of zero');
+    pow_g(a, PG - 2)
+}
+
+pub fn div_g(a: u64, b: u64) -> u64 {
+    mul_g(a, inv_g(b))
+}
@@ -0,0 +1,48 @@
+use ir::PeriodicColumn;
+use winter_math::{fft, fields::f64::BaseElement, StarkField};
+
+/// Returns the `evaluate_periodic_columns` function, which evaluates the periodic columns at the
+/// OOD point.
+///
+/// Each periodic column is interpolated at compile time into a polynomial over the domain of its
+/// cycle, exactly as Winterfell does. The generated function evaluates each polynomial at
+/// `x^(trace_length / cycle_len)` with Horner's rule and returns the values in the order in
+/// which the columns are declared.
+pub fn evaluate_periodic_columns(periodic_columns: &[PeriodicColumn]) -> String {
+    let mut code = "\n// PERIODIC COLUMNS\n".to_string();
+    code += "// ===============================================\n";
+    code += "pub fn evaluate_periodic_columns(trace_length: u64, x: u64) -> Array<u64> {\n";
+    code += "    let mut periodic_row: Array<u64> = ArrayTrait::new();\n";
+
+    for (col, values) in periodic_columns.iter().enumerate() {
+        let cycle_len = values.len();
+        let coeffs = interpolate(values);
+
+        code += &format!("\n    // Periodic column {col}: cycle length {cycle_len}\n");
+        code += &format!("    let z_{col} = pow_g(x, trace_length / {cycle_len});\n");
+
+        // Horner evaluation, starting from the leading coefficient
+        let last = coeffs.len() - 1;
+        code += &format!("    let p_{col}: u64 = {};\n", coeffs[last]);
+        for coeff in coeffs[..last].iter().rev() {
+            code += &format!("    let p_{col} = add_g(mul_g(p_{col}, z_{col}), {coeff});\n");
+        }
+        code += &format!("    periodic_row.append(p_{col});\n");
+    }
+
+    code += "\n    periodic_row\n";
+    code += "}\n";
+    code
+}
+
+/// Returns the coefficients of the polynomial interpolated from the values of a periodic column
+/// over the domain of its cycle.
+fn interpolate(values: &[u64]) -> Vec<u64> {
+    let mut poly = values
+        .iter()
+        .map(|v| BaseElement::new(*v))
+        .collect::<Vec<_>>();
+    let inv_twiddles = fft::get_inv_twiddles::<BaseElement>(poly.len());
+    fft::interpolate_poly(&mut poly, &inv_twiddles);
+    poly.iter().map(|c| c.as_int()).collect()
+}
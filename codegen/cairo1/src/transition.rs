@@ -0,0 +1,146 @@
+use super::expression::{evaluate_constraint, scale, segment_type, ElemType};
+use ir::{
+    constraints::{AlgebraicGraph, ConstraintRoot},
+    CodegenError, ConstraintLocation,
+};
+use std::collections::BTreeMap;
+
+/// Returns the `evaluate_transition_{segment}` function, which evaluates the integrity
+/// constraints of a trace segment against an evaluation frame and returns their evaluations in
+/// the order of the constraints.
+///
+/// The constraints of aux segments are evaluated in the quadratic extension, so their
+/// evaluations are `Ext2` elements, and constraints which only depend on the main trace are
+/// lifted to the extension.
+///
+/// # Errors
+/// Returns an error naming the constraint if it uses a feature which is not supported by the
+/// Cairo 1 verifier.
+pub fn evaluate_transitions(
+    graph: &AlgebraicGraph,
+    segment: usize,
+    integrity_constraints: &[ConstraintRoot],
+) -> Result<String, CodegenError> {
+    let elem_type = segment_type(segment);
+    let ty = elem_type.cairo_type();
+
+    let mut code = format!("pub fn evaluate_transition_{segment}(\n");
+    code += "    frame_0: EvaluationFrame,\n";
+    if segment > 0 {
+        code += &format!("    frame_{segment}: AuxEvaluationFrame,\n");
+    }
+    code += "    periodic_row: Span<u64>,\n";
+    if segment > 0 {
+        code += "    rand: Span<Ext2>,\n";
+    }
+    code += &format!(") -> Array<{ty}> {{\n");
+    code += "    let cur_0 = frame_0.current;\n";
+    code += "    let nxt_0 = frame_0.next;\n";
+    if segment > 0 {
+        code += &format!("    let cur_{segment} = frame_{segment}.current;\n");
+        code += &format!("    let nxt_{segment} = frame_{segment}.next;\n");
+    }
+    code += &format!("    let mut t_evaluations: Array<{ty}> = ArrayTrait::new();\n\n");
+
+    let mut emitted = BTreeMap::new();
+    for (i, constraint) in integrity_constraints.iter().enumerate() {
+        let location = ConstraintLocation::integrity(segment as u8, i);
+        code += &evaluate_constraint(graph, constraint, segment, "t_evaluations", &mut emitted)
+            .map_err(|err| err.in_constraint(location))?;
+    }
+
+    code += "    t_evaluations\n";
+    code += "}\n\n";
+    Ok(code)
+}
+
+/// Returns the `merge_transitions_{segment}` function, which merges the evaluations of the
+/// integrity constraints of a trace segment into their contribution to the constraint
+/// composition polynomial at the OOD point `x`.
+///
+/// This follows Winterfell's `TransitionConstraints`: constraints are grouped by their
+/// evaluation degree, which depends on their base degree and on the cycles of the periodic
+/// columns they use, and the evaluations of each group are adjusted to the target degree before
+/// the sum is divided by the transition divisor. The coefficients of each constraint are taken
+/// in the order of the constraints.
+pub fn merge_transitions(
+    graph: &AlgebraicGraph,
+    segment: usize,
+    integrity_constraints: &[ConstraintRoot],
+    num_transition_exemptions: usize,
+) -> String {
+    let elem_type = segment_type(segment);
+    let ty = elem_type.cairo_type();
+    let (add, _) = elem_type.add_mul();
+
+    let mut code = format!("pub fn merge_transitions_{segment}(\n");
+    code += "    trace_length: u64,\n";
+    code += "    target_degree: u64,\n";
+    code += "    coeffs_transition_a: Span<u64>,\n";
+    code += "    coeffs_transition_b: Span<u64>,\n";
+    code += &format!("    t_evaluations: Span<{ty}>,\n");
+    code += "    x: u64,\n";
+    code += "    trace_domain_generator: u64,\n";
+    code += &format!(") -> {ty} {{\n");
+
+    // The divisor vanishes on every row of the trace except the exempted last rows
+    let factors = (1..=num_transition_exemptions)
+        .map(|i| format!("(x - g^(n - {i}))"))
+        .collect::<Vec<_>>();
+    code += &format!(
+        "    // Evaluate the transition divisor (x^n - 1) / {}\n",
+        factors.join(" * ")
+    );
+    code += "    let numerator = sub_g(pow_g(x, trace_length), 1);\n";
+    code += "    let denominator = sub_g(x, pow_g(trace_domain_generator, trace_length - 1));\n";
+    for i in 2..=num_transition_exemptions {
+        code += &format!(
+            "    let denominator = mul_g(denominator, sub_g(x, pow_g(trace_domain_generator, trace_length - {i})));\n"
+        );
+    }
+    code += "    let z = div_g(numerator, denominator);\n\n";
+    code += &format!("    let sum: {ty} = {};\n", elem_type.zero());
+
+    let mut groups: BTreeMap<(usize, Vec<usize>), Vec<usize>> = BTreeMap::new();
+    for (i, constraint) in integrity_constraints.iter().enumerate() {
+        let degree = graph.degree(&constraint.index);
+        groups
+            .entry((degree.base(), degree.cycles().to_vec()))
+            .or_default()
+            .push(i);
+    }
+
+    for ((base, cycles), constraints) in groups {
+        let mut evaluation_degree = format!("{base} * (trace_length - 1)");
+        for cycle in cycles.iter() {
+            evaluation_degree += &format!(" + trace_length / {cycle} * {}", cycle - 1);
+        }
+        code += &format!("\n    // Merge evaluation degree {base}");
+        if !cycles.is_empty() {
+            let cycles = cycles.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+            code += &format!(", cycles {}", cycles.join(", "));
+        }
+        code += "\n";
+        code += &format!("    let evaluation_degree = {evaluation_degree};\n");
+        code += "    let degree_adjustment = target_degree - evaluation_degree;\n";
+        code += "    let xp = pow_g(x, degree_adjustment);\n";
+        for i in constraints {
+            code += &format!("\n    // Include transition {i}\n");
+            code += &format!("    let v1 = mul_g(*coeffs_transition_b.at({i}), xp);\n");
+            code += &format!("    let v2 = add_g(*coeffs_transition_a.at({i}), v1);\n");
+            code += &format!(
+                "    let v3 = {};\n",
+                scale(elem_type, "v2", i, "t_evaluations")
+            );
+            code += &format!("    let sum = {add}(sum, v3);\n");
+        }
+    }
+
+    code += "\n";
+    match elem_type {
+        ElemType::Base => code += "    div_g(sum, z)\n",
+        ElemType::Ext => code += "    mul_eg(sum, inv_g(z))\n",
+    }
+    code += "}\n";
+    code
+}
@@ -0,0 +1,87 @@
+use super::{expression::ProcedureBody, layout::MemoryLayout, procedure};
+use ir::{
+    constraints::{AlgebraicGraph, ConstraintDomain, ConstraintRoot, Operation},
+    CodegenError, ConstraintLocation, NodeIndex, Value,
+};
+
+/// Returns the `evaluate_boundaries_{segment}` procedure, which evaluates the boundary
+/// constraints of a trace segment at the current row of the OOD frame, as in Winterfell.
+/// Constraints on the first and on the last row therefore read the same values, and only differ
+/// by their divisor.
+///
+/// The evaluations are left on the stack in the order of Winterfell's assertion indices, with
+/// the evaluation of the first assertion on top. Winterfell sorts the assertions of a segment by
+/// step and then by column, so the constraints on the first row come before those on the last
+/// row.
+///
+/// # Errors
+/// Returns an error naming the constraint if it is not an assertion against a single column on
+/// the first or last row, or if it uses a feature which is not supported by the Miden assembly
+/// target.
+pub fn evaluate_boundaries(
+    graph: &AlgebraicGraph,
+    layout: &MemoryLayout,
+    segment: usize,
+    boundary_constraints: &[ConstraintRoot],
+) -> Result<String, CodegenError> {
+    let mut keys = Vec::new();
+    for (i, constraint) in boundary_constraints.iter().enumerate() {
+        let location = ConstraintLocation::boundary(segment as u8, i);
+        let step = match constraint.domain() {
+            ConstraintDomain::FirstRow => 0,
+            ConstraintDomain::LastRow => 1,
+            domain => return Err(invalid_domain(domain).in_constraint(location)),
+        };
+        let column = boundary_column(graph, constraint.node_index())
+            .map_err(|err| err.in_constraint(location))?;
+        keys.push(((step, column), i));
+    }
+    keys.sort();
+
+    let constraints = keys
+        .iter()
+        .map(|(_, i)| &boundary_constraints[*i])
+        .collect::<Vec<_>>();
+    let mut body = ProcedureBody::new(graph, layout, &constraints);
+    for (_, i) in keys.iter().rev() {
+        let location = ConstraintLocation::boundary(segment as u8, *i);
+        body.push_constraint(&boundary_constraints[*i])
+            .map_err(|err| err.in_constraint(location))?;
+    }
+
+    let mut code = format!(
+        "#! Evaluates the {} boundary constraints of segment {segment} at the current row of the OOD frame.\n",
+        constraints.len()
+    );
+    code += "#!\n";
+    code += "#! Input: [...]\n";
+    code += "#! Output: [b_0, b_1, ..., b_{n-1}, ...], with the high coefficient of each evaluation on top\n";
+    code += &procedure(&format!("evaluate_boundaries_{segment}"), &body);
+    Ok(code)
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the column constrained by the boundary constraint with the specified root.
+fn boundary_column(graph: &AlgebraicGraph, index: &NodeIndex) -> Result<usize, CodegenError> {
+    match graph.node(index).op() {
+        Operation::Sub(lhs, _) => match graph.node(lhs).op() {
+            Operation::Value(Value::TraceElement(trace_access)) => Ok(trace_access.col_idx()),
+            _ => Err(CodegenError::invalid_constraint(
+                "expected a trace column on the left-hand side",
+            )),
+        },
+        _ => Err(CodegenError::invalid_constraint(
+            "expected the subtraction of an expression from a trace column",
+        )),
+    }
+}
+
+/// Returns the error for a boundary constraint which applies neither to the first nor to the
+/// last row.
+fn invalid_domain(domain: ConstraintDomain) -> CodegenError {
+    CodegenError::invalid_constraint(format!(
+        "boundary constraints must apply to the first or last row, not to {domain}"
+    ))
+}
@@ -0,0 +1,22 @@
+use ir::{layout::constant_elements, CodegenError, ConstantBinding};
+
+/// Returns the declarations of the named constants.
+///
+/// Every constant is declared as a Miden assembly constant, with vector and matrix constants
+/// unrolled into one constant per element by [constant_elements]. The values are canonical, which
+/// is the only form accepted by `push`.
+///
+/// # Errors
+/// Returns an error if two of the unrolled constants have the same name.
+pub fn constants(constants: &[ConstantBinding]) -> Result<String, CodegenError> {
+    if constants.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut code = "\n# CONSTANTS\n".to_string();
+    code += "# ===============================================\n";
+    for (name, value) in constant_elements(constants)? {
+        code += &format!("const.{name}={value}\n");
+    }
+    Ok(code)
+}
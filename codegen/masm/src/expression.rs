@@ -0,0 +1,238 @@
+use super::layout::MemoryLayout;
+use ir::{
+    constraints::{AlgebraicGraph, ConstraintRoot, Operation},
+    layout::{felt, matrix_element, vector_element},
+    AccessType, CodegenError, NodeIndex, Value,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+// PROCEDURE BODIES
+// ================================================================================================
+
+/// Builds the body of a procedure which evaluates a list of constraints, leaving the evaluation
+/// of each of them on the stack.
+///
+/// Every value is an element `(a0, a1)` of the quadratic extension, which takes two stack
+/// elements with `a1` on top, as expected by the `ext2` instructions. The nodes of the
+/// [AlgebraicGraph] are evaluated by a post-order walk, so that the operands of each operation
+/// are on top of the stack when it is applied. An operation which is used more than once by the
+/// constraints of the procedure is evaluated once and saved to a pair of procedure locals, from
+/// which it is loaded again on its later uses.
+pub struct ProcedureBody<'a> {
+    graph: &'a AlgebraicGraph,
+    layout: &'a MemoryLayout,
+    uses: BTreeMap<usize, usize>,
+    locals: BTreeMap<usize, u32>,
+    code: String,
+}
+
+impl<'a> ProcedureBody<'a> {
+    /// Returns an empty body for a procedure evaluating the specified constraints.
+    pub fn new(
+        graph: &'a AlgebraicGraph,
+        layout: &'a MemoryLayout,
+        constraints: &[&ConstraintRoot],
+    ) -> Self {
+        Self {
+            graph,
+            layout,
+            uses: count_uses(graph, constraints),
+            locals: BTreeMap::new(),
+            code: String::new(),
+        }
+    }
+
+    /// Appends the instructions which evaluate the constraint and push its evaluation to the
+    /// stack, followed by a comment giving the degree and the domain of the constraint.
+    ///
+    /// # Errors
+    /// Returns an error if the constraint uses a feature which is not supported by the Miden
+    /// assembly target.
+    pub fn push_constraint(&mut self, constraint: &ConstraintRoot) -> Result<(), CodegenError> {
+        let index = constraint.node_index();
+        self.code += "\n";
+        self.push_node(index)?;
+        let degree = self.graph.degree(index);
+        self.code += &format!(
+            "    # deg = {}, domain: {}\n",
+            degree.base(),
+            constraint.domain()
+        );
+        Ok(())
+    }
+
+    /// Returns the number of procedure locals used to save shared nodes.
+    pub fn num_locals(&self) -> u32 {
+        2 * self.locals.len() as u32
+    }
+
+    /// Returns the instructions of the procedure body.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    fn push_node(&mut self, index: &NodeIndex) -> Result<(), CodegenError> {
+        if let Some(local) = self.locals.get(&index.0) {
+            self.code += &format!("    loc_load.{local} loc_load.{}\n", local + 1);
+            return Ok(());
+        }
+
+        match self.graph.node(index).op() {
+            Operation::Value(value) => {
+                self.code += &format!("    {}\n", self.leaf(value)?);
+                return Ok(());
+            }
+            Operation::Add(lhs, rhs) => self.push_binop(lhs, rhs, "ext2add")?,
+            Operation::Sub(lhs, rhs) => self.push_binop(lhs, rhs, "ext2sub")?,
+            Operation::Mul(lhs, rhs) => self.push_binop(lhs, rhs, "ext2mul")?,
+            Operation::Exp(lhs, exponent) => {
+                self.push_node(lhs)?;
+                self.code += &power(*exponent);
+            }
+        }
+
+        if self.uses[&index.0] > 1 {
+            let local = self.num_locals();
+            self.locals.insert(index.0, local);
+            self.code += &format!(
+                "    dup.1 dup.1 loc_store.{} loc_store.{local}\n",
+                local + 1
+            );
+        }
+        Ok(())
+    }
+
+    fn push_binop(
+        &mut self,
+        lhs: &NodeIndex,
+        rhs: &NodeIndex,
+        instruction: &str,
+    ) -> Result<(), CodegenError> {
+        self.push_node(lhs)?;
+        self.push_node(rhs)?;
+        self.code += &format!("    {instruction}\n");
+        Ok(())
+    }
+
+    /// Returns the instructions which push the value of a leaf of the [AlgebraicGraph]. Base
+    /// field values are pushed with a zero high coefficient.
+    fn leaf(&self, value: &Value) -> Result<String, CodegenError> {
+        let result = match value {
+            Value::BoundConstant(_) | Value::InlineConstant(_) => {
+                format!("push.{} push.0", constant(value)?)
+            }
+            Value::TraceElement(trace_access) => {
+                if trace_access.row_offset() > 1 {
+                    return Err(CodegenError::unsupported_feature(
+                        "row offsets greater than 1",
+                    ));
+                }
+                let address = self.layout.trace_element(
+                    trace_access.trace_segment() as usize,
+                    trace_access.col_idx(),
+                    trace_access.row_offset(),
+                );
+                load_ext(address)
+            }
+            Value::PeriodicColumn(idx, _) => load_ext(self.layout.periodic_value(*idx)),
+            Value::PublicInput(name, idx) => {
+                format!("mem_load.{} push.0", self.layout.public_input(name, *idx))
+            }
+            Value::RandomValue(idx) => load_ext(self.layout.random_value(*idx)),
+        };
+        Ok(result)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns, for each node reachable from the constraints, the number of times it is used, either
+/// as the operand of another node or as a constraint.
+fn count_uses(graph: &AlgebraicGraph, constraints: &[&ConstraintRoot]) -> BTreeMap<usize, usize> {
+    let mut uses = BTreeMap::new();
+    let mut visited = BTreeSet::new();
+    let mut stack = Vec::new();
+    for constraint in constraints.iter() {
+        let index = constraint.node_index();
+        *uses.entry(index.0).or_insert(0) += 1;
+        if visited.insert(index.0) {
+            stack.push(*index);
+        }
+    }
+
+    while let Some(index) = stack.pop() {
+        let operands = match graph.node(&index).op() {
+            Operation::Value(_) => vec![],
+            Operation::Add(lhs, rhs) | Operation::Sub(lhs, rhs) | Operation::Mul(lhs, rhs) => {
+                vec![*lhs, *rhs]
+            }
+            Operation::Exp(lhs, _) => vec![*lhs],
+        };
+        for operand in operands {
+            *uses.entry(operand.0).or_insert(0) += 1;
+            if visited.insert(operand.0) {
+                stack.push(operand);
+            }
+        }
+    }
+    uses
+}
+
+/// Returns the name or the value of a constant leaf.
+fn constant(value: &Value) -> Result<String, CodegenError> {
+    match value {
+        Value::BoundConstant(symbol_access) => {
+            let name = symbol_access.name();
+            match symbol_access.access_type() {
+                AccessType::Default => Ok(name.to_string()),
+                AccessType::Vector(idx) => Ok(vector_element(name, *idx)),
+                AccessType::Matrix(row_idx, col_idx) => {
+                    Ok(matrix_element(name, *row_idx, *col_idx))
+                }
+                AccessType::Slice(_) => {
+                    Err(CodegenError::unsupported_feature("slices of constants"))
+                }
+            }
+        }
+        Value::InlineConstant(value) => Ok(felt(*value).to_string()),
+        _ => unreachable!("not a constant"),
+    }
+}
+
+/// Returns the instructions which push the extension element stored at the specified address.
+fn load_ext(address: u32) -> String {
+    format!("mem_load.{address} mem_load.{}", address + 1)
+}
+
+/// Returns the instructions which replace the extension element on top of the stack by its power
+/// with a constant exponent.
+///
+/// The power is unrolled into left-to-right square-and-multiply steps, so x^7 takes the 4
+/// multiplications x^2, x^3, x^6 and x^7. Unless the exponent is a power of two, a copy of the
+/// base stays below the accumulator until the last multiplication.
+fn power(exponent: usize) -> String {
+    match exponent {
+        0 => return "    drop drop push.1 push.0\n".to_string(),
+        1 => return String::new(),
+        _ => {}
+    }
+
+    // The base is only kept if it is multiplied into the accumulator again
+    let keep_base = !exponent.is_power_of_two();
+    let mut code = String::new();
+    if keep_base {
+        code += "    dup.1 dup.1\n";
+    }
+    let msb = usize::BITS - 1 - exponent.leading_zeros();
+    for bit in (0..msb).rev() {
+        code += "    dup.1 dup.1 ext2mul\n";
+        if exponent >> bit & 1 == 1 {
+            code += "    dup.3 dup.3 ext2mul\n";
+        }
+    }
+    if keep_base {
+        code += "    movup.2 drop movup.2 drop\n";
+    }
+    code
+}
@@ -0,0 +1,112 @@
+use ir::AirIR;
+
+/// The memory addresses from which the generated procedures read their inputs.
+///
+/// The values of the OOD frame, the periodic columns and the random values are elements of the
+/// quadratic extension, and each of them takes two consecutive addresses: the low coefficient
+/// `a0` is stored at the address of the element, and the high coefficient `a1` at the next one.
+/// The public inputs are base field elements, and each of them takes a single address.
+///
+/// The regions are laid out one after the other, starting at address 0, in the following order:
+/// the current and next rows of each trace segment, the periodic values, the random values and
+/// the public inputs.
+#[derive(Debug)]
+pub struct MemoryLayout {
+    current: Vec<u32>,
+    next: Vec<u32>,
+    periodic_values: u32,
+    random_values: u32,
+    public_inputs: Vec<(String, u32)>,
+    regions: Vec<(String, u32, u32)>,
+}
+
+impl MemoryLayout {
+    /// Returns the memory layout of the inputs of the constraints of the provided AirIR.
+    pub fn new(ir: &AirIR) -> Self {
+        let mut layout = Self {
+            current: Vec::new(),
+            next: Vec::new(),
+            periodic_values: 0,
+            random_values: 0,
+            public_inputs: Vec::new(),
+            regions: Vec::new(),
+        };
+
+        let mut address = 0;
+        for (segment, width) in ir.trace_segment_widths().iter().enumerate() {
+            let size = 2 * *width as u32;
+            layout.current.push(address);
+            layout.push_region(format!("segment {segment}, current row"), address, size);
+            address += size;
+            layout.next.push(address);
+            layout.push_region(format!("segment {segment}, next row"), address, size);
+            address += size;
+        }
+
+        let size = 2 * ir.periodic_columns().len() as u32;
+        layout.periodic_values = address;
+        layout.push_region("periodic values".to_string(), address, size);
+        address += size;
+
+        let size = 2 * ir.num_random_values() as u32;
+        layout.random_values = address;
+        layout.push_region("random values".to_string(), address, size);
+        address += size;
+
+        for (name, size) in ir.public_inputs().iter() {
+            let size = *size as u32;
+            layout.public_inputs.push((name.clone(), address));
+            layout.push_region(format!("public input {name}"), address, size);
+            address += size;
+        }
+
+        layout
+    }
+
+    /// Returns the address of the value of a column in a row of the OOD frame, where row offset
+    /// 0 is the current row and 1 is the next one.
+    pub fn trace_element(&self, segment: usize, column: usize, row_offset: usize) -> u32 {
+        let row = if row_offset == 0 {
+            self.current[segment]
+        } else {
+            self.next[segment]
+        };
+        row + 2 * column as u32
+    }
+
+    /// Returns the address of the value of a periodic column at the OOD point.
+    pub fn periodic_value(&self, column: usize) -> u32 {
+        self.periodic_values + 2 * column as u32
+    }
+
+    /// Returns the address of a random value.
+    pub fn random_value(&self, index: usize) -> u32 {
+        self.random_values + 2 * index as u32
+    }
+
+    /// Returns the address of an element of the named public input.
+    pub fn public_input(&self, name: &str, index: usize) -> u32 {
+        let (_, address) = self
+            .public_inputs
+            .iter()
+            .find(|(input, _)| input == name)
+            .expect("public inputs are declared");
+        address + index as u32
+    }
+
+    /// Returns the comment which describes the memory layout at the top of the generated module.
+    pub fn describe(&self) -> String {
+        let mut code = "#\n# Memory layout of the inputs. Extension elements take two addresses, with the low\n".to_string();
+        code += "# coefficient first, and public inputs take one address per element.\n";
+        for (name, start, size) in self.regions.iter() {
+            if *size > 0 {
+                code += &format!("#   [{start}, {}): {name}\n", start + size);
+            }
+        }
+        code
+    }
+
+    fn push_region(&mut self, name: String, start: u32, size: u32) {
+        self.regions.push((name, start, size));
+    }
+}
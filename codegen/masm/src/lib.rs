@@ -0,0 +1,89 @@
+use expression::ProcedureBody;
+use ir::{AirIR, CodegenError};
+use layout::MemoryLayout;
+
+mod boundary;
+mod constants;
+mod expression;
+mod layout;
+mod transition;
+
+// GENERATE MIDEN ASSEMBLY CONSTRAINT EVALUATION
+// ================================================================================================
+
+/// CodeGenerator is used to generate the Miden assembly procedures which evaluate the constraints
+/// of the AirIR used to build the CodeGenerator at the OOD frame, for use by a recursive STARK
+/// verifier running in the Miden VM.
+///
+/// The generated module exports an `evaluate_transitions_i` and an `evaluate_boundaries_i`
+/// procedure for each trace segment `i`. They read the OOD frame, the periodic values, the random
+/// values and the public inputs from the memory addresses described at the top of the module,
+/// and leave the evaluations of the constraints on the stack, as elements of the quadratic
+/// extension of the Goldilocks field.
+pub struct CodeGenerator<'a> {
+    ir: &'a AirIR,
+}
+
+impl<'a> CodeGenerator<'a> {
+    // --- CONSTRUCTOR ----------------------------------------------------------------------------
+
+    /// Returns a new [CodeGenerator] for the provided AirIR.
+    pub fn new(ir: &'a AirIR) -> Self {
+        Self { ir }
+    }
+
+    /// Returns a string of Miden assembly code evaluating the constraints of the AirIR with which
+    /// this [CodeGenerator] was instantiated.
+    ///
+    /// # Errors
+    /// Returns an error if a constraint uses a feature which the Miden assembly target does not
+    /// support.
+    pub fn generate(&self) -> Result<String, CodegenError> {
+        let ir = self.ir;
+        let layout = MemoryLayout::new(ir);
+        let graph = ir.constraint_graph();
+        let segment_widths = ir.trace_segment_widths();
+
+        let mut code = format!(
+            "# Air name {}, {} segments\n",
+            ir.air_name(),
+            segment_widths.len()
+        );
+        code += &layout.describe();
+        code += &constants::constants(ir.constants())?;
+
+        for (segment, width) in segment_widths.iter().enumerate() {
+            code += &format!("\n# SEGMENT {segment} size {width}\n");
+            code += "# ===============================================\n\n";
+            code += &transition::evaluate_transitions(
+                graph,
+                &layout,
+                segment,
+                ir.integrity_constraints(segment as u8),
+            )?;
+            code += "\n";
+            code += &boundary::evaluate_boundaries(
+                graph,
+                &layout,
+                segment,
+                ir.boundary_constraints(segment as u8),
+            )?;
+        }
+        Ok(code)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the exported procedure with the specified name and body, declaring the locals used by
+/// the body.
+fn procedure(name: &str, body: &ProcedureBody) -> String {
+    let mut code = match body.num_locals() {
+        0 => format!("export.{name}"),
+        num_locals => format!("export.{name}.{num_locals}"),
+    };
+    code += body.code();
+    code += "end\n";
+    code
+}
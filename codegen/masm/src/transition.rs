@@ -0,0 +1,40 @@
+use super::{expression::ProcedureBody, layout::MemoryLayout, procedure};
+use ir::{
+    constraints::{AlgebraicGraph, ConstraintRoot},
+    CodegenError, ConstraintLocation,
+};
+
+/// Returns the `evaluate_transitions_{segment}` procedure, which evaluates the integrity
+/// constraints of a trace segment at the OOD frame.
+///
+/// The evaluations are left on the stack in the order of Winterfell's transition constraint
+/// indices, with the evaluation of the first constraint on top. The constraints are therefore
+/// evaluated from the last to the first.
+///
+/// # Errors
+/// Returns an error naming the constraint if it uses a feature which is not supported by the
+/// Miden assembly target.
+pub fn evaluate_transitions(
+    graph: &AlgebraicGraph,
+    layout: &MemoryLayout,
+    segment: usize,
+    integrity_constraints: &[ConstraintRoot],
+) -> Result<String, CodegenError> {
+    let constraints = integrity_constraints.iter().collect::<Vec<_>>();
+    let mut body = ProcedureBody::new(graph, layout, &constraints);
+    for (i, constraint) in constraints.iter().enumerate().rev() {
+        let location = ConstraintLocation::integrity(segment as u8, i);
+        body.push_constraint(constraint)
+            .map_err(|err| err.in_constraint(location))?;
+    }
+
+    let mut code = format!(
+        "#! Evaluates the {} transition constraints of segment {segment} at the OOD frame.\n",
+        constraints.len()
+    );
+    code += "#!\n";
+    code += "#! Input: [...]\n";
+    code += "#! Output: [t_0, t_1, ..., t_{n-1}, ...], with the high coefficient of each evaluation on top\n";
+    code += &procedure(&format!("evaluate_transitions_{segment}"), &body);
+    Ok(code)
+}
@@ -0,0 +1,44 @@
+use super::{AirIR, ConstantValueExpr, Scope};
+
+/// Updates the provided scope with constant declarations.
+///
+/// Constants are declared as canonical `u64` values, which are lifted into the field of the
+/// builder where they are used.
+pub(super) fn add_constants(scope: &mut Scope, ir: &AirIR) {
+    let mut consts = vec![];
+    for constant in ir.constants() {
+        let const_str = match constant.value() {
+            ConstantValueExpr::Scalar(scalar) => {
+                format!("const {}: u64 = {scalar};", constant.name())
+            }
+            ConstantValueExpr::Vector(vector) => format!(
+                "const {}: [u64; {}] = {};",
+                constant.name(),
+                vector.len(),
+                vector_to_string(vector)
+            ),
+            ConstantValueExpr::Matrix(matrix) => format!(
+                "const {}: [[u64; {}]; {}] = [{}];",
+                constant.name(),
+                matrix[0].len(),
+                matrix.len(),
+                matrix
+                    .iter()
+                    .map(|row| vector_to_string(row))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        consts.push(const_str);
+    }
+    scope.raw(consts.join("\n"));
+}
+
+/// Returns the array literal of a vector constant.
+fn vector_to_string(vector: &[u64]) -> String {
+    let values = vector
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>();
+    format!("[{}]", values.join(", "))
+}
@@ -0,0 +1,244 @@
+use super::{
+    AccessType, AirIR, CodegenError, ConstraintLocation, ConstraintRoot, NodeIndex, Operation,
+    TraceAccess, Value,
+};
+use std::collections::BTreeMap;
+
+// RUST STRING GENERATION FOR THE CONSTRAINT GRAPH
+// ================================================================================================
+
+/// Code generation trait for generating Rust code strings from IR types related to constraints and
+/// the [AlgebraicGraph](ir::constraints::AlgebraicGraph).
+///
+/// Every value is generated as an `AB::Expr`, the expression type of the Plonky3 `AirBuilder`,
+/// so that the operators of the generated code are those of the constraint graph.
+///
+/// Returns an error if the IR uses a feature which cannot be expressed in a Plonky3 Air.
+pub trait Codegen {
+    fn to_string(&self, ir: &AirIR) -> Result<String, CodegenError>;
+}
+
+impl Codegen for TraceAccess {
+    fn to_string(&self, _ir: &AirIR) -> Result<String, CodegenError> {
+        if self.trace_segment() != 0 {
+            return Err(CodegenError::unsupported_feature(
+                "auxiliary trace segments",
+            ));
+        }
+        match self.row_offset() {
+            0 => Ok(format!("main_current[{}].clone()", self.col_idx())),
+            1 => Ok(format!("main_next[{}].clone()", self.col_idx())),
+            _ => Err(CodegenError::unsupported_feature(
+                "row offsets greater than 1",
+            )),
+        }
+    }
+}
+
+impl Codegen for NodeIndex {
+    fn to_string(&self, ir: &AirIR) -> Result<String, CodegenError> {
+        node_to_string(self, ir, &Bindings::default())
+    }
+}
+
+impl Codegen for Operation {
+    fn to_string(&self, ir: &AirIR) -> Result<String, CodegenError> {
+        op_to_string(self, ir, &Bindings::default())
+    }
+}
+
+impl Codegen for Value {
+    fn to_string(&self, ir: &AirIR) -> Result<String, CodegenError> {
+        let result = match self {
+            Value::InlineConstant(value) => format!("AB::Expr::from_canonical_u64({value})"),
+            Value::BoundConstant(symbol_access) => {
+                let name = symbol_access.name();
+                let value = match symbol_access.access_type() {
+                    AccessType::Default => name.to_string(),
+                    AccessType::Vector(idx) => format!("{name}[{idx}]"),
+                    AccessType::Matrix(row_idx, col_idx) => format!("{name}[{row_idx}][{col_idx}]"),
+                    AccessType::Slice(_) => {
+                        return Err(CodegenError::unsupported_feature("slices of constants"))
+                    }
+                };
+                format!("AB::Expr::from_canonical_u64({value})")
+            }
+            Value::TraceElement(trace_access) => trace_access.to_string(ir)?,
+            Value::PeriodicColumn(_, _) => {
+                return Err(CodegenError::unsupported_feature("periodic columns"))
+            }
+            Value::PublicInput(ident, idx) => {
+                format!(
+                    "public_values[{}].clone()",
+                    public_value_index(ir, ident, *idx)
+                )
+            }
+            Value::RandomValue(_) => {
+                return Err(CodegenError::unsupported_feature("random values"))
+            }
+        };
+        Ok(result)
+    }
+}
+
+// SHARED SUBEXPRESSIONS
+// ================================================================================================
+
+/// The nodes of the constraint graph which are bound to local variables, and are referenced by
+/// the names of these variables instead of being rendered inline.
+#[derive(Debug, Default)]
+pub struct Bindings {
+    names: BTreeMap<usize, String>,
+}
+
+impl Bindings {
+    /// Binds the node at the specified index to the local variable with the provided name.
+    pub fn insert(&mut self, idx: &NodeIndex, name: String) {
+        self.names.insert(idx.0, name);
+    }
+
+    /// Returns the name of the local variable to which the node at the specified index is bound,
+    /// if any.
+    pub fn get(&self, idx: &NodeIndex) -> Option<&str> {
+        self.names.get(&idx.0).map(String::as_str)
+    }
+
+    /// Returns the number of bound nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+}
+
+/// Returns the operations of the constraint graph which are used more than once by the provided
+/// constraints, either by several parents or by several constraints, ordered so that each node
+/// comes after the nodes it uses. Each node is returned with the location of the first constraint
+/// which uses it.
+///
+/// Leaves are never returned, since they are cheaper to render inline than to bind.
+pub fn shared_nodes(
+    ir: &AirIR,
+    constraints: &[(ConstraintLocation, &ConstraintRoot)],
+) -> Vec<(NodeIndex, ConstraintLocation)> {
+    let mut uses = BTreeMap::new();
+    let mut order = Vec::new();
+    for (location, constraint) in constraints {
+        visit_node(
+            ir,
+            constraint.node_index(),
+            *location,
+            &mut uses,
+            &mut order,
+        );
+    }
+    order
+        .into_iter()
+        .filter(|(idx, _)| {
+            uses[&idx.0] > 1 && !matches!(ir.constraint_graph().node(idx).op(), Operation::Value(_))
+        })
+        .collect()
+}
+
+/// Counts a use of the node at the specified index, and visits its children if it had not been
+/// visited yet. Nodes are appended to the order after their children.
+fn visit_node(
+    ir: &AirIR,
+    idx: &NodeIndex,
+    location: ConstraintLocation,
+    uses: &mut BTreeMap<usize, usize>,
+    order: &mut Vec<(NodeIndex, ConstraintLocation)>,
+) {
+    let count = uses.entry(idx.0).or_insert(0);
+    *count += 1;
+    if *count > 1 {
+        return;
+    }
+    match ir.constraint_graph().node(idx).op() {
+        Operation::Value(_) => {}
+        Operation::Add(l_idx, r_idx)
+        | Operation::Sub(l_idx, r_idx)
+        | Operation::Mul(l_idx, r_idx) => {
+            visit_node(ir, l_idx, location, uses, order);
+            visit_node(ir, r_idx, location, uses, order);
+        }
+        Operation::Exp(l_idx, _) => visit_node(ir, l_idx, location, uses, order),
+    }
+    order.push((*idx, location));
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the code of the node at the specified index, using the names of the nodes bound to
+/// local variables.
+pub fn node_to_string(
+    idx: &NodeIndex,
+    ir: &AirIR,
+    bindings: &Bindings,
+) -> Result<String, CodegenError> {
+    match bindings.get(idx) {
+        Some(name) => Ok(format!("{name}.clone()")),
+        None => op_to_string(ir.constraint_graph().node(idx).op(), ir, bindings),
+    }
+}
+
+/// Returns the code of the provided operation, using the names of the nodes bound to local
+/// variables.
+fn op_to_string(op: &Operation, ir: &AirIR, bindings: &Bindings) -> Result<String, CodegenError> {
+    match op {
+        Operation::Value(value) => value.to_string(ir),
+        Operation::Add(l_idx, r_idx) => {
+            let lhs = node_to_string(l_idx, ir, bindings)?;
+            let rhs = node_to_string(r_idx, ir, bindings)?;
+            Ok(format!("{lhs} + {rhs}"))
+        }
+        Operation::Sub(l_idx, r_idx) => {
+            let lhs = node_to_string(l_idx, ir, bindings)?;
+            let rhs = operand_to_string(ir, r_idx, op.precedence() + 1, bindings)?;
+            Ok(format!("{lhs} - {rhs}"))
+        }
+        Operation::Mul(l_idx, r_idx) => {
+            let lhs = operand_to_string(ir, l_idx, op.precedence(), bindings)?;
+            let rhs = operand_to_string(ir, r_idx, op.precedence(), bindings)?;
+            Ok(format!("{lhs} * {rhs}"))
+        }
+        Operation::Exp(l_idx, exponent) => match exponent {
+            // x^0 = 1
+            0 => Ok("AB::Expr::one()".to_string()),
+            // x^1 = x
+            1 => node_to_string(l_idx, ir, bindings),
+            _ => {
+                let lhs = operand_to_string(ir, l_idx, op.precedence(), bindings)?;
+                Ok(format!("{lhs}.exp_u64({exponent})"))
+            }
+        },
+    }
+}
+
+/// Returns the code of the operand at the specified node index, which is parenthesized if it is
+/// not bound to a local variable and its precedence is lower than the specified one.
+fn operand_to_string(
+    ir: &AirIR,
+    idx: &NodeIndex,
+    precedence: usize,
+    bindings: &Bindings,
+) -> Result<String, CodegenError> {
+    let operand = node_to_string(idx, ir, bindings)?;
+    let op = ir.constraint_graph().node(idx).op();
+    if bindings.get(idx).is_none() && op.precedence() < precedence {
+        Ok(format!("({operand})"))
+    } else {
+        Ok(operand)
+    }
+}
+
+/// Returns the index among the public values of the builder of the element at the specified index
+/// of the named public input. The public inputs are laid out in the order of their declaration.
+fn public_value_index(ir: &AirIR, name: &str, idx: usize) -> usize {
+    let offset: usize = ir
+        .public_inputs()
+        .iter()
+        .take_while(|(pub_input, _)| pub_input != name)
+        .map(|(_, size)| size)
+        .sum();
+    offset + idx
+}
@@ -0,0 +1,184 @@
+use super::{AirIR, Impl, Scope};
+use air_script_core::{AccessType, ConstantValueExpr, TraceAccess};
+use ir::{
+    constraints::{ConstraintDomain, ConstraintRoot, Operation},
+    CodegenError, ConstraintKind, ConstraintLocation, NodeIndex, Value,
+};
+
+mod constants;
+use constants::add_constants;
+
+mod graph;
+use graph::{node_to_string, shared_nodes, Bindings};
+
+// HELPERS TO GENERATE AN IMPLEMENTATION OF THE PLONKY3 AIR TRAITS
+// ================================================================================================
+
+/// Updates the provided scope with a new Air struct and the Plonky3 `BaseAir` and `Air` trait
+/// implementations which are equivalent to the provided AirIR.
+///
+/// Returns an error if the AirIR uses an auxiliary trace segment, periodic columns or more than one
+/// transition exemption, which the Plonky3 `AirBuilder` does not provide, or if any of the
+/// constraints cannot be expressed in a Plonky3 Air.
+pub(super) fn add_air(scope: &mut Scope, ir: &AirIR) -> Result<(), CodegenError> {
+    if ir.trace_segment_widths().len() > 1 {
+        return Err(CodegenError::unsupported_feature(
+            "auxiliary trace segments",
+        ));
+    }
+    if !ir.periodic_columns().is_empty() {
+        return Err(CodegenError::unsupported_feature("periodic columns"));
+    }
+    if ir.num_transition_exemptions() > 1 {
+        return Err(CodegenError::unsupported_feature(
+            "multiple transition exemptions",
+        ));
+    }
+
+    // add constant declarations. Check required to avoid adding extra line during codegen.
+    if !ir.constants().is_empty() {
+        add_constants(scope, ir);
+    }
+
+    let name = ir.air_name();
+
+    // add the Air struct.
+    add_air_struct(scope, ir, name);
+
+    // add the Plonky3 BaseAir trait implementation, which defines the width of the trace.
+    scope
+        .new_impl(name)
+        .generic("F")
+        .impl_trait("BaseAir<F>")
+        .new_fn("width")
+        .arg_ref_self()
+        .ret("usize")
+        .line(ir.trace_segment_widths()[0].to_string());
+
+    // add the Plonky3 Air trait implementation, which evaluates the constraints.
+    let air_impl = scope
+        .new_impl(name)
+        .generic("AB: AirBuilderWithPublicValues")
+        .impl_trait("Air<AB>");
+    add_fn_eval(air_impl, ir)
+}
+
+/// Updates the provided scope with a custom Air struct, documenting where each public input is
+/// found among the public values of the builder.
+fn add_air_struct(scope: &mut Scope, ir: &AirIR, name: &str) {
+    let mut doc = vec![format!(
+        "The {name} AIR. Its public inputs are read from the public values of the builder:"
+    )];
+    let mut offset = 0;
+    for (pub_input, pub_input_size) in ir.public_inputs() {
+        doc.push(format!(
+            "- `{pub_input}`: [{offset}, {})",
+            offset + pub_input_size
+        ));
+        offset += pub_input_size;
+    }
+    scope.new_struct(name).vis("pub").doc(&doc.join("\n"));
+}
+
+/// Adds an implementation of the "eval" method to the referenced Air implementation based on the
+/// data in the provided AirIR.
+///
+/// Boundary constraints are enforced with `when_first_row` or `when_last_row`, transition
+/// constraints with `when_transition`, and validity constraints on every row. The operations
+/// which are used more than once by the constraints are evaluated once beforehand and bound to
+/// local variables `t0`, `t1`, etc., which the constraints reference instead of recomputing them.
+fn add_fn_eval(impl_ref: &mut Impl, ir: &AirIR) -> Result<(), CodegenError> {
+    // define the function.
+    let eval = impl_ref
+        .new_fn("eval")
+        .arg_ref_self()
+        .arg("builder", "&mut AB");
+
+    // read the current and next trace rows and the public values as expressions.
+    eval.line("let main = builder.main();");
+    eval.line(
+        "let main_current: Vec<AB::Expr> = main.row_slice(0).iter().map(|&v| v.into()).collect();",
+    );
+    eval.line(
+        "let main_next: Vec<AB::Expr> = main.row_slice(1).iter().map(|&v| v.into()).collect();",
+    );
+    if !ir.public_inputs().is_empty() {
+        eval.line(
+            "let public_values: Vec<AB::Expr> = builder.public_values().iter().map(|&v| v.into()).collect();",
+        );
+    }
+
+    let constraints = ir
+        .boundary_constraints(0)
+        .iter()
+        .enumerate()
+        .map(|(idx, constraint)| (ConstraintLocation::boundary(0, idx), constraint))
+        .chain(
+            ir.integrity_constraints(0)
+                .iter()
+                .enumerate()
+                .map(|(idx, constraint)| (ConstraintLocation::integrity(0, idx), constraint)),
+        )
+        .collect::<Vec<_>>();
+
+    // bind the subexpressions shared by the constraints to local variables.
+    let mut bindings = Bindings::default();
+    for (node_idx, location) in shared_nodes(ir, &constraints) {
+        let expr =
+            node_to_string(&node_idx, ir, &bindings).map_err(|err| err.in_constraint(location))?;
+        let name = format!("t{}", bindings.len());
+        eval.line(format!("let {name}: AB::Expr = {expr};"));
+        bindings.insert(&node_idx, name);
+    }
+
+    // add the boundary and integrity constraints.
+    for (location, constraint) in constraints {
+        let line = match location.kind() {
+            ConstraintKind::Boundary => boundary_constraint(ir, constraint, &bindings),
+            ConstraintKind::Integrity => integrity_constraint(ir, constraint, &bindings),
+        }
+        .map_err(|err| err.in_constraint(location))?;
+        eval.line(line);
+    }
+    Ok(())
+}
+
+/// Returns the line of generated code which enforces the provided boundary constraint.
+fn boundary_constraint(
+    ir: &AirIR,
+    constraint: &ConstraintRoot,
+    bindings: &Bindings,
+) -> Result<String, CodegenError> {
+    let when = match constraint.domain() {
+        ConstraintDomain::FirstRow => "when_first_row",
+        ConstraintDomain::LastRow => "when_last_row",
+        domain => {
+            return Err(CodegenError::invalid_constraint(format!(
+                "boundary constraints must apply to the first or last row, not to {domain}"
+            )))
+        }
+    };
+    let expr = node_to_string(constraint.node_index(), ir, bindings)?;
+    Ok(format!("builder.{when}().assert_zero({expr});"))
+}
+
+/// Returns the line of generated code which enforces the provided integrity constraint.
+fn integrity_constraint(
+    ir: &AirIR,
+    constraint: &ConstraintRoot,
+    bindings: &Bindings,
+) -> Result<String, CodegenError> {
+    let expr = node_to_string(constraint.node_index(), ir, bindings)?;
+    match constraint.domain() {
+        ConstraintDomain::EveryRow => Ok(format!("builder.assert_zero({expr});")),
+        ConstraintDomain::EveryFrame(2) => {
+            Ok(format!("builder.when_transition().assert_zero({expr});"))
+        }
+        ConstraintDomain::EveryFrame(_) => Err(CodegenError::unsupported_feature(
+            "row offsets greater than 1",
+        )),
+        domain => Err(CodegenError::invalid_constraint(format!(
+            "integrity constraints must apply to every row or frame, not to {domain}"
+        ))),
+    }
+}
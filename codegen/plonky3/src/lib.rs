@@ -0,0 +1,44 @@
+use codegen::{Impl, Scope};
+use ir::{AirIR, CodegenError};
+
+mod imports;
+use imports::add_imports;
+
+mod air;
+use air::add_air;
+
+// GENERATE RUST CODE FOR PLONKY3 AIR
+// ================================================================================================
+
+/// CodeGenerator is used to generate a Rust implementation of the Plonky3 `BaseAir` and `Air`
+/// traits. The generated Air expresses the constraints specified by the AirIR used to build the
+/// CodeGenerator.
+pub struct CodeGenerator<'a> {
+    ir: &'a AirIR,
+}
+
+impl<'a> CodeGenerator<'a> {
+    // --- CONSTRUCTOR ----------------------------------------------------------------------------
+
+    /// Returns a new [CodeGenerator] for the provided AirIR.
+    pub fn new(ir: &'a AirIR) -> Self {
+        Self { ir }
+    }
+
+    /// Returns a string of Rust code containing a Plonky3 Air implementation for the AirIR with
+    /// which this [CodeGenerator] was instantiated.
+    ///
+    /// # Errors
+    /// Returns an error if the AirIR uses a feature which cannot be expressed in a Plonky3 Air.
+    pub fn generate(&self) -> Result<String, CodegenError> {
+        let mut scope = Scope::new();
+
+        // add plonky3 imports.
+        add_imports(&mut scope);
+
+        // add an Air struct and Plonky3 Air trait implementations for the AirIR.
+        add_air(&mut scope, self.ir)?;
+
+        Ok(scope.to_string())
+    }
+}
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+// Arithmetic in the Goldilocks field and in its quadratic extension. Field elements are held in
+// uint256 values in their canonical form, i.e. strictly less than P.
+
+// The modulus of the Goldilocks field, 2^64 - 2^32 + 1.
+uint256 constant P = 0xFFFFFFFF00000001;
+
+// An element a0 + a1 * phi of the quadratic extension of the Goldilocks field, where phi is a
+// root of x^2 - x + 2. This is the extension used by Winterfell's QuadExtension<BaseElement>,
+// so phi^2 = phi - 2.
+struct Ext2 {
+    uint256 a0;
+    uint256 a1;
+}
+
+// BASE FIELD
+// ================================================================================================
+
+function add_g(uint256 a, uint256 b) pure returns (uint256) {
+    return addmod(a, b, P);
+}
+
+function sub_g(uint256 a, uint256 b) pure returns (uint256) {
+    return addmod(a, P - b, P);
+}
+
+function mul_g(uint256 a, uint256 b) pure returns (uint256) {
+    return mulmod(a, b, P);
+}
+
+// Returns base ** exp, computed with square-and-multiply.
+function pow_g(uint256 base, uint256 exp) pure returns (uint256) {
+    uint256 result = 1;
+    while (exp != 0) {
+        if (exp & 1 == 1) {
+            result = mulmod(result, base, P);
+        }
+        base = mulmod(base, base, P);
+        exp >>= 1;
+    }
+    return result;
+}
+
+function inv_g(uint256 a) pure returns (uint256) {
+    require(a != 0, "inverse of zero");
+    return pow_g(a, P - 2);
+}
+
+function div_g(uint256 a, uint256 b) pure returns (uint256) {
+    return mulmod(a, inv_g(b), P);
+}
+
+// QUADRATIC EXTENSION
+// ================================================================================================
+
+// Returns the extension element holding a base field element.
+function lift(uint256 a) pure returns (Ext2 memory) {
+    return Ext2(a, 0);
+}
+
+function add_e(Ext2 memory a, Ext2 memory b) pure returns (Ext2 memory) {
+    return Ext2(addmod(a.a0, b.a0, P), addmod(a.a1, b.a1, P));
+}
+
+function sub_e(Ext2 memory a, Ext2 memory b) pure returns (Ext2 memory) {
+    return Ext2(sub_g(a.a0, b.a0), sub_g(a.a1, b.a1));
+}
+
+// (a0 + a1 * phi) * (b0 + b1 * phi) = (a0 * b0 - 2 * a1 * b1) + (a0 * b1 + a1 * b0 + a1 * b1) * phi
+function mul_e(Ext2 memory a, Ext2 memory b) pure returns (Ext2 memory) {
+    uint256 a0b0 = mulmod(a.a0, b.a0, P);
+    uint256 a1b1 = mulmod(a.a1, b.a1, P);
+    uint256 r0 = sub_g(a0b0, addmod(a1b1, a1b1, P));
+    uint256 sum_product = mulmod(addmod(a.a0, a.a1, P), addmod(b.a0, b.a1, P), P);
+    return Ext2(r0, sub_g(sum_product, a0b0));
+}
+
+// Multiplies an extension element by a base field element.
+function mul_eg(Ext2 memory a, uint256 b) pure returns (Ext2 memory) {
+    return Ext2(mulmod(a.a0, b, P), mulmod(a.a1, b, P));
+}
+
+// The inverse is the conjugate (the image of the Frobenius map) divided by the norm, which is in
+// the base field.
+function inv_e(Ext2 memory a) pure returns (Ext2 memory) {
+    Ext2 memory conjugate = Ext2(addmod(a.a0, a.a1, P), sub_g(0, a.a1));
+    Ext2 memory norm = mul_e(a, conjugate);
+    return mul_eg(conjugate, inv_g(norm.a0));
+}
+
+function div_e(Ext2 memory a, Ext2 memory b) pure returns (Ext2 memory) {
+    return mul_e(a, inv_e(b));
+}
@@ -0,0 +1,200 @@
+use super::{
+    expression::{scale, segment_type, ElemType, Evaluator},
+    signature,
+};
+use ir::{
+    constraints::{AlgebraicGraph, ConstraintDomain, ConstraintRoot, Operation},
+    CodegenError, ConstraintLocation, NodeIndex, PublicInput, Value,
+};
+
+/// Returns the `evaluate_boundaries_{segment}` function, which evaluates the boundary
+/// constraints of a trace segment and returns their evaluations in the order of the constraints.
+///
+/// The constraints are evaluated against the current row of the OOD frame, as in Winterfell, so
+/// constraints on the first and on the last row read the same values and only differ by their
+/// divisor.
+///
+/// # Errors
+/// Returns an error naming the constraint if it uses a feature which is not supported by the
+/// Solidity target.
+pub fn evaluate_boundaries(
+    graph: &AlgebraicGraph,
+    public_inputs: &[PublicInput],
+    segment: usize,
+    boundary_constraints: &[ConstraintRoot],
+) -> Result<String, CodegenError> {
+    let elem_type = segment_type(segment);
+
+    let mut params = vec!["EvaluationFrame memory frame_0".to_string()];
+    if segment > 0 {
+        params.push(format!("AuxEvaluationFrame memory frame_{segment}"));
+    }
+    params.extend(
+        public_inputs
+            .iter()
+            .map(|(name, _)| format!("uint256[] memory {name}")),
+    );
+    if segment > 0 {
+        params.push("Ext2[] memory rand".to_string());
+    }
+
+    let mut evaluator = Evaluator::new(graph);
+    for (i, constraint) in boundary_constraints.iter().enumerate() {
+        evaluator
+            .evaluate_constraint(constraint, segment, "b_evaluations", i)
+            .map_err(|err| err.in_constraint(ConstraintLocation::boundary(segment as u8, i)))?;
+    }
+
+    let mut code = signature(
+        &format!("evaluate_boundaries_{segment}"),
+        &params,
+        elem_type.array_type(),
+    );
+    code += &format!(
+        "        {} b_evaluations = {};\n",
+        elem_type.array_type(),
+        elem_type.new_array(boundary_constraints.len())
+    );
+    code += &evaluator.code();
+    code += "\n        return b_evaluations;\n";
+    code += "    }\n";
+    Ok(code)
+}
+
+/// Returns the `merge_boundaries_{segment}` function, which merges the evaluations of the
+/// boundary constraints of a trace segment into their contribution to the constraint composition
+/// polynomial at the OOD point `x`.
+///
+/// This follows Winterfell's `BoundaryConstraints`: every boundary constraint is a single-step
+/// assertion, so they are grouped by step (the first row or the last step) and divided by
+/// `x - g^step`, where the last step is the last row which is not exempted from the transition
+/// constraints. All groups share the same degree adjustment, which depends only on the trace
+/// length. The coefficients of the segment start at `offset` in the boundary coefficients, and
+/// are assigned in the order in which Winterfell sorts assertions: by step, then by column.
+///
+/// # Errors
+/// Returns an error naming the constraint if it is not an assertion against a single column on
+/// the first or last row.
+pub fn merge_boundaries(
+    graph: &AlgebraicGraph,
+    segment: usize,
+    offset: usize,
+    boundary_constraints: &[ConstraintRoot],
+    num_transition_exemptions: usize,
+) -> Result<String, CodegenError> {
+    let elem_type = segment_type(segment);
+    let ty = elem_type.sol_type();
+    let (add, _) = elem_type.add_mul();
+
+    let params = [
+        "uint256 trace_length".to_string(),
+        "uint256 blowup_factor".to_string(),
+        "CompositionCoefficients memory coeffs".to_string(),
+        format!("{} b_evaluations", elem_type.array_type()),
+        "uint256 trace_domain_generator".to_string(),
+        "uint256 x".to_string(),
+    ];
+    let mut code = signature(&format!("merge_boundaries_{segment}"), &params, ty);
+
+    code += "        // Evaluate the degree adjustment, from the degree of the trace polynomials to the\n";
+    code += "        // degree of the composition polynomial times the degree of the divisor\n";
+    code += "        uint256 composition_degree = trace_length * blowup_factor - 1;\n";
+    code += "        uint256 xp = pow_g(x, composition_degree + 1 - (trace_length - 1));\n\n";
+    code += &format!("        {ty} first_sum = {};\n", elem_type.zero());
+    code += &format!("        {ty} last_sum = {};\n", elem_type.zero());
+
+    let coeff_indices = coefficient_order(graph, segment, boundary_constraints)?;
+    for (i, constraint) in boundary_constraints.iter().enumerate() {
+        let sum = match constraint.domain {
+            ConstraintDomain::FirstRow => "first_sum",
+            ConstraintDomain::LastRow => "last_sum",
+            domain => {
+                let location = ConstraintLocation::boundary(segment as u8, i);
+                return Err(invalid_domain(domain).in_constraint(location));
+            }
+        };
+        let j = offset + coeff_indices[i];
+        let coeff = format!("add_g(coeffs.boundary_a[{j}], mul_g(coeffs.boundary_b[{j}], xp))");
+        code += &format!("\n        // Include boundary {i}\n");
+        code += &format!(
+            "        {sum} = {add}({sum}, {});\n",
+            scale(elem_type, &coeff, i, "b_evaluations")
+        );
+    }
+
+    let k = num_transition_exemptions;
+    code += &format!(
+        "\n        // Divide by x - 1 on the first row, and by x - g^(n - {k}) on the last row\n"
+    );
+    let last_z = format!("sub_g(x, pow_g(trace_domain_generator, trace_length - {k}))");
+    let (first, last) = match elem_type {
+        ElemType::Base => (
+            "div_g(first_sum, sub_g(x, 1))".to_string(),
+            format!("div_g(last_sum, {last_z})"),
+        ),
+        ElemType::Ext => (
+            "mul_eg(first_sum, inv_g(sub_g(x, 1)))".to_string(),
+            format!("mul_eg(last_sum, inv_g({last_z}))"),
+        ),
+    };
+    code += &format!("        first_sum = {first};\n");
+    code += &format!("        last_sum = {last};\n");
+    code += &format!("        return {add}(first_sum, last_sum);\n");
+    code += "    }\n";
+    Ok(code)
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns, for each boundary constraint, the index of its composition coefficients. Winterfell
+/// sorts the assertions of a segment by step and then by column before assigning coefficients.
+fn coefficient_order(
+    graph: &AlgebraicGraph,
+    segment: usize,
+    boundary_constraints: &[ConstraintRoot],
+) -> Result<Vec<usize>, CodegenError> {
+    let keys = boundary_constraints
+        .iter()
+        .enumerate()
+        .map(|(i, constraint)| {
+            let step = match constraint.domain {
+                ConstraintDomain::FirstRow => 0,
+                _ => 1,
+            };
+            let column = boundary_column(graph, &constraint.index)
+                .map_err(|err| err.in_constraint(ConstraintLocation::boundary(segment as u8, i)))?;
+            Ok((step, column))
+        })
+        .collect::<Result<Vec<_>, CodegenError>>()?;
+
+    let mut sorted = keys.clone();
+    sorted.sort();
+    Ok(keys
+        .iter()
+        .map(|key| sorted.iter().position(|k| k == key).unwrap())
+        .collect())
+}
+
+/// Returns the column constrained by the boundary constraint with the specified root.
+fn boundary_column(graph: &AlgebraicGraph, index: &NodeIndex) -> Result<usize, CodegenError> {
+    match graph.node(index).op() {
+        Operation::Sub(lhs, _) => match graph.node(lhs).op() {
+            Operation::Value(Value::TraceElement(trace_access)) => Ok(trace_access.col_idx()),
+            _ => Err(CodegenError::invalid_constraint(
+                "expected a trace column on the left-hand side",
+            )),
+        },
+        _ => Err(CodegenError::invalid_constraint(
+            "expected the subtraction of an expression from a trace column",
+        )),
+    }
+}
+
+/// Returns the error for a boundary constraint which applies neither to the first nor to the
+/// last row.
+fn invalid_domain(domain: ConstraintDomain) -> CodegenError {
+    CodegenError::invalid_constraint(format!(
+        "boundary constraints must apply to the first or last row, not to {domain}"
+    ))
+}
@@ -0,0 +1,104 @@
+use super::{expression::segment_type, signature};
+use ir::PublicInput;
+
+/// Returns the `evaluate_constraints` entry point, which evaluates and merges the transition and
+/// boundary constraints of every segment at the OOD point `x`, and returns the value of the
+/// constraint composition polynomial expected by the STARK verifier.
+///
+/// The composition coefficients are laid out as in `ConstraintCompositionCoefficients`: the
+/// coefficients of the main segment come first, followed by those of each aux segment. The aux
+/// segments and the random values are in the quadratic extension, so an AIR with aux segments
+/// evaluates to an `Ext2`.
+pub fn evaluate_constraints(nsegments: usize, public_inputs: &[PublicInput]) -> String {
+    let mut params = vec!["EvaluationFrame memory frame_0".to_string()];
+    for segment in 1..nsegments {
+        params.push(format!("AuxEvaluationFrame memory frame_{segment}"));
+    }
+    params.extend(
+        public_inputs
+            .iter()
+            .map(|(name, _)| format!("uint256[] memory {name}")),
+    );
+    if nsegments > 1 {
+        params.push("Ext2[] memory rand".to_string());
+    }
+    params.extend([
+        "CompositionCoefficients memory coeffs".to_string(),
+        "uint256 trace_length".to_string(),
+        "uint256 blowup_factor".to_string(),
+        "uint256 trace_domain_generator".to_string(),
+        "uint256 x".to_string(),
+    ]);
+
+    let ty = segment_type(nsegments - 1).sol_type();
+    let mut code = "\n    // CONSTRAINT EVALUATION\n".to_string();
+    code += "    // ===============================================\n";
+    code += &signature("evaluate_constraints", &params, ty);
+
+    // Periodic values are computed from the OOD point rather than supplied by the caller
+    code += "        uint256[] memory periodic_row = evaluate_periodic_columns(trace_length, x);\n";
+
+    for segment in 0..nsegments {
+        // Aux segments are evaluated against the main frame as well as their own
+        let mut frames = vec!["frame_0".to_string()];
+        if segment > 0 {
+            frames.push(format!("frame_{segment}"));
+        }
+        let mut transition_args = frames.clone();
+        transition_args.push("periodic_row".to_string());
+        let mut boundary_args = frames;
+        boundary_args.extend(public_inputs.iter().map(|(name, _)| name.clone()));
+        if segment > 0 {
+            transition_args.push("rand".to_string());
+            boundary_args.push("rand".to_string());
+        }
+
+        let transitions = merge(
+            &format!("merge_transitions_{segment}"),
+            &format!(
+                "evaluate_transitions_{segment}({})",
+                transition_args.join(", ")
+            ),
+        );
+        let boundaries = merge(
+            &format!("merge_boundaries_{segment}"),
+            &format!(
+                "evaluate_boundaries_{segment}({})",
+                boundary_args.join(", ")
+            ),
+        );
+
+        // The base field sum of the main segment is lifted to the extension if there are aux
+        // segments
+        code += &format!("\n        // Segment {segment}\n");
+        code += &match (segment, nsegments) {
+            (0, 1) => format!(
+                "        uint256 sum = {transitions};\n        sum = add_g(sum, {boundaries});\n"
+            ),
+            (0, _) => format!(
+                "        Ext2 memory sum = lift({transitions});\n        sum = add_e(sum, lift({boundaries}));\n"
+            ),
+            _ => format!(
+                "        sum = add_e(sum, {transitions});\n        sum = add_e(sum, {boundaries});\n"
+            ),
+        };
+    }
+
+    code += "\n        return sum;\n";
+    code += "    }\n";
+    code
+}
+
+/// Returns the call to the named merge function of a segment, for the evaluations returned by
+/// `evaluations`.
+fn merge(function: &str, evaluations: &str) -> String {
+    let mut code = format!("{function}(\n");
+    code += "            trace_length,\n";
+    code += "            blowup_factor,\n";
+    code += "            coeffs,\n";
+    code += &format!("            {evaluations},\n");
+    code += "            trace_domain_generator,\n";
+    code += "            x\n";
+    code += "        )";
+    code
+}
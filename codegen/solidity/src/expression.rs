@@ -0,0 +1,323 @@
+use ir::{
+    constraints::{AlgebraicGraph, ConstraintRoot, Operation},
+    layout::{felt, matrix_element, vector_element},
+    AccessType, CodegenError, NodeIndex, Value,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+// ELEMENT TYPES
+// ================================================================================================
+
+/// The field of the value of a node of the [AlgebraicGraph].
+///
+/// As in Winterfell, the aux trace columns and the random values are elements of the quadratic
+/// extension, and so is any node which depends on them. Everything else is in the base field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElemType {
+    Base,
+    Ext,
+}
+
+impl ElemType {
+    /// Returns the Solidity type of local variables and parameters holding values of this field.
+    pub fn sol_type(&self) -> &'static str {
+        match self {
+            Self::Base => "uint256",
+            Self::Ext => "Ext2 memory",
+        }
+    }
+
+    /// Returns the Solidity type of memory arrays of values of this field.
+    pub fn array_type(&self) -> &'static str {
+        match self {
+            Self::Base => "uint256[] memory",
+            Self::Ext => "Ext2[] memory",
+        }
+    }
+
+    /// Returns the Solidity expression allocating a memory array of `len` values of this field.
+    pub fn new_array(&self, len: usize) -> String {
+        match self {
+            Self::Base => format!("new uint256[]({len})"),
+            Self::Ext => format!("new Ext2[]({len})"),
+        }
+    }
+
+    /// Returns the functions adding and multiplying two values of this field.
+    pub fn add_mul(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Base => ("add_g", "mul_g"),
+            Self::Ext => ("add_e", "mul_e"),
+        }
+    }
+
+    /// Returns the Solidity expression for the zero of this field.
+    pub fn zero(&self) -> &'static str {
+        match self {
+            Self::Base => "0",
+            Self::Ext => "Ext2(0, 0)",
+        }
+    }
+}
+
+/// Returns the field of the evaluations of the constraints of the specified segment.
+pub fn segment_type(segment: usize) -> ElemType {
+    if segment == 0 {
+        ElemType::Base
+    } else {
+        ElemType::Ext
+    }
+}
+
+/// Returns the Solidity expression multiplying the evaluation at the specified index of the
+/// `evaluations` array by the base field `coeff`.
+pub fn scale(elem_type: ElemType, coeff: &str, index: usize, evaluations: &str) -> String {
+    match elem_type {
+        ElemType::Base => format!("mul_g({coeff}, {evaluations}[{index}])"),
+        ElemType::Ext => format!("mul_eg({evaluations}[{index}], {coeff})"),
+    }
+}
+
+// CONSTRAINT EVALUATION
+// ================================================================================================
+
+/// Builds the body of a function which evaluates a list of constraints into a memory array.
+///
+/// Leaves are read where they are used, and the value of every operation is written once to an
+/// element of the `v` memory array if it is in the base field, or of the `w` array if it is in
+/// the extension. Keeping intermediate values in memory rather than in local variables keeps the
+/// generated functions within the stack limits of the EVM, whatever the number of nodes.
+pub struct Evaluator<'a> {
+    graph: &'a AlgebraicGraph,
+    values: BTreeMap<usize, (String, ElemType)>,
+    num_base: usize,
+    num_ext: usize,
+    code: String,
+}
+
+impl<'a> Evaluator<'a> {
+    /// Returns an evaluator of nodes of the provided graph.
+    pub fn new(graph: &'a AlgebraicGraph) -> Self {
+        Self {
+            graph,
+            values: BTreeMap::new(),
+            num_base: 0,
+            num_ext: 0,
+            code: String::new(),
+        }
+    }
+
+    /// Appends the statements which evaluate the constraint and write its evaluation at the
+    /// specified index of the `array` of evaluations of a segment. The evaluations of aux
+    /// segments are extension elements, so a constraint which only depends on the main trace is
+    /// lifted to the extension.
+    ///
+    /// # Errors
+    /// Returns an error if the constraint uses a feature which is not supported by the Solidity
+    /// target.
+    pub fn evaluate_constraint(
+        &mut self,
+        constraint: &ConstraintRoot,
+        segment: usize,
+        array: &str,
+        index: usize,
+    ) -> Result<(), CodegenError> {
+        let root = constraint.node_index();
+        self.code += "\n";
+        self.evaluate(root)?;
+        self.code += &format!("        // {}\n", self.display(root));
+        let (value, elem_type) = &self.values[&root.0];
+        match (segment_type(segment), elem_type) {
+            (ElemType::Ext, ElemType::Base) => {
+                self.code += &format!("        {array}[{index}] = lift({value});\n")
+            }
+            _ => self.code += &format!("        {array}[{index}] = {value};\n"),
+        }
+        let degree = self.graph.degree(root);
+        self.code += &format!(
+            "        // deg = {}, domain: {}\n",
+            degree.base(),
+            constraint.domain()
+        );
+        Ok(())
+    }
+
+    /// Returns the declarations of the memory arrays used by the statements, followed by the
+    /// statements themselves.
+    pub fn code(&self) -> String {
+        let mut code = String::new();
+        if self.num_base > 0 {
+            code += &format!(
+                "        uint256[] memory v = new uint256[]({});\n",
+                self.num_base
+            );
+        }
+        if self.num_ext > 0 {
+            code += &format!("        Ext2[] memory w = new Ext2[]({});\n", self.num_ext);
+        }
+        code += &self.code;
+        code
+    }
+
+    /// Appends the statements which evaluate the node at the specified index and the nodes it
+    /// depends on, unless they were already evaluated. The graph only adds a node after its
+    /// children, so evaluating the reachable nodes in index order is a topological order.
+    fn evaluate(&mut self, index: &NodeIndex) -> Result<(), CodegenError> {
+        let mut reachable = BTreeSet::new();
+        let mut stack = vec![*index];
+        while let Some(index) = stack.pop() {
+            if self.values.contains_key(&index.0) || !reachable.insert(index.0) {
+                continue;
+            }
+            match self.graph.node(&index).op() {
+                Operation::Value(_) => {}
+                Operation::Add(lhs, rhs) | Operation::Sub(lhs, rhs) | Operation::Mul(lhs, rhs) => {
+                    stack.push(*lhs);
+                    stack.push(*rhs);
+                }
+                Operation::Exp(lhs, _) => stack.push(*lhs),
+            }
+        }
+
+        for index in reachable.into_iter() {
+            let value = match self.graph.node(&NodeIndex(index)).op() {
+                Operation::Value(value) => leaf(value)?,
+                Operation::Add(lhs, rhs) => self.binop("add", lhs, rhs),
+                Operation::Sub(lhs, rhs) => self.binop("sub", lhs, rhs),
+                Operation::Mul(lhs, rhs) => match (&self.values[&lhs.0], &self.values[&rhs.0]) {
+                    ((lhs, ElemType::Ext), (rhs, ElemType::Base))
+                    | ((rhs, ElemType::Base), (lhs, ElemType::Ext)) => {
+                        let expr = format!("mul_eg({lhs}, {rhs})");
+                        self.assign(expr, ElemType::Ext)
+                    }
+                    _ => self.binop("mul", lhs, rhs),
+                },
+                Operation::Exp(lhs, exponent) => self.power(lhs, *exponent),
+            };
+            self.values.insert(index, value);
+        }
+        Ok(())
+    }
+
+    /// Writes the value of a binary operation, evaluated in the base field if both operands are
+    /// base field elements, or in the extension otherwise, with a base field operand lifted to
+    /// the extension.
+    fn binop(&mut self, op: &str, lhs: &NodeIndex, rhs: &NodeIndex) -> (String, ElemType) {
+        let (lhs, lhs_type) = self.values[&lhs.0].clone();
+        let (rhs, rhs_type) = self.values[&rhs.0].clone();
+        let (expr, elem_type) = match (lhs_type, rhs_type) {
+            (ElemType::Base, ElemType::Base) => (format!("{op}_g({lhs}, {rhs})"), ElemType::Base),
+            (ElemType::Ext, ElemType::Ext) => (format!("{op}_e({lhs}, {rhs})"), ElemType::Ext),
+            (ElemType::Base, ElemType::Ext) => {
+                (format!("{op}_e(lift({lhs}), {rhs})"), ElemType::Ext)
+            }
+            (ElemType::Ext, ElemType::Base) => {
+                (format!("{op}_e({lhs}, lift({rhs}))"), ElemType::Ext)
+            }
+        };
+        self.assign(expr, elem_type)
+    }
+
+    /// Writes a power with a constant exponent, unrolled into left-to-right square-and-multiply
+    /// steps, so x^7 takes the 4 multiplications x^2, x^3, x^6 and x^7.
+    fn power(&mut self, base: &NodeIndex, exponent: usize) -> (String, ElemType) {
+        let (base, elem_type) = self.values[&base.0].clone();
+        match (exponent, elem_type) {
+            (0, ElemType::Base) => return ("1".to_string(), ElemType::Base),
+            (0, ElemType::Ext) => return ("Ext2(1, 0)".to_string(), ElemType::Ext),
+            (1, _) => return (base, elem_type),
+            _ => {}
+        }
+
+        let (_, mul) = elem_type.add_mul();
+        let mut acc = base.clone();
+        let msb = usize::BITS - 1 - exponent.leading_zeros();
+        for bit in (0..msb).rev() {
+            (acc, _) = self.assign(format!("{mul}({acc}, {acc})"), elem_type);
+            if exponent >> bit & 1 == 1 {
+                (acc, _) = self.assign(format!("{mul}({acc}, {base})"), elem_type);
+            }
+        }
+        (acc, elem_type)
+    }
+
+    /// Returns a human-readable representation of the operation of an evaluated node over the
+    /// values of its operands, which is emitted as a comment above the statement writing the
+    /// evaluation of each constraint.
+    fn display(&self, index: &NodeIndex) -> String {
+        let value = |index: &NodeIndex| &self.values[&index.0].0;
+        let op = match self.graph.node(index).op() {
+            Operation::Value(_) => value(index).to_string(),
+            Operation::Add(lhs, rhs) => format!("{} + {}", value(lhs), value(rhs)),
+            Operation::Sub(lhs, rhs) => format!("{} - {}", value(lhs), value(rhs)),
+            Operation::Mul(lhs, rhs) => format!("{} * {}", value(lhs), value(rhs)),
+            Operation::Exp(lhs, exponent) => format!("{} ^ {exponent}", value(lhs)),
+        };
+        format!("{} = {op}", value(index))
+    }
+
+    /// Writes the expression to the next element of the memory array of its field, and returns
+    /// that element.
+    fn assign(&mut self, expr: String, elem_type: ElemType) -> (String, ElemType) {
+        let element = match elem_type {
+            ElemType::Base => {
+                self.num_base += 1;
+                format!("v[{}]", self.num_base - 1)
+            }
+            ElemType::Ext => {
+                self.num_ext += 1;
+                format!("w[{}]", self.num_ext - 1)
+            }
+        };
+        self.code += &format!("        {element} = {expr};\n");
+        (element, elem_type)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the Solidity expression of a leaf of the [AlgebraicGraph] and the field of its value.
+///
+/// The frames only hold the current and the next rows. Boundary constraints are evaluated against
+/// the current row of the frame, as in Winterfell, which is the row at offset 0.
+fn leaf(value: &Value) -> Result<(String, ElemType), CodegenError> {
+    let result = match value {
+        Value::BoundConstant(symbol_access) => {
+            let name = symbol_access.name();
+            let name = match symbol_access.access_type() {
+                AccessType::Default => name.to_string(),
+                AccessType::Vector(idx) => vector_element(name, *idx),
+                AccessType::Matrix(row_idx, col_idx) => matrix_element(name, *row_idx, *col_idx),
+                AccessType::Slice(_) => {
+                    return Err(CodegenError::unsupported_feature("slices of constants"))
+                }
+            };
+            (name, ElemType::Base)
+        }
+        Value::InlineConstant(value) => (felt(*value).to_string(), ElemType::Base),
+        Value::TraceElement(trace_access) => {
+            let row = match trace_access.row_offset() {
+                0 => "current",
+                1 => "next",
+                _ => {
+                    return Err(CodegenError::unsupported_feature(
+                        "row offsets greater than 1",
+                    ))
+                }
+            };
+            let segment = trace_access.trace_segment();
+            let elem_type = if segment == 0 {
+                ElemType::Base
+            } else {
+                ElemType::Ext
+            };
+            let col = trace_access.col_idx();
+            (format!("frame_{segment}.{row}[{col}]"), elem_type)
+        }
+        Value::PeriodicColumn(idx, _) => (format!("periodic_row[{idx}]"), ElemType::Base),
+        Value::PublicInput(name, idx) => (format!("{name}[{idx}]"), ElemType::Base),
+        Value::RandomValue(idx) => (format!("rand[{idx}]"), ElemType::Ext),
+    };
+    Ok(result)
+}
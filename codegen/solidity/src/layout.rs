@@ -0,0 +1,41 @@
+use ir::AirLayout;
+
+/// Returns the declarations of the layout constants of the AIR, which are computed from the
+/// constraints by [AirLayout].
+pub fn layout(layout: &AirLayout) -> String {
+    let mut code = "\n    // LAYOUT\n".to_string();
+    code += "    // ===============================================\n";
+    code += &format!(
+        "    uint256 internal constant MAIN_SEGMENT_WIDTH = {};\n",
+        layout.main_segment_width
+    );
+    code += &format!(
+        "    uint256 internal constant AUX_TRACE_WIDTH = {};\n",
+        layout.aux_trace_width
+    );
+    code += &format!(
+        "    uint256 internal constant NUM_AUX_SEGMENTS = {};\n",
+        layout.num_aux_segments
+    );
+    code += &format!(
+        "    uint256 internal constant NUM_RANDOM_VALUES = {};\n",
+        layout.num_random_values
+    );
+    code += &format!(
+        "    uint256 internal constant NUM_TRANSITION_CONSTRAINTS = {};\n",
+        layout.num_transition_constraints
+    );
+    code += &format!(
+        "    uint256 internal constant NUM_ASSERTIONS = {};\n",
+        layout.num_assertions
+    );
+    code += &format!(
+        "    uint256 internal constant CE_BLOWUP_FACTOR = {};\n",
+        layout.ce_blowup_factor
+    );
+    code += &format!(
+        "    uint256 internal constant EVAL_FRAME_SIZE = {};\n",
+        layout.eval_frame_size
+    );
+    code
+}
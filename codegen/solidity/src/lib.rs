@@ -0,0 +1,128 @@
+use ir::{AirIR, CodegenError};
+
+mod boundary;
+mod constants;
+mod evaluate;
+mod expression;
+mod layout;
+mod periodic;
+mod transition;
+
+// GENERATE SOLIDITY CONSTRAINT EVALUATION
+// ================================================================================================
+
+/// CodeGenerator is used to generate a Solidity library with the functions which evaluate the
+/// constraints of the AirIR used to build the CodeGenerator at the OOD point, for use by an
+/// on-chain STARK verifier.
+///
+/// The generated library evaluates the constraints over the Goldilocks field and its quadratic
+/// extension with `addmod` and `mulmod`, using the free functions of `Goldilocks.sol`, which is
+/// shipped with this crate and imported from the directory of the generated file.
+pub struct CodeGenerator<'a> {
+    ir: &'a AirIR,
+}
+
+impl<'a> CodeGenerator<'a> {
+    // --- CONSTRUCTOR ----------------------------------------------------------------------------
+
+    /// Returns a new [CodeGenerator] for the provided AirIR.
+    pub fn new(ir: &'a AirIR) -> Self {
+        Self { ir }
+    }
+
+    /// Returns a string of Solidity code evaluating the constraints of the AirIR with which this
+    /// [CodeGenerator] was instantiated.
+    ///
+    /// # Errors
+    /// Returns an error if a constraint uses a feature which the Solidity target does not
+    /// support.
+    pub fn generate(&self) -> Result<String, CodegenError> {
+        let ir = self.ir;
+        let segment_widths = ir.trace_segment_widths();
+        let nsegments = segment_widths.len();
+        let graph = ir.constraint_graph();
+        let integrity_constraints = (0..nsegments as u8)
+            .map(|segment| ir.integrity_constraints(segment).to_vec())
+            .collect::<Vec<_>>();
+        let boundary_constraints = (0..nsegments as u8)
+            .map(|segment| ir.boundary_constraints(segment).to_vec())
+            .collect::<Vec<_>>();
+
+        let mut code = "// SPDX-License-Identifier: MIT\n".to_string();
+        code += &format!("// Air name {}, {nsegments} segments\n", ir.air_name());
+        code += "pragma solidity ^0.8.0;\n\n";
+        code += "import \"./Goldilocks.sol\";\n\n";
+        code += &format!("library {}Evaluator {{\n", ir.air_name());
+
+        code += "    struct EvaluationFrame {\n";
+        code += "        uint256[] current;\n";
+        code += "        uint256[] next;\n";
+        code += "    }\n";
+        if nsegments > 1 {
+            code += "\n    struct AuxEvaluationFrame {\n";
+            code += "        Ext2[] current;\n";
+            code += "        Ext2[] next;\n";
+            code += "    }\n";
+        }
+        code += "\n    struct CompositionCoefficients {\n";
+        code += "        uint256[] transition_a;\n";
+        code += "        uint256[] transition_b;\n";
+        code += "        uint256[] boundary_a;\n";
+        code += "        uint256[] boundary_b;\n";
+        code += "    }\n";
+
+        code += &constants::constants(ir.constants())?;
+        code += &layout::layout(&ir.layout());
+
+        let mut transition_offset = 0;
+        let mut boundary_offset = 0;
+        for (segment, width) in segment_widths.iter().enumerate() {
+            code += &format!("\n    // SEGMENT {segment} size {width}\n");
+            code += "    // ===============================================\n";
+            code +=
+                &transition::evaluate_transitions(graph, segment, &integrity_constraints[segment])?;
+            code += &boundary::evaluate_boundaries(
+                graph,
+                ir.public_inputs(),
+                segment,
+                &boundary_constraints[segment],
+            )?;
+            code += &transition::merge_transitions(
+                graph,
+                segment,
+                transition_offset,
+                &integrity_constraints[segment],
+                ir.num_transition_exemptions(),
+            );
+            code += &boundary::merge_boundaries(
+                graph,
+                segment,
+                boundary_offset,
+                &boundary_constraints[segment],
+                ir.num_transition_exemptions(),
+            )?;
+            transition_offset += integrity_constraints[segment].len();
+            boundary_offset += boundary_constraints[segment].len();
+        }
+
+        code += &periodic::evaluate_periodic_columns(ir.periodic_columns());
+        code += &evaluate::evaluate_constraints(nsegments, ir.public_inputs());
+        code += "}\n";
+        Ok(code)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the signature of an internal pure function of the library, up to its opening brace.
+fn signature(name: &str, params: &[String], returns: &str) -> String {
+    let mut code = format!("\n    function {name}(\n");
+    code += &params
+        .iter()
+        .map(|param| format!("        {param}"))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    code += &format!("\n    ) internal pure returns ({returns}) {{\n");
+    code
+}
@@ -0,0 +1,57 @@
+use super::signature;
+use ir::PeriodicColumn;
+use winter_math::{fft, fields::f64::BaseElement, StarkField};
+
+/// Returns the `evaluate_periodic_columns` function, which evaluates the periodic columns at the
+/// OOD point.
+///
+/// Each periodic column is interpolated at compile time into a polynomial over the domain of its
+/// cycle, exactly as Winterfell does. The generated function evaluates each polynomial at
+/// `x^(trace_length / cycle_len)` with Horner's rule and returns the values in the order in
+/// which the columns are declared.
+pub fn evaluate_periodic_columns(periodic_columns: &[PeriodicColumn]) -> String {
+    let mut code = "\n    // PERIODIC COLUMNS\n".to_string();
+    code += "    // ===============================================\n";
+    let params = ["uint256 trace_length".to_string(), "uint256 x".to_string()];
+    code += &signature("evaluate_periodic_columns", &params, "uint256[] memory");
+    code += &format!(
+        "        uint256[] memory periodic_row = new uint256[]({});\n",
+        periodic_columns.len()
+    );
+    if !periodic_columns.is_empty() {
+        code += "        uint256 z;\n";
+        code += "        uint256 p;\n";
+    }
+
+    for (col, values) in periodic_columns.iter().enumerate() {
+        let cycle_len = values.len();
+        let coeffs = interpolate(values);
+
+        code += &format!("\n        // Periodic column {col}: cycle length {cycle_len}\n");
+        code += &format!("        z = pow_g(x, trace_length / {cycle_len});\n");
+
+        // Horner evaluation, starting from the leading coefficient
+        let last = coeffs.len() - 1;
+        code += &format!("        p = {};\n", coeffs[last]);
+        for coeff in coeffs[..last].iter().rev() {
+            code += &format!("        p = add_g(mul_g(p, z), {coeff});\n");
+        }
+        code += &format!("        periodic_row[{col}] = p;\n");
+    }
+
+    code += "\n        return periodic_row;\n";
+    code += "    }\n";
+    code
+}
+
+/// Returns the coefficients of the polynomial interpolated from the values of a periodic column
+/// over the domain of its cycle.
+fn interpolate(values: &[u64]) -> Vec<u64> {
+    let mut poly = values
+        .iter()
+        .map(|v| BaseElement::new(*v))
+        .collect::<Vec<_>>();
+    let inv_twiddles = fft::get_inv_twiddles::<BaseElement>(poly.len());
+    fft::interpolate_poly(&mut poly, &inv_twiddles);
+    poly.iter().map(|c| c.as_int()).collect()
+}
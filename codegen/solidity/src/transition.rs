@@ -0,0 +1,158 @@
+use super::{
+    expression::{scale, segment_type, ElemType, Evaluator},
+    signature,
+};
+use ir::{
+    constraints::{AlgebraicGraph, ConstraintRoot},
+    CodegenError, ConstraintLocation,
+};
+use std::collections::BTreeMap;
+
+/// Returns the `evaluate_transitions_{segment}` function, which evaluates the integrity
+/// constraints of a trace segment against the OOD frame and returns their evaluations in the
+/// order of the constraints.
+///
+/// The constraints of aux segments are evaluated in the quadratic extension, so their
+/// evaluations are `Ext2` elements, and constraints which only depend on the main trace are
+/// lifted to the extension.
+///
+/// # Errors
+/// Returns an error naming the constraint if it uses a feature which is not supported by the
+/// Solidity target.
+pub fn evaluate_transitions(
+    graph: &AlgebraicGraph,
+    segment: usize,
+    integrity_constraints: &[ConstraintRoot],
+) -> Result<String, CodegenError> {
+    let elem_type = segment_type(segment);
+
+    let mut params = vec!["EvaluationFrame memory frame_0".to_string()];
+    if segment > 0 {
+        params.push(format!("AuxEvaluationFrame memory frame_{segment}"));
+    }
+    params.push("uint256[] memory periodic_row".to_string());
+    if segment > 0 {
+        params.push("Ext2[] memory rand".to_string());
+    }
+
+    let mut evaluator = Evaluator::new(graph);
+    for (i, constraint) in integrity_constraints.iter().enumerate() {
+        evaluator
+            .evaluate_constraint(constraint, segment, "t_evaluations", i)
+            .map_err(|err| err.in_constraint(ConstraintLocation::integrity(segment as u8, i)))?;
+    }
+
+    let mut code = signature(
+        &format!("evaluate_transitions_{segment}"),
+        &params,
+        elem_type.array_type(),
+    );
+    code += &format!(
+        "        {} t_evaluations = {};\n",
+        elem_type.array_type(),
+        elem_type.new_array(integrity_constraints.len())
+    );
+    code += &evaluator.code();
+    code += "\n        return t_evaluations;\n";
+    code += "    }\n";
+    Ok(code)
+}
+
+/// Returns the `merge_transitions_{segment}` function, which merges the evaluations of the
+/// integrity constraints of a trace segment into their contribution to the constraint
+/// composition polynomial at the OOD point `x`.
+///
+/// This follows Winterfell's `TransitionConstraints`: constraints are grouped by their
+/// evaluation degree, which depends on their base degree and on the cycles of the periodic
+/// columns they use, and the evaluations of each group are adjusted to the target degree before
+/// the sum is divided by the transition divisor, which exempts the last
+/// `num_transition_exemptions` rows. The coefficients of the segment start at `offset` in the
+/// transition coefficients, and are taken in the order of the constraints.
+pub fn merge_transitions(
+    graph: &AlgebraicGraph,
+    segment: usize,
+    offset: usize,
+    integrity_constraints: &[ConstraintRoot],
+    num_transition_exemptions: usize,
+) -> String {
+    let elem_type = segment_type(segment);
+    let ty = elem_type.sol_type();
+    let (add, _) = elem_type.add_mul();
+
+    let params = [
+        "uint256 trace_length".to_string(),
+        "uint256 blowup_factor".to_string(),
+        "CompositionCoefficients memory coeffs".to_string(),
+        format!("{} t_evaluations", elem_type.array_type()),
+        "uint256 trace_domain_generator".to_string(),
+        "uint256 x".to_string(),
+    ];
+    let mut code = signature(&format!("merge_transitions_{segment}"), &params, ty);
+
+    // The transition divisor exempts the last rows, so its degree is the trace length minus the
+    // number of exemptions
+    code += "        uint256 composition_degree = trace_length * blowup_factor - 1;\n";
+    code += &format!(
+        "        uint256 target_degree = composition_degree + trace_length - {num_transition_exemptions};\n\n"
+    );
+    let factors = (1..=num_transition_exemptions)
+        .map(|i| format!("(x - g^(n - {i}))"))
+        .collect::<Vec<_>>();
+    let mut denominator = "sub_g(x, pow_g(trace_domain_generator, trace_length - 1))".to_string();
+    for i in 2..=num_transition_exemptions {
+        denominator = format!(
+            "mul_g({denominator}, sub_g(x, pow_g(trace_domain_generator, trace_length - {i})))"
+        );
+    }
+    code += &format!(
+        "        // Evaluate the transition divisor (x^n - 1) / {}\n",
+        factors.join(" * ")
+    );
+    code += "        uint256 z = div_g(\n";
+    code += "            sub_g(pow_g(x, trace_length), 1),\n";
+    code += &format!("            {denominator}\n");
+    code += "        );\n\n";
+    code += &format!("        {ty} sum = {};\n", elem_type.zero());
+    code += "        uint256 xp;\n";
+
+    let mut groups: BTreeMap<(usize, Vec<usize>), Vec<usize>> = BTreeMap::new();
+    for (i, constraint) in integrity_constraints.iter().enumerate() {
+        let degree = graph.degree(&constraint.index);
+        groups
+            .entry((degree.base(), degree.cycles().to_vec()))
+            .or_default()
+            .push(i);
+    }
+
+    for ((base, cycles), constraints) in groups {
+        let mut evaluation_degree = format!("{base} * (trace_length - 1)");
+        for cycle in cycles.iter() {
+            evaluation_degree += &format!(" + trace_length / {cycle} * {}", cycle - 1);
+        }
+        code += &format!("\n        // Merge evaluation degree {base}");
+        if !cycles.is_empty() {
+            let cycles = cycles.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+            code += &format!(", cycles {}", cycles.join(", "));
+        }
+        code += "\n";
+        code += &format!("        xp = pow_g(x, target_degree - ({evaluation_degree}));\n");
+        for i in constraints {
+            let j = offset + i;
+            let coeff =
+                format!("add_g(coeffs.transition_a[{j}], mul_g(coeffs.transition_b[{j}], xp))");
+            code += &format!("\n        // Include transition {i}\n");
+            code += &format!(
+                "        sum = {add}(sum, {});\n",
+                scale(elem_type, &coeff, i, "t_evaluations")
+            );
+        }
+    }
+
+    code += "\n";
+    match elem_type {
+        ElemType::Base => code += "        return div_g(sum, z);\n",
+        ElemType::Ext => code += "        return mul_eg(sum, inv_g(z));\n",
+    }
+    code += "    }\n";
+    code
+}
@@ -1,8 +1,6 @@
-use core::panic;
-
 use super::{
-    AirIR, AlgebraicGraph, Codegen, ConstraintDomain, ElemType, Impl, NodeIndex, Operation,
-    TraceAccess, Value,
+    AirIR, AlgebraicGraph, Codegen, CodegenError, ConstraintDomain, ConstraintLocation,
+    ConstraintRoot, ElemType, Impl, NodeIndex, Operation, TraceAccess, Value,
 };
 
 // HELPERS TO GENERATE THE WINTERFELL BOUNDARY CONSTRAINT METHODS
@@ -10,8 +8,7 @@ use super::{
 
 /// Adds an implementation of the "get_assertions" method to the referenced Air implementation
 /// based on the data in the provided AirIR.
-/// TODO: add result types to these functions.
-pub(super) fn add_fn_get_assertions(impl_ref: &mut Impl, ir: &AirIR) {
+pub(super) fn add_fn_get_assertions(impl_ref: &mut Impl, ir: &AirIR) -> Result<(), CodegenError> {
     // define the function
     let get_assertions = impl_ref
         .new_fn("get_assertions")
@@ -19,15 +16,19 @@ pub(super) fn add_fn_get_assertions(impl_ref: &mut Impl, ir: &AirIR) {
         .ret("Vec<Assertion<Felt>>");
     get_assertions.line("type E = Felt;");
     // add the boundary constraints
-    add_assertions(get_assertions, ir, 0);
+    add_assertions(get_assertions, ir, 0)?;
 
     // return the result
     get_assertions.line("result");
+    Ok(())
 }
 
 /// Adds an implementation of the "get_aux_assertions" method to the referenced Air implementation
 /// based on the data in the provided AirIR.
-pub(super) fn add_fn_get_aux_assertions(impl_ref: &mut Impl, ir: &AirIR) {
+pub(super) fn add_fn_get_aux_assertions(
+    impl_ref: &mut Impl,
+    ir: &AirIR,
+) -> Result<(), CodegenError> {
     // define the function
     let get_aux_assertions = impl_ref
         .new_fn("get_aux_assertions")
@@ -37,15 +38,20 @@ pub(super) fn add_fn_get_aux_assertions(impl_ref: &mut Impl, ir: &AirIR) {
         .ret("Vec<Assertion<E>>");
 
     // add the boundary constraints
-    add_assertions(get_aux_assertions, ir, 1);
+    add_assertions(get_aux_assertions, ir, 1)?;
 
     // return the result
     get_aux_assertions.line("result");
+    Ok(())
 }
 
 /// Declares a result vector and adds assertions for boundary constraints to it for the specified
 /// trace segment
-fn add_assertions(func_body: &mut codegen::Function, ir: &AirIR, trace_segment: u8) {
+fn add_assertions(
+    func_body: &mut codegen::Function,
+    ir: &AirIR,
+    trace_segment: u8,
+) -> Result<(), CodegenError> {
     let elem_type = if trace_segment == 0 {
         ElemType::Base
     } else {
@@ -56,28 +62,42 @@ fn add_assertions(func_body: &mut codegen::Function, ir: &AirIR, trace_segment:
     func_body.line("let mut result = Vec::new();");
 
     // add the boundary constraints
-    for constraint in ir.boundary_constraints(trace_segment) {
-        let (trace_access, expr_root) =
-            split_boundary_constraint(ir.constraint_graph(), constraint.node_index());
-        debug_assert!(trace_access.trace_segment() == trace_segment);
-
-        let assertion = format!(
-            "result.push(Assertion::<E>::single({}, {}, {}.into()));",
-            trace_access.col_idx(),
-            domain_to_str(constraint.domain()),
-            expr_root.to_string(ir, elem_type, trace_segment)
-        );
+    for (idx, constraint) in ir.boundary_constraints(trace_segment).iter().enumerate() {
+        let assertion = boundary_assertion(ir, constraint, elem_type, trace_segment)
+            .map_err(|err| err.in_constraint(ConstraintLocation::boundary(trace_segment, idx)))?;
         func_body.line(assertion);
     }
+    Ok(())
+}
+
+/// Returns the line of generated code which adds the assertion of the provided boundary constraint
+/// to the result vector.
+fn boundary_assertion(
+    ir: &AirIR,
+    constraint: &ConstraintRoot,
+    elem_type: ElemType,
+    trace_segment: u8,
+) -> Result<String, CodegenError> {
+    let (trace_access, expr_root) =
+        split_boundary_constraint(ir.constraint_graph(), constraint.node_index())?;
+    debug_assert!(trace_access.trace_segment() == trace_segment);
+
+    Ok(format!(
+        "result.push(Assertion::<E>::single({}, {}, {}.into()));",
+        trace_access.col_idx(),
+        domain_to_str(constraint.domain())?,
+        expr_root.to_string(ir, elem_type, trace_segment)?
+    ))
 }
 
 /// Returns a string slice representing the provided constraint domain.
-fn domain_to_str(domain: ConstraintDomain) -> String {
+fn domain_to_str(domain: ConstraintDomain) -> Result<String, CodegenError> {
     match domain {
-        ConstraintDomain::FirstRow => "0".to_string(),
-        ConstraintDomain::LastRow => "self.last_step()".to_string(),
-        // TODO: replace this with an Error once we have a Result return type.
-        _ => panic!("invalid constraint domain"),
+        ConstraintDomain::FirstRow => Ok("0".to_string()),
+        ConstraintDomain::LastRow => Ok("self.last_step()".to_string()),
+        _ => Err(CodegenError::invalid_constraint(format!(
+            "boundary constraints must apply to the first or last row, not to {domain}"
+        ))),
     }
 }
 
@@ -89,21 +109,25 @@ fn domain_to_str(domain: ConstraintDomain) -> String {
 /// boundary constraint expression must hold, as well as the node index that represents the root
 /// of the constraint expression that must equal zero during evaluation.
 ///
-/// TODO: replace panics with Result and Error
+/// Returns an error if the node is not the subtraction of an expression from a trace column.
 pub fn split_boundary_constraint(
     graph: &AlgebraicGraph,
     index: &NodeIndex,
-) -> (TraceAccess, NodeIndex) {
+) -> Result<(TraceAccess, NodeIndex), CodegenError> {
     let node = graph.node(index);
     match node.op() {
         Operation::Sub(lhs, rhs) => {
             if let Operation::Value(Value::TraceElement(trace_access)) = graph.node(lhs).op() {
                 debug_assert!(trace_access.row_offset() == 0);
-                (*trace_access, *rhs)
+                Ok((*trace_access, *rhs))
             } else {
-                panic!("InvalidUsage: index {index:?} is not the constraint root of a boundary constraint");
+                Err(CodegenError::invalid_constraint(
+                    "expected a trace column on the left-hand side",
+                ))
             }
         }
-        _ => panic!("InvalidUsage: index {index:?} is not the root index of a constraint"),
+        _ => Err(CodegenError::invalid_constraint(
+            "expected the subtraction of an expression from a trace column",
+        )),
     }
 }
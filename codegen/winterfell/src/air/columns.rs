@@ -0,0 +1,133 @@
+use super::{AirIR, Scope};
+
+// HELPERS TO GENERATE THE COLUMN INDICES AND THE TRACE ROW BUILDER
+// ================================================================================================
+
+/// Updates the provided scope with a `columns` module of constants holding the indices of the
+/// trace columns by their declared names, and with a builder for the rows of the main trace which
+/// sets the columns by name.
+///
+/// A single column `clk` is given the constant `CLK`, and a group of columns `state` is given the
+/// constants `STATE_START` and `STATE_LEN`. The indices are relative to the trace segment of the
+/// columns.
+pub(super) fn add_columns(scope: &mut Scope, ir: &AirIR) {
+    add_columns_module(scope, ir);
+    add_main_trace_row(scope, ir);
+}
+
+/// Adds the module of column index constants to the provided scope.
+fn add_columns_module(scope: &mut Scope, ir: &AirIR) {
+    let module = scope.new_module("columns").vis("pub").scope();
+    module.raw("//! The indices of the columns of the execution trace, by their declared names.");
+
+    // add the widths of the trace segments.
+    let mut widths = vec![format!(
+        "/// The number of columns of the main trace.\npub const MAIN_WIDTH: usize = {};",
+        ir.trace_segment_widths()[0]
+    )];
+    if let Some(aux_width) = ir.trace_segment_widths().get(1) {
+        widths.push(format!(
+            "/// The number of columns of the auxiliary trace.\npub const AUX_WIDTH: usize = \
+            {aux_width};"
+        ));
+    }
+    module.raw(widths.join("\n"));
+
+    // add the indices of the columns of each segment.
+    for binding in ir.trace_bindings() {
+        let name = binding.name();
+        let constant = name.to_uppercase();
+        let segment = segment_name(binding.trace_segment());
+        if binding.size() == 1 {
+            module.raw(format!(
+                "/// The index of the `{name}` column of the {segment} trace.\n\
+                pub const {constant}: usize = {};",
+                binding.offset()
+            ));
+        } else {
+            module.raw(format!(
+                "/// The index of the first column of the `{name}` group of the {segment} trace.\n\
+                pub const {constant}_START: usize = {};\n\
+                /// The number of columns of the `{name}` group of the {segment} trace.\n\
+                pub const {constant}_LEN: usize = {};",
+                binding.offset(),
+                binding.size()
+            ));
+        }
+    }
+}
+
+/// Adds a builder for the rows of the main trace to the provided scope, with a setter for each
+/// column or group of columns of the main trace.
+fn add_main_trace_row(scope: &mut Scope, ir: &AirIR) {
+    scope
+        .new_struct("MainTraceRow")
+        .doc("A row of the main trace, whose columns are set by their declared names.")
+        .vis("pub")
+        .derive("Debug")
+        .derive("Clone")
+        .derive("Copy")
+        .field("values", "[Felt; columns::MAIN_WIDTH]");
+
+    let base_impl = scope.new_impl("MainTraceRow");
+    base_impl
+        .new_fn("new")
+        .doc("Returns a row whose columns are all zero.")
+        .vis("pub")
+        .ret("Self")
+        .line("Self {")
+        .line("    values: [Felt::ZERO; columns::MAIN_WIDTH],")
+        .line("}");
+
+    for binding in ir.trace_bindings() {
+        if binding.trace_segment() != 0 {
+            continue;
+        }
+        let name = binding.name();
+        let constant = name.to_uppercase();
+        let setter = base_impl
+            .new_fn(&format!("set_{}", name.to_lowercase()))
+            .vis("pub")
+            .arg_mut_self()
+            .ret("&mut Self");
+        if binding.size() == 1 {
+            setter
+                .doc(format!("Sets the value of the `{name}` column."))
+                .arg("value", "Felt")
+                .line(format!("self.values[columns::{constant}] = value;"));
+        } else {
+            setter
+                .doc(format!("Sets the values of the `{name}` group of columns."))
+                .arg("values", format!("[Felt; columns::{constant}_LEN]"))
+                .line(format!(
+                    "self.values[columns::{constant}_START..columns::{constant}_START + \
+                    columns::{constant}_LEN]"
+                ))
+                .line("    .copy_from_slice(&values);");
+        }
+        setter.line("self");
+    }
+
+    base_impl
+        .new_fn("values")
+        .doc("Returns the values of the columns of the row, in the order of the main trace.")
+        .vis("pub")
+        .arg_ref_self()
+        .ret("&[Felt; columns::MAIN_WIDTH]")
+        .line("&self.values");
+
+    scope
+        .new_impl("MainTraceRow")
+        .impl_trait("Default")
+        .new_fn("default")
+        .ret("Self")
+        .line("Self::new()");
+}
+
+/// Returns the name of the trace segment with the specified index.
+fn segment_name(trace_segment: u8) -> &'static str {
+    match trace_segment {
+        0 => "main",
+        _ => "auxiliary",
+    }
+}
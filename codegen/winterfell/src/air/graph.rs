@@ -1,22 +1,34 @@
 use super::{
-    AccessType, AirIR, ElemType, IntegrityConstraintDegree, NodeIndex, Operation, TraceAccess,
-    Value,
+    AccessType, AirIR, CodegenError, ConstraintRoot, ElemType, IntegrityConstraintDegree,
+    NodeIndex, Operation, TraceAccess, Value,
 };
+use std::collections::BTreeMap;
 
 // RUST STRING GENERATION FOR THE CONSTRAINT GRAPH
 // ================================================================================================
 
 /// Code generation trait for generating Rust code strings from IR types related to constraints and
 /// the [AlgebraicGraph].
-/// TODO: replace panics with errors
+///
+/// Returns an error if the IR uses a feature which cannot be expressed in a Winterfell Air.
 pub trait Codegen {
-    fn to_string(&self, ir: &AirIR, elem_type: ElemType, trace_segment: u8) -> String;
+    fn to_string(
+        &self,
+        ir: &AirIR,
+        elem_type: ElemType,
+        trace_segment: u8,
+    ) -> Result<String, CodegenError>;
 }
 
 impl Codegen for IntegrityConstraintDegree {
-    fn to_string(&self, _ir: &AirIR, _elem_type: ElemType, _trace_segment: u8) -> String {
+    fn to_string(
+        &self,
+        _ir: &AirIR,
+        _elem_type: ElemType,
+        _trace_segment: u8,
+    ) -> Result<String, CodegenError> {
         if self.cycles().is_empty() {
-            format!("TransitionConstraintDegree::new({})", self.base())
+            Ok(format!("TransitionConstraintDegree::new({})", self.base()))
         } else {
             let cycles = self
                 .cycles()
@@ -24,17 +36,22 @@ impl Codegen for IntegrityConstraintDegree {
                 .map(|cycle_len| cycle_len.to_string())
                 .collect::<Vec<String>>()
                 .join(", ");
-            format!(
+            Ok(format!(
                 "TransitionConstraintDegree::with_cycles({}, vec![{}])",
                 self.base(),
                 cycles
-            )
+            ))
         }
     }
 }
 
 impl Codegen for TraceAccess {
-    fn to_string(&self, _ir: &AirIR, _elem_type: ElemType, trace_segment: u8) -> String {
+    fn to_string(
+        &self,
+        _ir: &AirIR,
+        _elem_type: ElemType,
+        trace_segment: u8,
+    ) -> Result<String, CodegenError> {
         let frame = if let 0 = self.trace_segment() {
             "main"
         } else {
@@ -47,60 +64,50 @@ impl Codegen for TraceAccess {
             1 => {
                 format!("next[{}]", self.col_idx())
             }
-            _ => panic!("Winterfell doesn't support row offsets greater than 1."),
+            _ => {
+                return Err(CodegenError::unsupported_feature(
+                    "row offsets greater than 1",
+                ))
+            }
         };
         if self.trace_segment() == 0 && self.trace_segment() != trace_segment {
-            format!("E::from({frame}_{row_offset})")
+            Ok(format!("E::from({frame}_{row_offset})"))
         } else {
-            format!("{frame}_{row_offset}")
+            Ok(format!("{frame}_{row_offset}"))
         }
     }
 }
 
 impl Codegen for NodeIndex {
-    fn to_string(&self, ir: &AirIR, elem_type: ElemType, trace_segment: u8) -> String {
-        let op = ir.constraint_graph().node(self).op();
-        op.to_string(ir, elem_type, trace_segment)
+    fn to_string(
+        &self,
+        ir: &AirIR,
+        elem_type: ElemType,
+        trace_segment: u8,
+    ) -> Result<String, CodegenError> {
+        node_to_string(self, ir, elem_type, trace_segment, &Bindings::default())
     }
 }
 
 impl Codegen for Operation {
-    fn to_string(&self, ir: &AirIR, elem_type: ElemType, trace_segment: u8) -> String {
-        match self {
-            Operation::Value(value) => value.to_string(ir, elem_type, trace_segment),
-            Operation::Add(_, _) => binary_op_to_string(ir, self, elem_type, trace_segment),
-            Operation::Sub(_, _) => binary_op_to_string(ir, self, elem_type, trace_segment),
-            Operation::Mul(_, _) => binary_op_to_string(ir, self, elem_type, trace_segment),
-            // TODO: move this logic to a helper function
-            Operation::Exp(l_idx, r_idx) => {
-                let lhs = l_idx.to_string(ir, elem_type, trace_segment);
-                let lhs = if is_leaf(l_idx, ir) {
-                    lhs
-                } else {
-                    format!("({lhs})")
-                };
-                match r_idx {
-                    0 => match elem_type {
-                        // x^0 = 1
-                        ElemType::Base => "Felt::ONE".to_string(),
-                        ElemType::Ext => "E::ONE".to_string(),
-                    },
-                    1 => lhs, // x^1 = x
-                    _ => match elem_type {
-                        ElemType::Base => format!("{lhs}.exp(Felt::new({r_idx}))"),
-                        ElemType::Ext => {
-                            format!("{lhs}.exp(E::PositiveInteger::from({r_idx}_u64))")
-                        }
-                    },
-                }
-            }
-        }
+    fn to_string(
+        &self,
+        ir: &AirIR,
+        elem_type: ElemType,
+        trace_segment: u8,
+    ) -> Result<String, CodegenError> {
+        op_to_string(self, ir, elem_type, trace_segment, &Bindings::default())
     }
 }
 
 impl Codegen for Value {
-    fn to_string(&self, ir: &AirIR, elem_type: ElemType, trace_segment: u8) -> String {
-        match self {
+    fn to_string(
+        &self,
+        ir: &AirIR,
+        elem_type: ElemType,
+        trace_segment: u8,
+    ) -> Result<String, CodegenError> {
+        let result = match self {
             // TODO: move constant handling to a helper function
             Value::InlineConstant(0) => match elem_type {
                 ElemType::Base => "Felt::ZERO".to_string(),
@@ -123,7 +130,9 @@ impl Codegen for Value {
                     AccessType::Matrix(row_idx, col_idx) => {
                         format!("{name}[{row_idx}][{col_idx}]",)
                     }
-                    AccessType::Slice(_) => panic!("unsupported access type"),
+                    AccessType::Slice(_) => {
+                        return Err(CodegenError::unsupported_feature("slices of constants"))
+                    }
                 };
                 match elem_type {
                     ElemType::Base => base_value,
@@ -131,10 +140,15 @@ impl Codegen for Value {
                 }
             }
             Value::TraceElement(trace_access) => {
-                trace_access.to_string(ir, elem_type, trace_segment)
+                trace_access.to_string(ir, elem_type, trace_segment)?
             }
             Value::PeriodicColumn(col_idx, _) => {
-                format!("periodic_values[{col_idx}]")
+                // periodic values are base field elements in the auxiliary transition constraints.
+                if trace_segment == 0 {
+                    format!("periodic_values[{col_idx}]")
+                } else {
+                    format!("E::from(periodic_values[{col_idx}])")
+                }
             }
             Value::PublicInput(ident, idx) => {
                 format!("self.{ident}[{idx}]")
@@ -142,6 +156,149 @@ impl Codegen for Value {
             Value::RandomValue(idx) => {
                 format!("aux_rand_elements.get_segment_elements(0)[{idx}]")
             }
+        };
+        Ok(result)
+    }
+}
+
+// SHARED SUBEXPRESSIONS
+// ================================================================================================
+
+/// The nodes of the constraint graph which are bound to local variables, and are referenced by
+/// the names of these variables instead of being rendered inline.
+#[derive(Debug, Default)]
+pub struct Bindings {
+    names: BTreeMap<usize, String>,
+}
+
+impl Bindings {
+    /// Binds the node at the specified index to the local variable with the provided name.
+    pub fn insert(&mut self, idx: &NodeIndex, name: String) {
+        self.names.insert(idx.0, name);
+    }
+
+    /// Returns the name of the local variable to which the node at the specified index is bound,
+    /// if any.
+    pub fn get(&self, idx: &NodeIndex) -> Option<&str> {
+        self.names.get(&idx.0).map(String::as_str)
+    }
+
+    /// Returns the number of bound nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+}
+
+/// Returns the operations of the constraint graph which are used more than once by the provided
+/// constraints, either by several parents or by several constraints, ordered so that each node
+/// comes after the nodes it uses. Each node is returned with the index of the first constraint
+/// which uses it.
+///
+/// Nodes which are rendered without any arithmetic, such as leaves, are never returned, since they
+/// are cheaper to render inline than to bind.
+pub fn shared_nodes(ir: &AirIR, constraints: &[ConstraintRoot]) -> Vec<(NodeIndex, usize)> {
+    let mut uses = BTreeMap::new();
+    let mut order = Vec::new();
+    for (constraint_idx, constraint) in constraints.iter().enumerate() {
+        visit_node(
+            ir,
+            constraint.node_index(),
+            constraint_idx,
+            &mut uses,
+            &mut order,
+        );
+    }
+    order
+        .into_iter()
+        .filter(|(idx, _)| uses[&idx.0] > 1 && !is_trivial(idx, ir))
+        .collect()
+}
+
+/// Counts a use of the node at the specified index, and visits its children if it had not been
+/// visited yet. Nodes are appended to the order after their children.
+fn visit_node(
+    ir: &AirIR,
+    idx: &NodeIndex,
+    constraint_idx: usize,
+    uses: &mut BTreeMap<usize, usize>,
+    order: &mut Vec<(NodeIndex, usize)>,
+) {
+    let count = uses.entry(idx.0).or_insert(0);
+    *count += 1;
+    if *count > 1 {
+        return;
+    }
+    match ir.constraint_graph().node(idx).op() {
+        Operation::Value(_) => {}
+        Operation::Add(l_idx, r_idx)
+        | Operation::Sub(l_idx, r_idx)
+        | Operation::Mul(l_idx, r_idx) => {
+            visit_node(ir, l_idx, constraint_idx, uses, order);
+            visit_node(ir, r_idx, constraint_idx, uses, order);
+        }
+        Operation::Exp(l_idx, _) => visit_node(ir, l_idx, constraint_idx, uses, order),
+    }
+    order.push((*idx, constraint_idx));
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns a string representation of the node at the specified index, using the names of the
+/// nodes bound to local variables.
+pub fn node_to_string(
+    idx: &NodeIndex,
+    ir: &AirIR,
+    elem_type: ElemType,
+    trace_segment: u8,
+    bindings: &Bindings,
+) -> Result<String, CodegenError> {
+    match bindings.get(idx) {
+        Some(name) => Ok(name.to_string()),
+        None => {
+            let op = ir.constraint_graph().node(idx).op();
+            op_to_string(op, ir, elem_type, trace_segment, bindings)
+        }
+    }
+}
+
+/// Returns a string representation of an operation, using the names of the nodes bound to local
+/// variables.
+fn op_to_string(
+    op: &Operation,
+    ir: &AirIR,
+    elem_type: ElemType,
+    trace_segment: u8,
+    bindings: &Bindings,
+) -> Result<String, CodegenError> {
+    match op {
+        Operation::Value(value) => value.to_string(ir, elem_type, trace_segment),
+        Operation::Add(_, _) => binary_op_to_string(ir, op, elem_type, trace_segment, bindings),
+        Operation::Sub(_, _) => binary_op_to_string(ir, op, elem_type, trace_segment, bindings),
+        Operation::Mul(_, _) => binary_op_to_string(ir, op, elem_type, trace_segment, bindings),
+        // TODO: move this logic to a helper function
+        Operation::Exp(l_idx, r_idx) => {
+            let lhs = node_to_string(l_idx, ir, elem_type, trace_segment, bindings)?;
+            let lhs = if is_leaf(l_idx, ir) || bindings.get(l_idx).is_some() {
+                lhs
+            } else {
+                format!("({lhs})")
+            };
+            let result = match r_idx {
+                0 => match elem_type {
+                    // x^0 = 1
+                    ElemType::Base => "Felt::ONE".to_string(),
+                    ElemType::Ext => "E::ONE".to_string(),
+                },
+                1 => lhs, // x^1 = x
+                _ => match elem_type {
+                    ElemType::Base => format!("{lhs}.exp(Felt::new({r_idx}))"),
+                    ElemType::Ext => {
+                        format!("{lhs}.exp(E::PositiveInteger::from({r_idx}_u64))")
+                    }
+                },
+            };
+            Ok(result)
         }
     }
 }
@@ -154,41 +311,69 @@ fn is_leaf(idx: &NodeIndex, ir: &AirIR) -> bool {
     )
 }
 
+/// Returns true if the node at the specified index is rendered without any arithmetic, which is
+/// the case for leaves and for their powers with an exponent of 0 or 1.
+fn is_trivial(idx: &NodeIndex, ir: &AirIR) -> bool {
+    match ir.constraint_graph().node(idx).op() {
+        Operation::Value(_) => true,
+        Operation::Exp(l_idx, 0 | 1) => is_trivial(l_idx, ir),
+        _ => false,
+    }
+}
+
+/// Returns the precedence of the node at the specified index. Nodes bound to local variables are
+/// referenced by name, so they never need to be parenthesized.
+fn precedence(idx: &NodeIndex, ir: &AirIR, bindings: &Bindings) -> usize {
+    match bindings.get(idx) {
+        Some(_) => usize::MAX,
+        None => ir.constraint_graph().node(idx).op().precedence(),
+    }
+}
+
 /// Returns a string representation of a binary operation.
 fn binary_op_to_string(
     ir: &AirIR,
     op: &Operation,
     elem_type: ElemType,
     trace_segment: u8,
-) -> String {
-    match op {
+    bindings: &Bindings,
+) -> Result<String, CodegenError> {
+    let result = match op {
         Operation::Add(l_idx, r_idx) => {
-            let lhs = l_idx.to_string(ir, elem_type, trace_segment);
-            let rhs = r_idx.to_string(ir, elem_type, trace_segment);
+            let lhs = node_to_string(l_idx, ir, elem_type, trace_segment, bindings)?;
+            let rhs = node_to_string(r_idx, ir, elem_type, trace_segment, bindings)?;
             format!("{lhs} + {rhs}")
         }
         Operation::Sub(l_idx, r_idx) => {
-            let lhs = l_idx.to_string(ir, elem_type, trace_segment);
-            let rhs = if ir.constraint_graph().node(r_idx).op().precedence() <= op.precedence() {
-                format!("({})", r_idx.to_string(ir, elem_type, trace_segment))
+            let lhs = node_to_string(l_idx, ir, elem_type, trace_segment, bindings)?;
+            let rhs = node_to_string(r_idx, ir, elem_type, trace_segment, bindings)?;
+            let rhs = if precedence(r_idx, ir, bindings) <= op.precedence() {
+                format!("({rhs})")
             } else {
-                r_idx.to_string(ir, elem_type, trace_segment)
+                rhs
             };
             format!("{lhs} - {rhs}")
         }
         Operation::Mul(l_idx, r_idx) => {
-            let lhs = if ir.constraint_graph().node(l_idx).op().precedence() < op.precedence() {
-                format!("({})", l_idx.to_string(ir, elem_type, trace_segment))
+            let lhs = node_to_string(l_idx, ir, elem_type, trace_segment, bindings)?;
+            let lhs = if precedence(l_idx, ir, bindings) < op.precedence() {
+                format!("({lhs})")
             } else {
-                l_idx.to_string(ir, elem_type, trace_segment)
+                lhs
             };
-            let rhs = if ir.constraint_graph().node(r_idx).op().precedence() < op.precedence() {
-                format!("({})", r_idx.to_string(ir, elem_type, trace_segment))
+            let rhs = node_to_string(r_idx, ir, elem_type, trace_segment, bindings)?;
+            let rhs = if precedence(r_idx, ir, bindings) < op.precedence() {
+                format!("({rhs})")
             } else {
-                r_idx.to_string(ir, elem_type, trace_segment)
+                rhs
             };
             format!("{lhs} * {rhs}")
         }
-        _ => panic!("unsupported operation"),
-    }
+        _ => {
+            return Err(CodegenError::invalid_constraint(
+                "expected an addition, subtraction or multiplication",
+            ))
+        }
+    };
+    Ok(result)
 }
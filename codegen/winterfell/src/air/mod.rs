@@ -1,13 +1,16 @@
 use super::{AirIR, Impl, Scope};
 use air_script_core::{AccessType, ConstantBinding, ConstantValueExpr, TraceAccess};
 use ir::{
-    constraints::{AlgebraicGraph, ConstraintDomain, Operation},
-    IntegrityConstraintDegree, NodeIndex, PeriodicColumn, Value,
+    constraints::{AlgebraicGraph, ConstraintDomain, ConstraintRoot, Operation},
+    CodegenError, ConstraintLocation, IntegrityConstraintDegree, NodeIndex, PeriodicColumn, Value,
 };
 
 mod constants;
 use constants::add_constants;
 
+mod columns;
+use columns::add_columns;
+
 mod public_inputs;
 use public_inputs::add_public_inputs_struct;
 
@@ -37,12 +40,17 @@ pub enum ElemType {
 
 /// Updates the provided scope with a new Air struct and Winterfell Air trait implementation
 /// which are equivalent the provided AirIR.
-pub(super) fn add_air(scope: &mut Scope, ir: &AirIR) {
+///
+/// Returns an error if any of the constraints cannot be expressed in a Winterfell Air.
+pub(super) fn add_air(scope: &mut Scope, ir: &AirIR) -> Result<(), CodegenError> {
     // add constant declarations. Check required to avoid adding extra line during codegen.
     if !ir.constants().is_empty() {
         add_constants(scope, ir);
     }
 
+    // add the column indices and a builder for the rows of the main trace.
+    add_columns(scope, ir);
+
     // add the Public Inputs struct and its base implementation.
     add_public_inputs_struct(scope, ir);
 
@@ -52,7 +60,7 @@ pub(super) fn add_air(scope: &mut Scope, ir: &AirIR) {
     add_air_struct(scope, ir, name);
 
     // add Winterfell Air trait implementation for the provided AirIR.
-    add_air_trait(scope, ir, name);
+    add_air_trait(scope, ir, name)
 }
 
 /// Updates the provided scope with a custom Air struct.
@@ -64,8 +72,8 @@ fn add_air_struct(scope: &mut Scope, ir: &AirIR, name: &str) {
         .field("context", "AirContext<Felt>");
 
     // add public inputs
-    for (pub_input, _pub_input_size) in ir.public_inputs() {
-        air_struct.field(pub_input, format!("Vec::<Felt>"));
+    for (pub_input, pub_input_size) in ir.public_inputs() {
+        air_struct.field(pub_input, format!("[Felt; {pub_input_size}]"));
     }
 
     // add the custom Air implementation block
@@ -81,7 +89,7 @@ fn add_air_struct(scope: &mut Scope, ir: &AirIR, name: &str) {
 
 /// Updates the provided scope with the custom Air struct and an Air trait implementation based on
 /// the provided AirIR.
-fn add_air_trait(scope: &mut Scope, ir: &AirIR, name: &str) {
+fn add_air_trait(scope: &mut Scope, ir: &AirIR, name: &str) -> Result<(), CodegenError> {
     // add the implementation block for the Air trait.
     let air_impl = scope
         .new_impl(name)
@@ -97,22 +105,22 @@ fn add_air_trait(scope: &mut Scope, ir: &AirIR, name: &str) {
     fn_context.line("&self.context");
 
     // add the method implementations required by the AIR trait.
-    add_fn_new(air_impl, ir);
+    add_fn_new(air_impl, ir)?;
 
     add_fn_get_periodic_column_values(air_impl, ir);
 
-    add_fn_get_assertions(air_impl, ir);
+    add_fn_get_assertions(air_impl, ir)?;
 
-    add_fn_get_aux_assertions(air_impl, ir);
+    add_fn_get_aux_assertions(air_impl, ir)?;
 
-    add_fn_evaluate_transition(air_impl, ir);
+    add_fn_evaluate_transition(air_impl, ir)?;
 
-    add_fn_evaluate_aux_transition(air_impl, ir);
+    add_fn_evaluate_aux_transition(air_impl, ir)
 }
 
 /// Adds an implementation of the "new" method to the referenced Air implementation based on the
 /// data in the provided AirIR.
-fn add_fn_new(impl_ref: &mut Impl, ir: &AirIR) {
+fn add_fn_new(impl_ref: &mut Impl, ir: &AirIR) -> Result<(), CodegenError> {
     // define the function.
     let new = impl_ref
         .new_fn("new")
@@ -122,10 +130,10 @@ fn add_fn_new(impl_ref: &mut Impl, ir: &AirIR) {
         .ret("Self");
 
     // define the integrity constraint degrees of the main trace `main_degrees`.
-    add_constraint_degrees(new, ir, 0, "main_degrees");
+    add_constraint_degrees(new, ir, 0, "main_degrees")?;
 
     // define the integrity constraint degrees of the aux trace `aux_degrees`.
-    add_constraint_degrees(new, ir, 1, "aux_degrees");
+    add_constraint_degrees(new, ir, 1, "aux_degrees")?;
 
     // define the number of main trace boundary constraints `num_main_assertions`.
     new.line(format!(
@@ -139,8 +147,10 @@ fn add_fn_new(impl_ref: &mut Impl, ir: &AirIR) {
         ir.num_boundary_constraints(1)
     ));
 
-    // define the context.
-    let context = "
+    // define the context, exempting the last rows which the transition constraints cannot be
+    // enforced on.
+    let context = format!(
+        "
 let context = AirContext::new_multi_segment(
     trace_info,
     main_degrees,
@@ -149,7 +159,9 @@ let context = AirContext::new_multi_segment(
     num_aux_assertions,
     options,
 )
-.set_num_transition_exemptions(1);";
+.set_num_transition_exemptions({});",
+        ir.num_transition_exemptions()
+    );
 
     new.line(context);
 
@@ -160,6 +172,7 @@ let context = AirContext::new_multi_segment(
     }
     // return initialized Self.
     new.line(format!("Self {{ context, {} }}", pub_inputs.join(", ")));
+    Ok(())
 }
 
 /// Iterates through the degrees of the integrity constraints in the IR, and appends a line of
@@ -169,11 +182,12 @@ fn add_constraint_degrees(
     ir: &AirIR,
     trace_segment: u8,
     decl_name: &str,
-) {
+) -> Result<(), CodegenError> {
     let degrees = ir
         .integrity_constraint_degrees(trace_segment)
         .iter()
         .map(|degree| degree.to_string(ir, ElemType::Ext, trace_segment))
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, _>>()?;
     func_body.line(format!("let {decl_name} = vec![{}];", degrees.join(", ")));
+    Ok(())
 }
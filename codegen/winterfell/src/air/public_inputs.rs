@@ -1,13 +1,19 @@
-use super::{AirIR, Scope};
+use super::{AirIR, Impl, Scope};
 
 /// Updates the provided scope with a public inputs.
+///
+/// Each public input is a fixed-size array of field elements, and the public inputs are laid out
+/// as elements in the order in which they are declared, which is also the order of the manifest.
 pub(super) fn add_public_inputs_struct(scope: &mut Scope, ir: &AirIR) {
     let name = "PublicInputs";
     // define the PublicInputs struct.
-    let pub_inputs_struct = scope.new_struct(name).vis("pub");
+    let pub_inputs_struct = scope
+        .new_struct(name)
+        .vis("pub")
+        .doc("The public inputs of the AIR, laid out as elements in the order of declaration.");
 
-    for (pub_input, _pub_input_size) in ir.public_inputs() {
-        pub_inputs_struct.field(pub_input, format!("Vec::<Felt>"));
+    for (pub_input, pub_input_size) in ir.public_inputs() {
+        pub_inputs_struct.field(pub_input, format!("[Felt; {pub_input_size}]"));
     }
 
     // add the public inputs implementation block
@@ -18,6 +24,10 @@ pub(super) fn add_public_inputs_struct(scope: &mut Scope, ir: &AirIR) {
         .iter()
         .map(|input| input.0.clone())
         .collect();
+    let num_elements: usize = ir.public_inputs().iter().map(|(_, size)| size).sum();
+
+    // add the number of elements of the public inputs
+    base_impl.associate_const("NUM_ELEMENTS", "usize", num_elements.to_string(), "pub");
 
     // add a constructor for public inputs
     let new_fn = base_impl
@@ -25,14 +35,49 @@ pub(super) fn add_public_inputs_struct(scope: &mut Scope, ir: &AirIR) {
         .vis("pub")
         .ret("Self")
         .line(format!("Self {{ {} }}", pub_inputs_values.join(", ")));
-    for (pub_input, _pub_input_size) in ir.public_inputs() {
-        new_fn.arg(pub_input, format!("Vec::<Felt>"));
+    for (pub_input, pub_input_size) in ir.public_inputs() {
+        new_fn.arg(pub_input, format!("[Felt; {pub_input_size}]"));
     }
 
+    add_fn_from_elements(base_impl, ir);
     add_serializable_impl(scope, &pub_inputs_values);
+    add_deserializable_impl(scope);
     add_to_elements_impl(scope, &pub_inputs_values)
 }
 
+/// Adds a fallible constructor which splits a slice of elements into the public inputs, and
+/// returns an error if the number of elements does not match the declared sizes.
+fn add_fn_from_elements(base_impl: &mut Impl, ir: &AirIR) {
+    let from_elements_fn = base_impl
+        .new_fn("from_elements")
+        .doc(
+            "Returns the public inputs laid out in the provided elements, or an error if the\n\
+            number of elements does not match the sizes of the public inputs.",
+        )
+        .vis("pub")
+        .arg("elements", "&[Felt]")
+        .ret("Result<Self, String>")
+        .line("if elements.len() != Self::NUM_ELEMENTS {")
+        .line("    return Err(format!(")
+        .line("        \"expected {} public input elements, found {}\",")
+        .line("        Self::NUM_ELEMENTS,")
+        .line("        elements.len()")
+        .line("    ));")
+        .line("}");
+
+    // split the elements, which cannot fail once their number has been checked.
+    from_elements_fn.line("Ok(Self {");
+    let mut offset = 0;
+    for (pub_input, pub_input_size) in ir.public_inputs() {
+        from_elements_fn.line(format!(
+            "    {pub_input}: elements[{offset}..{}].try_into().unwrap(),",
+            offset + pub_input_size
+        ));
+        offset += pub_input_size;
+    }
+    from_elements_fn.line("})");
+}
+
 /// Adds Serialization implementation for PublicInputs to the scope
 fn add_serializable_impl(scope: &mut Scope, pub_input_values: &Vec<String>) {
     let serializable_impl = scope.new_impl("PublicInputs").impl_trait("Serializable");
@@ -46,6 +91,22 @@ fn add_serializable_impl(scope: &mut Scope, pub_input_values: &Vec<String>) {
     }
 }
 
+/// Adds Deserialization implementation for PublicInputs to the scope, which reads the elements
+/// written by the Serialization implementation.
+fn add_deserializable_impl(scope: &mut Scope) {
+    scope
+        .new_impl("PublicInputs")
+        .impl_trait("Deserializable")
+        .new_fn("read_from")
+        .generic("R: ByteReader")
+        .arg("source", "&mut R")
+        .ret("Result<Self, DeserializationError>")
+        .line("let elements = (0..Self::NUM_ELEMENTS)")
+        .line("    .map(|_| Felt::read_from(source))")
+        .line("    .collect::<Result<Vec<_>, _>>()?;")
+        .line("Self::from_elements(&elements).map_err(DeserializationError::InvalidValue)");
+}
+
 fn add_to_elements_impl(scope: &mut Scope, pub_input_values: &Vec<String>) {
     let to_elements_impl = scope
         .new_impl("PublicInputs")
@@ -1,11 +1,17 @@
-use super::{AirIR, Codegen, ElemType, Impl};
+use super::{
+    graph::{node_to_string, shared_nodes, Bindings},
+    AirIR, CodegenError, ConstraintLocation, ElemType, Impl,
+};
 
 // HELPERS TO GENERATE THE WINTERFELL TRANSITION CONSTRAINT METHODS
 // ================================================================================================
 
 /// Adds an implementation of the "evaluate_transition" method to the referenced Air implementation
 /// based on the data in the provided AirIR.
-pub(super) fn add_fn_evaluate_transition(impl_ref: &mut Impl, ir: &AirIR) {
+pub(super) fn add_fn_evaluate_transition(
+    impl_ref: &mut Impl,
+    ir: &AirIR,
+) -> Result<(), CodegenError> {
     // define the function.
     let evaluate_transition = impl_ref
         .new_fn("evaluate_transition")
@@ -20,12 +26,22 @@ pub(super) fn add_fn_evaluate_transition(impl_ref: &mut Impl, ir: &AirIR) {
     evaluate_transition.line("let main_next = frame.next();");
 
     // output the constraints.
-    add_constraints(evaluate_transition, ir, 0);
+    add_constraints(evaluate_transition, ir, 0)
 }
 
 /// Adds an implementation of the "evaluate_aux_transition" method to the referenced Air implementation
 /// based on the data in the provided AirIR.
-pub(super) fn add_fn_evaluate_aux_transition(impl_ref: &mut Impl, ir: &AirIR) {
+pub(super) fn add_fn_evaluate_aux_transition(
+    impl_ref: &mut Impl,
+    ir: &AirIR,
+) -> Result<(), CodegenError> {
+    // name the periodic values argument only if it is used, to avoid an unused variable warning.
+    let periodic_values = if uses_periodic_columns(ir, 1) {
+        "periodic_values"
+    } else {
+        "_periodic_values"
+    };
+
     // define the function.
     let evaluate_aux_transition = impl_ref
         .new_fn("evaluate_aux_transition")
@@ -33,7 +49,7 @@ pub(super) fn add_fn_evaluate_aux_transition(impl_ref: &mut Impl, ir: &AirIR) {
         .arg_ref_self()
         .arg("main_frame", "&EvaluationFrame<F>")
         .arg("aux_frame", "&EvaluationFrame<E>")
-        .arg("_periodic_values", "&[F]")
+        .arg(periodic_values, "&[F]")
         .arg("aux_rand_elements", "&AuxTraceRandElements<E>")
         .arg("result", "&mut [E]")
         .bound("F", "FieldElement<BaseField = Felt>")
@@ -46,19 +62,55 @@ pub(super) fn add_fn_evaluate_aux_transition(impl_ref: &mut Impl, ir: &AirIR) {
     evaluate_aux_transition.line("let aux_next = aux_frame.next();");
 
     // output the constraints.
-    add_constraints(evaluate_aux_transition, ir, 1);
+    add_constraints(evaluate_aux_transition, ir, 1)
 }
 
 /// Iterates through the integrity constraints in the IR, and appends a line of generated code to
 /// the provided codegen function body for each constraint.
-fn add_constraints(func_body: &mut codegen::Function, ir: &AirIR, trace_segment: u8) {
-    for (idx, constraint) in ir.integrity_constraints(trace_segment).iter().enumerate() {
-        func_body.line(format!(
-            "result[{}] = {};",
-            idx,
-            constraint
-                .node_index()
-                .to_string(ir, ElemType::Ext, trace_segment)
-        ));
+///
+/// The operations which are used more than once by the constraints of the segment are evaluated
+/// once beforehand and bound to local variables `t0`, `t1`, etc., which the constraints reference
+/// instead of recomputing them.
+fn add_constraints(
+    func_body: &mut codegen::Function,
+    ir: &AirIR,
+    trace_segment: u8,
+) -> Result<(), CodegenError> {
+    let constraints = ir.integrity_constraints(trace_segment);
+
+    // bind the shared subexpressions to local variables.
+    let mut bindings = Bindings::default();
+    for (node_idx, idx) in shared_nodes(ir, constraints) {
+        let expr = node_to_string(&node_idx, ir, ElemType::Ext, trace_segment, &bindings)
+            .map_err(|err| err.in_constraint(ConstraintLocation::integrity(trace_segment, idx)))?;
+        let name = format!("t{}", bindings.len());
+        func_body.line(format!("let {name} = {expr};"));
+        bindings.insert(&node_idx, name);
+    }
+
+    for (idx, constraint) in constraints.iter().enumerate() {
+        let expr = node_to_string(
+            constraint.node_index(),
+            ir,
+            ElemType::Ext,
+            trace_segment,
+            &bindings,
+        )
+        .map_err(|err| err.in_constraint(ConstraintLocation::integrity(trace_segment, idx)))?;
+        func_body.line(format!("result[{idx}] = {expr};"));
     }
+    Ok(())
+}
+
+/// Returns true if any of the integrity constraints of the specified trace segment uses a periodic
+/// column, which is the case if its degree has cycles.
+fn uses_periodic_columns(ir: &AirIR, trace_segment: u8) -> bool {
+    ir.integrity_constraints(trace_segment)
+        .iter()
+        .any(|constraint| {
+            !ir.constraint_graph()
+                .degree(constraint.node_index())
+                .cycles()
+                .is_empty()
+        })
 }
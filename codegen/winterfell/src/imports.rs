@@ -15,7 +15,10 @@ pub(super) fn add_imports(scope: &mut Scope) {
     scope.import("winter_math", "ExtensionOf");
     scope.import("winter_math", "FieldElement");
     scope.import("winter_utils::collections", "Vec");
+    scope.import("winter_utils", "ByteReader");
     scope.import("winter_utils", "ByteWriter");
+    scope.import("winter_utils", "Deserializable");
+    scope.import("winter_utils", "DeserializationError");
     scope.import("winter_utils", "Serializable");
     scope.import("winter_math", "ToElements");
 }
@@ -1,5 +1,5 @@
 use codegen::{Impl, Scope};
-use ir::AirIR;
+use ir::{AirIR, CodegenError};
 
 mod imports;
 use imports::add_imports;
@@ -7,36 +7,61 @@ use imports::add_imports;
 mod air;
 use air::add_air;
 
+mod prover;
+use prover::add_prover;
+
 // GENERATE RUST CODE FOR WINTERFELL AIR
 // ================================================================================================
 
 /// CodeGenerator is used to generate a Rust implementation of the Winterfell STARK prover library's
 /// Air trait. The generated Air expresses the constraints specified by the AirIR used to build the
 /// CodeGenerator.
-pub struct CodeGenerator {
-    scope: Scope,
+pub struct CodeGenerator<'a> {
+    ir: &'a AirIR,
 }
 
-impl CodeGenerator {
+impl<'a> CodeGenerator<'a> {
     // --- CONSTRUCTOR ----------------------------------------------------------------------------
 
-    /// Builds a new Rust scope that represents a Winterfell Air trait implementation for the
-    /// provided AirIR.
-    pub fn new(ir: &AirIR) -> Self {
+    /// Returns a new [CodeGenerator] for the provided AirIR.
+    pub fn new(ir: &'a AirIR) -> Self {
+        Self { ir }
+    }
+
+    /// Returns a string of Rust code containing a Winterfell Air implementation for the AirIR with
+    /// which this [CodeGenerator] was instantiated.
+    ///
+    /// # Errors
+    /// Returns an error if the AirIR uses a feature which cannot be expressed in a Winterfell Air.
+    pub fn generate(&self) -> Result<String, CodegenError> {
         let mut scope = Scope::new();
 
         // add winterfell imports.
         add_imports(&mut scope);
 
-        // add an Air struct and Winterfell Air trait implementation for the provided AirIR.
-        add_air(&mut scope, ir);
+        // add an Air struct and Winterfell Air trait implementation for the AirIR.
+        add_air(&mut scope, self.ir)?;
 
-        Self { scope }
+        Ok(scope.to_string())
     }
 
-    /// Returns a string of Rust code containing a Winterfell Air implementation for the AirIR with
-    /// which this [CodeGenerator] was instantiated.
-    pub fn generate(&self) -> String {
-        self.scope.to_string()
+    /// Returns a string of Rust code for the crate root of a binary which proves and verifies an
+    /// execution trace of the AirIR with which this [CodeGenerator] was instantiated.
+    ///
+    /// The binary declares the module `air_module`, which must contain the Air implementation
+    /// returned by [CodeGenerator::generate]. It contains a Winterfell `Prover` implementation, a
+    /// constructor of the `PublicInputs` from a flat vector of public values, a loader for trace
+    /// tables with one row per line, and a `main` function which proves and verifies a trace.
+    ///
+    /// # Errors
+    /// Returns an error if the AirIR has an auxiliary trace segment, which the generated prover
+    /// does not support.
+    pub fn generate_prover(&self, air_module: &str) -> Result<String, CodegenError> {
+        let mut scope = Scope::new();
+
+        // add a Winterfell prover and a main function proving and verifying a trace.
+        add_prover(&mut scope, self.ir, air_module)?;
+
+        Ok(scope.to_string())
     }
 }
@@ -0,0 +1,208 @@
+use super::{AirIR, Scope};
+use ir::CodegenError;
+
+// HELPERS TO GENERATE A WINTERFELL PROVER FOR THE AIR
+// ================================================================================================
+
+/// Updates the provided scope with the crate root of a binary which proves and verifies an
+/// execution trace of the AIR with Winterfell. The binary declares the module `air_module`, which
+/// is expected to contain the Air implementation generated for the same AirIR.
+///
+/// Returns an error if the AirIR has an auxiliary trace segment, since the trace table loaded by
+/// the generated binary only holds the main trace.
+pub(super) fn add_prover(
+    scope: &mut Scope,
+    ir: &AirIR,
+    air_module: &str,
+) -> Result<(), CodegenError> {
+    if ir.trace_segment_widths().len() > 1 {
+        return Err(CodegenError::unsupported_feature(
+            "auxiliary trace segments in the generated prover",
+        ));
+    }
+
+    let name = ir.air_name();
+    add_prover_imports(scope, air_module, name);
+    scope.raw(format!("mod {air_module};"));
+
+    add_fn_build_public_inputs(scope);
+    add_fn_parse_values(scope);
+    add_fn_load_trace(scope);
+    add_prover_struct(scope, name);
+    add_fn_main(scope, name);
+    Ok(())
+}
+
+/// Adds the imports required by the prover to the provided scope.
+fn add_prover_imports(scope: &mut Scope, air_module: &str, name: &str) {
+    scope.import("std", "env");
+    scope.import("std", "fs");
+    scope.import("std::marker", "PhantomData");
+    scope.import("winter_air", "FieldExtension");
+    scope.import("winter_air", "ProofOptions as WinterProofOptions");
+    scope.import("winter_math::fields::f64", "BaseElement as Felt");
+    scope.import("winter_prover::crypto::hashers", "Blake3_192");
+    scope.import("winter_prover::crypto", "DefaultRandomCoin");
+    scope.import("winter_prover::crypto", "ElementHasher");
+    scope.import("winter_prover", "Prover");
+    scope.import("winter_prover", "TraceTable");
+    scope.import("winter_verifier", "verify");
+    scope.import(air_module, name);
+    scope.import(air_module, "PublicInputs");
+    scope.import(&format!("{air_module}::columns"), "MAIN_WIDTH");
+}
+
+/// Adds a function which builds the PublicInputs of the AIR from a flat vector of public values,
+/// which is split according to the sizes of the public inputs.
+fn add_fn_build_public_inputs(scope: &mut Scope) {
+    scope
+        .new_fn("build_public_inputs")
+        .doc(
+            "Builds the public inputs of the AIR from their values, laid out one after the other \
+            in the\norder in which they are declared.",
+        )
+        .vis("pub")
+        .arg("values", "&[Felt]")
+        .ret("PublicInputs")
+        .line("PublicInputs::from_elements(values)")
+        .line("    .unwrap_or_else(|err| panic!(\"invalid public values: {err}\"))");
+}
+
+/// Adds a function which parses whitespace-separated field elements.
+fn add_fn_parse_values(scope: &mut Scope) {
+    scope
+        .new_fn("parse_values")
+        .doc("Parses a sequence of whitespace-separated field elements.")
+        .vis("pub")
+        .arg("data", "&str")
+        .ret("Vec<Felt>")
+        .line("data.split_whitespace()")
+        .line("    .map(|word| Felt::new(word.parse().expect(\"invalid field element\")))")
+        .line("    .collect()");
+}
+
+/// Adds a function which loads the main trace from a table with one row per line.
+fn add_fn_load_trace(scope: &mut Scope) {
+    scope
+        .new_fn("load_trace")
+        .doc(
+            "Loads the main trace from a table of field elements with one row of the trace per \
+            line.",
+        )
+        .vis("pub")
+        .arg("data", "&str")
+        .ret("TraceTable<Felt>")
+        .line("let rows = data")
+        .line("    .lines()")
+        .line("    .filter(|line| !line.trim().is_empty())")
+        .line("    .map(parse_values)")
+        .line("    .collect::<Vec<_>>();")
+        .line("assert!(")
+        .line("    rows.len().is_power_of_two(),")
+        .line("    \"the trace length must be a power of two, found {}\",")
+        .line("    rows.len()")
+        .line(");")
+        .line("")
+        .line("let mut columns = vec![Vec::with_capacity(rows.len()); MAIN_WIDTH];")
+        .line("for (i, row) in rows.iter().enumerate() {")
+        .line("    assert_eq!(")
+        .line("        row.len(),")
+        .line("        MAIN_WIDTH,")
+        .line("        \"row {i} of the trace has {} columns, expected {MAIN_WIDTH}\",")
+        .line("        row.len()")
+        .line("    );")
+        .line("    for (column, value) in columns.iter_mut().zip(row) {")
+        .line("        column.push(*value);")
+        .line("    }")
+        .line("}")
+        .line("TraceTable::init(columns)");
+}
+
+/// Adds a struct implementing Winterfell's Prover trait for the AIR, which returns the public
+/// values it was built with as the public inputs of the proof.
+fn add_prover_struct(scope: &mut Scope, name: &str) {
+    let prover_name = format!("{name}Prover");
+    scope
+        .new_struct(&prover_name)
+        .doc(&format!("A Winterfell prover for the {name} AIR."))
+        .vis("pub")
+        .generic("H")
+        .bound("H", "ElementHasher<BaseField = Felt>")
+        .field("options", "WinterProofOptions")
+        .field("public_values", "Vec<Felt>")
+        .field("_hasher", "PhantomData<H>");
+
+    scope
+        .new_impl(&prover_name)
+        .generic("H")
+        .target_generic("H")
+        .bound("H", "ElementHasher<BaseField = Felt>")
+        .new_fn("new")
+        .doc("Returns a new prover with the provided options and public values.")
+        .vis("pub")
+        .arg("options", "WinterProofOptions")
+        .arg("public_values", "Vec<Felt>")
+        .ret("Self")
+        .line("Self {")
+        .line("    options,")
+        .line("    public_values,")
+        .line("    _hasher: PhantomData,")
+        .line("}");
+
+    let prover_impl = scope
+        .new_impl(&prover_name)
+        .generic("H")
+        .target_generic("H")
+        .impl_trait("Prover")
+        .bound("H", "ElementHasher<BaseField = Felt>")
+        .associate_type("BaseField", "Felt")
+        .associate_type("Air", name)
+        .associate_type("Trace", "TraceTable<Felt>")
+        .associate_type("HashFn", "H")
+        .associate_type("RandomCoin", "DefaultRandomCoin<H>");
+    prover_impl
+        .new_fn("get_pub_inputs")
+        .arg_ref_self()
+        .arg("_trace", "&Self::Trace")
+        .ret("PublicInputs")
+        .line("build_public_inputs(&self.public_values)");
+    prover_impl
+        .new_fn("options")
+        .arg_ref_self()
+        .ret("&WinterProofOptions")
+        .line("&self.options");
+}
+
+/// Adds a main function which proves the trace read from the file passed as the first argument,
+/// with the public values read from the files passed as the remaining arguments, and verifies
+/// the proof.
+fn add_fn_main(scope: &mut Scope, name: &str) {
+    scope
+        .new_fn("main")
+        .line("let args = env::args().collect::<Vec<_>>();")
+        .line("assert!(")
+        .line("    args.len() >= 2,")
+        .line("    \"usage: {} <trace file> [public inputs file]...\",")
+        .line("    args[0]")
+        .line(");")
+        .line("let read = |path: &String| {")
+        .line("    fs::read_to_string(path).unwrap_or_else(|err| panic!(\"failed to read {path}: {err}\"))")
+        .line("};")
+        .line("let trace = load_trace(&read(&args[1]));")
+        .line("let public_values = args[2..]")
+        .line("    .iter()")
+        .line("    .flat_map(|path| parse_values(&read(path)))")
+        .line("    .collect::<Vec<_>>();")
+        .line("let pub_inputs = build_public_inputs(&public_values);")
+        .line("")
+        .line("let options = WinterProofOptions::new(27, 8, 16, FieldExtension::None, 8, 255);")
+        .line(format!(
+            "let prover = {name}Prover::<Blake3_192<Felt>>::new(options, public_values);"
+        ))
+        .line("let proof = prover.prove(trace).expect(\"failed to generate the proof\");")
+        .line(format!(
+            "verify::<{name}, Blake3_192<Felt>, DefaultRandomCoin<Blake3_192<Felt>>>(proof, pub_inputs)"
+        ))
+        .line("    .expect(\"failed to verify the proof\");")
+        .line("println!(\"The proof was verified\");");
+}
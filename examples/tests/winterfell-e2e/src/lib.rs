@@ -0,0 +1,219 @@
+//! Helpers for proving and verifying traces of the AIRs generated for Winterfell.
+//!
+//! The tests of this crate include the Winterfell code generated for each test fixture of the
+//! compiler with `air_script::include_air!`, build a small trace for it, and check that the trace
+//! can be proven and the proof verified, and that a mutated trace is rejected.
+
+use std::{
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+};
+use winter_air::{Air, EvaluationFrame, FieldExtension, ProofOptions, TraceLayout};
+use winter_math::FieldElement;
+use winter_prover::{
+    crypto::{hashers::Blake3_192, DefaultRandomCoin},
+    ColMatrix, Prover, Trace, TraceTable,
+};
+use winter_verifier::verify;
+
+pub use winter_math::fields::f64::BaseElement as Felt;
+
+type Hasher = Blake3_192<Felt>;
+type RandomCoin = DefaultRandomCoin<Hasher>;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The length of the traces built by the tests, which is a multiple of the cycles of all the
+/// periodic columns used by the fixtures.
+pub const TRACE_LENGTH: usize = 32;
+
+// TRACE BUILDERS
+// ================================================================================================
+
+/// Returns `n` pseudo-random values generated from the specified seed, so that the traces are the
+/// same on every run.
+pub fn rand_values(seed: u64, n: usize) -> Vec<u64> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            state >> 11
+        })
+        .collect()
+}
+
+/// Returns `n` pseudo-random field elements generated from the specified seed.
+pub fn rand_felts(seed: u64, n: usize) -> Vec<Felt> {
+    rand_values(seed, n).into_iter().map(Felt::new).collect()
+}
+
+/// Returns the columns of a trace from its rows.
+pub fn trace_columns<const W: usize>(rows: &[[Felt; W]]) -> Vec<Vec<Felt>> {
+    (0..W)
+        .map(|column| rows.iter().map(|row| row[column]).collect())
+        .collect()
+}
+
+/// Returns a trace with a main segment only, made of the specified columns.
+pub fn main_trace(columns: Vec<Vec<Felt>>) -> TraceTable<Felt> {
+    TraceTable::init(columns)
+}
+
+/// Builds the columns of the auxiliary segment of a trace from its main segment and the random
+/// elements drawn by the prover.
+pub trait AuxColumns {
+    fn build<E: FieldElement<BaseField = Felt>>(main: &[Vec<Felt>], rand: &[E]) -> Vec<Vec<E>>;
+}
+
+/// A trace with a main segment and a single auxiliary segment, the columns of which are built by
+/// `A` once the prover has drawn the random elements.
+pub struct AuxTraceTable<A> {
+    layout: TraceLayout,
+    main: ColMatrix<Felt>,
+    columns: Vec<Vec<Felt>>,
+    aux_mutation: Option<(usize, usize)>,
+    _aux: PhantomData<A>,
+}
+
+impl<A: AuxColumns> AuxTraceTable<A> {
+    /// Returns a trace made of the specified main columns, with an auxiliary segment of the
+    /// specified width which uses the specified number of random elements.
+    ///
+    /// At least one random element is drawn, as Winterfell requires it for every auxiliary
+    /// segment, even when the AIR does not use any.
+    pub fn new(main: Vec<Vec<Felt>>, aux_width: usize, num_rand_values: usize) -> Self {
+        let layout = TraceLayout::new(main.len(), [aux_width], [num_rand_values.max(1)]);
+        Self {
+            layout,
+            main: ColMatrix::new(main.clone()),
+            columns: main,
+            aux_mutation: None,
+            _aux: PhantomData,
+        }
+    }
+
+    /// Adds one to the value of the specified column of the auxiliary segment at the specified
+    /// row, once the segment is built.
+    pub fn with_aux_mutation(mut self, column: usize, row: usize) -> Self {
+        self.aux_mutation = Some((column, row));
+        self
+    }
+}
+
+impl<A: AuxColumns> Trace for AuxTraceTable<A> {
+    type BaseField = Felt;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.main.num_rows()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &[]
+    }
+
+    fn main_segment(&self) -> &ColMatrix<Felt> {
+        &self.main
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> Option<ColMatrix<E>>
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        // the trace has a single auxiliary segment.
+        if !aux_segments.is_empty() {
+            return None;
+        }
+
+        let mut columns = A::build(&self.columns, rand_elements);
+        if let Some((column, row)) = self.aux_mutation {
+            columns[column][row] += E::ONE;
+        }
+        Some(ColMatrix::new(columns))
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Felt>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.main.read_row_into(row_idx, frame.current_mut());
+        self.main.read_row_into(next_row_idx, frame.next_mut());
+    }
+}
+
+// PROVER
+// ================================================================================================
+
+/// A Winterfell prover for the AIR `A`, which proves traces of type `T` against the public inputs
+/// returned by `P`.
+struct AirProver<A, T, P> {
+    options: ProofOptions,
+    public_inputs: P,
+    _air: PhantomData<(A, T)>,
+}
+
+impl<A, T, P> Prover for AirProver<A, T, P>
+where
+    A: Air<BaseField = Felt>,
+    T: Trace<BaseField = Felt>,
+    P: Fn() -> A::PublicInputs,
+{
+    type BaseField = Felt;
+    type Air = A;
+    type Trace = T;
+    type HashFn = Hasher;
+    type RandomCoin = RandomCoin;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> A::PublicInputs {
+        (self.public_inputs)()
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+/// Proves the specified trace against the AIR `A` and the public inputs returned by
+/// `public_inputs`, and verifies the proof.
+///
+/// Returns an error describing the failure if the proof cannot be generated or verified. This
+/// includes the panics of the prover, which panics when the AIR cannot be instantiated for the
+/// trace, and, when debug assertions are enabled, when the trace does not satisfy the constraints
+/// of the AIR.
+pub fn prove_and_verify<A, T>(
+    trace: T,
+    public_inputs: impl Fn() -> A::PublicInputs,
+) -> Result<(), String>
+where
+    A: Air<BaseField = Felt>,
+    T: Trace<BaseField = Felt>,
+{
+    let options = ProofOptions::new(27, 8, 0, FieldExtension::Quadratic, 4, 31);
+    let prover = AirProver::<A, T, _> {
+        options,
+        public_inputs: &public_inputs,
+        _air: PhantomData,
+    };
+
+    let proof = panic::catch_unwind(AssertUnwindSafe(|| prover.prove(trace)))
+        .map_err(|payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            format!("the prover panicked: {message}")
+        })?
+        .map_err(|err| format!("failed to generate the proof: {err:?}"))?;
+
+    verify::<A, Hasher, RandomCoin>(proof, public_inputs())
+        .map_err(|err| format!("failed to verify the proof: {err:?}"))
+}
@@ -0,0 +1,795 @@
+use winter_math::FieldElement;
+use winterfell_e2e::{
+    main_trace, prove_and_verify, rand_felts, rand_values, trace_columns, AuxColumns,
+    AuxTraceTable, Felt, TRACE_LENGTH,
+};
+
+const N: usize = TRACE_LENGTH;
+
+// AIRS
+// ================================================================================================
+
+mod aux_trace {
+    air_script::include_air!("../../../air-script/tests/aux_trace/aux_trace.air");
+}
+
+mod binary {
+    air_script::include_air!("../../../air-script/tests/binary/binary.air");
+}
+
+mod bitwise {
+    air_script::include_air!("../../../air-script/tests/bitwise/bitwise.air");
+}
+
+mod constants {
+    air_script::include_air!("../../../air-script/tests/constants/constants.air");
+}
+
+mod indexed_trace_access {
+    air_script::include_air!(
+        "../../../air-script/tests/indexed_trace_access/indexed_trace_access.air"
+    );
+}
+
+mod list_comprehension {
+    air_script::include_air!("../../../air-script/tests/list_comprehension/list_comprehension.air");
+}
+
+mod list_folding {
+    air_script::include_air!("../../../air-script/tests/list_folding/list_folding.air");
+}
+
+mod periodic_aux {
+    air_script::include_air!("../../../air-script/tests/periodic_aux/periodic_aux.air");
+}
+
+mod periodic_columns {
+    air_script::include_air!("../../../air-script/tests/periodic_columns/periodic_columns.air");
+}
+
+mod pub_inputs {
+    air_script::include_air!("../../../air-script/tests/pub_inputs/pub_inputs.air");
+}
+
+mod random_values_bindings {
+    air_script::include_air!("../../../air-script/tests/random_values/random_values_bindings.air");
+}
+
+mod random_values_simple {
+    air_script::include_air!("../../../air-script/tests/random_values/random_values_simple.air");
+}
+
+mod shared_subexpressions {
+    air_script::include_air!(
+        "../../../air-script/tests/shared_subexpressions/shared_subexpressions.air"
+    );
+}
+
+mod system {
+    air_script::include_air!("../../../air-script/tests/system/system.air");
+}
+
+mod trace_col_groups {
+    air_script::include_air!("../../../air-script/tests/trace_col_groups/trace_col_groups.air");
+}
+
+mod transition_exemptions {
+    air_script::include_air!(
+        "../../../air-script/tests/transition_exemptions/transition_exemptions.air"
+    );
+}
+
+mod variables {
+    air_script::include_air!("../../../air-script/tests/variables/variables.air");
+}
+
+// HELPERS
+// ================================================================================================
+
+fn felt(value: u64) -> Felt {
+    Felt::new(value)
+}
+
+/// Returns `n` pseudo-random bits generated from the specified seed.
+fn bits(seed: u64, n: usize) -> Vec<Felt> {
+    rand_values(seed, n)
+        .into_iter()
+        .map(|value| felt(value & 1))
+        .collect()
+}
+
+/// Returns pseudo-random values for a public input of size `M`.
+fn public_input<const M: usize>(seed: u64) -> [Felt; M] {
+    rand_felts(seed, M).try_into().unwrap()
+}
+
+/// Lifts the specified columns of base field elements to the field `E`.
+fn lift<E: FieldElement<BaseField = Felt>>(columns: Vec<Vec<Felt>>) -> Vec<Vec<E>> {
+    columns
+        .into_iter()
+        .map(|column| column.into_iter().map(E::from).collect())
+        .collect()
+}
+
+/// Returns a column which starts with the specified value, and in which each following value is
+/// computed by `next` from the index and the value of the previous row.
+fn accumulate<E: FieldElement>(first: E, next: impl Fn(usize, E) -> E) -> Vec<E> {
+    let mut column = Vec::with_capacity(N);
+    column.push(first);
+    for i in 0..N - 1 {
+        column.push(next(i, column[i]));
+    }
+    column
+}
+
+// SATISFIABLE AIRS
+// ================================================================================================
+
+#[test]
+fn binary() {
+    use binary::{columns, BinaryAir, MainTraceRow, PublicInputs};
+
+    let (a, b) = (bits(1, N), bits(2, N));
+    let rows = (0..N)
+        .map(|i| {
+            let a = if i == 0 { Felt::ZERO } else { a[i] };
+            *MainTraceRow::new().set_a(a).set_b(b[i]).values()
+        })
+        .collect::<Vec<_>>();
+    let public_inputs = || PublicInputs::new(public_input(3));
+
+    let mut main = trace_columns(&rows);
+    prove_and_verify::<BinaryAir, _>(main_trace(main.clone()), public_inputs).unwrap();
+
+    // b is not binary.
+    main[columns::B][5] = felt(2);
+    assert!(prove_and_verify::<BinaryAir, _>(main_trace(main), public_inputs).is_err());
+}
+
+#[test]
+fn bitwise() {
+    use bitwise::{columns, BitwiseAir, MainTraceRow, PublicInputs};
+
+    // each 8-row cycle computes the AND (s = 0) or the XOR (s = 1) of the values aggregated from
+    // 4 bits of a and b per row.
+    let selectors = [0, 1, 1, 0];
+    let nibbles = rand_values(4, N);
+    let (mut a, mut b, mut zp) = (0, 0, 0);
+    let mut rows = Vec::with_capacity(N);
+    for i in 0..N {
+        let s = selectors[i / 8];
+        let (x, y) = (nibbles[i] & 15, (nibbles[i] >> 4) & 15);
+        if i % 8 == 0 {
+            (a, b, zp) = (x, y, 0);
+        }
+        let z = zp * 16 + if s == 0 { x & y } else { x ^ y };
+
+        let mut row = MainTraceRow::new();
+        row.set_s(felt(s))
+            .set_a(felt(a))
+            .set_b(felt(b))
+            .set_a0(felt(x & 1))
+            .set_a1(felt((x >> 1) & 1))
+            .set_a2(felt((x >> 2) & 1))
+            .set_a3(felt((x >> 3) & 1))
+            .set_b0(felt(y & 1))
+            .set_b1(felt((y >> 1) & 1))
+            .set_b2(felt((y >> 2) & 1))
+            .set_b3(felt((y >> 3) & 1))
+            .set_zp(felt(zp))
+            .set_z(felt(z));
+        rows.push(*row.values());
+
+        (a, b, zp) = (a * 16 + x, b * 16 + y, z);
+    }
+    let public_inputs = || PublicInputs::new(public_input(5));
+
+    let mut main = trace_columns(&rows);
+    prove_and_verify::<BitwiseAir, _>(main_trace(main.clone()), public_inputs).unwrap();
+
+    // the selector changes within a cycle.
+    main[columns::S][3] = Felt::ONE;
+    assert!(prove_and_verify::<BitwiseAir, _>(main_trace(main), public_inputs).is_err());
+}
+
+/// Returns the columns a, b and c of a trace satisfying the constraints of the periodic columns
+/// fixtures: a is constant within each 8-row cycle and starts at 0, and c = -b at the first row
+/// of each 4-row cycle.
+fn periodic_main(seed: u64) -> [Vec<Felt>; 3] {
+    let values = rand_felts(seed, N / 8);
+    let a = (0..N)
+        .map(|i| if i < 8 { Felt::ZERO } else { values[i / 8] })
+        .collect();
+    let b = rand_felts(seed + 1, N);
+    let c = rand_felts(seed + 2, N)
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| if i % 4 == 0 { -b[i] } else { c })
+        .collect();
+    [a, b, c]
+}
+
+#[test]
+fn periodic_columns() {
+    use periodic_columns::{columns, MainTraceRow, PeriodicColumnsAir, PublicInputs};
+
+    let [a, b, c] = periodic_main(70);
+    let rows = (0..N)
+        .map(|i| {
+            *MainTraceRow::new()
+                .set_a(a[i])
+                .set_b(b[i])
+                .set_c(c[i])
+                .values()
+        })
+        .collect::<Vec<_>>();
+    let public_inputs = || PublicInputs::new(public_input(73));
+
+    let mut main = trace_columns(&rows);
+    prove_and_verify::<PeriodicColumnsAir, _>(main_trace(main.clone()), public_inputs).unwrap();
+
+    // c = -b does not hold at the first row of a 4-row cycle.
+    main[columns::C][12] += Felt::ONE;
+    assert!(prove_and_verify::<PeriodicColumnsAir, _>(main_trace(main), public_inputs).is_err());
+}
+
+struct PeriodicAux;
+
+impl AuxColumns for PeriodicAux {
+    fn build<E: FieldElement<BaseField = Felt>>(main: &[Vec<Felt>], rand: &[E]) -> Vec<Vec<E>> {
+        use periodic_aux::columns::{A, B};
+
+        let p = accumulate(E::ONE, |i, p| {
+            let k0 = if i % 4 == 0 { main[A][i] } else { Felt::ZERO };
+            p * (E::from(k0) + rand[0])
+        });
+        let q = accumulate(E::ONE, |i, q| {
+            if i % 8 == 7 {
+                q
+            } else {
+                q * (E::from(main[B][i]) + rand[1])
+            }
+        });
+        vec![p, q]
+    }
+}
+
+#[test]
+fn periodic_aux() {
+    use periodic_aux::{columns, MainTraceRow, PeriodicAuxAir, PublicInputs};
+
+    let [a, b, c] = periodic_main(80);
+    let rows = (0..N)
+        .map(|i| {
+            *MainTraceRow::new()
+                .set_a(a[i])
+                .set_b(b[i])
+                .set_c(c[i])
+                .values()
+        })
+        .collect::<Vec<_>>();
+    let public_inputs = || PublicInputs::new(public_input(83));
+    let trace = |main| AuxTraceTable::<PeriodicAux>::new(main, columns::AUX_WIDTH, 2);
+
+    let mut main = trace_columns(&rows);
+    prove_and_verify::<PeriodicAuxAir, _>(trace(main.clone()), public_inputs).unwrap();
+
+    // q does not accumulate b at a row selected by k1.
+    let mutated = trace(main.clone()).with_aux_mutation(1, 10);
+    assert!(prove_and_verify::<PeriodicAuxAir, _>(mutated, public_inputs).is_err());
+
+    // a changes within an 8-row cycle.
+    main[columns::A][10] += Felt::ONE;
+    assert!(prove_and_verify::<PeriodicAuxAir, _>(trace(main), public_inputs).is_err());
+}
+
+#[test]
+fn pub_inputs() {
+    use pub_inputs::{columns, MainTraceRow, PubInputsAir, PublicInputs};
+
+    let (b, c, d) = (rand_felts(90, N), rand_felts(91, N), rand_felts(92, N));
+    let a = accumulate(felt(93), |i, _| b[i] + c[i]);
+    let rows = (0..N)
+        .map(|i| {
+            *MainTraceRow::new()
+                .set_a(a[i])
+                .set_b(b[i])
+                .set_c(c[i])
+                .set_d(d[i])
+                .values()
+        })
+        .collect::<Vec<_>>();
+
+    // the stack inputs are the first row, and the first stack outputs are the last row.
+    let stack_inputs = rows[0];
+    let mut stack_outputs = public_input::<20>(94);
+    stack_outputs[..4].copy_from_slice(&rows[N - 1]);
+    let public_inputs = || {
+        PublicInputs::new(
+            public_input(95),
+            stack_inputs,
+            stack_outputs,
+            public_input(96),
+        )
+    };
+
+    let mut main = trace_columns(&rows);
+    prove_and_verify::<PubInputsAir, _>(main_trace(main.clone()), public_inputs).unwrap();
+
+    // the last row of the trace does not match other stack outputs.
+    let mut other_outputs = stack_outputs;
+    other_outputs[3] += Felt::ONE;
+    let other_inputs = || {
+        PublicInputs::new(
+            public_input(95),
+            stack_inputs,
+            other_outputs,
+            public_input(96),
+        )
+    };
+    assert!(prove_and_verify::<PubInputsAir, _>(main_trace(main.clone()), other_inputs).is_err());
+
+    // the next value of a is not b + c.
+    main[columns::A][7] += Felt::ONE;
+    assert!(prove_and_verify::<PubInputsAir, _>(main_trace(main), public_inputs).is_err());
+}
+
+struct SharedSubexpressionsAux;
+
+impl AuxColumns for SharedSubexpressionsAux {
+    fn build<E: FieldElement<BaseField = Felt>>(main: &[Vec<Felt>], rand: &[E]) -> Vec<Vec<E>> {
+        use shared_subexpressions::columns::{A, B, C};
+
+        let factor = |i: usize| E::from(main[A][i] + main[B][i]) + rand[0];
+        let p = accumulate(E::ONE, |i, p| p * factor(i));
+        let q = accumulate(E::ONE, |i, q| {
+            q * factor(i) * (E::from(main[C][i]) + rand[1])
+        });
+        vec![p, q]
+    }
+}
+
+#[test]
+fn shared_subexpressions() {
+    use shared_subexpressions::{columns, MainTraceRow, PublicInputs, SharedSubexpressionsAir};
+
+    // c = a + b when s = 1, and c = 1 when s = 0, so that (a + b) * c = a + b.
+    let (s, b) = (bits(100, N), rand_felts(101, N));
+    let mut rows = Vec::with_capacity(N);
+    let mut a = Felt::ZERO;
+    for i in 0..N {
+        let total = a + b[i];
+        let c = if s[i] == Felt::ONE { total } else { Felt::ONE };
+        rows.push(
+            *MainTraceRow::new()
+                .set_s(s[i])
+                .set_a(a)
+                .set_b(b[i])
+                .set_c(c)
+                .values(),
+        );
+        a = (total * c).square();
+    }
+    let public_inputs = || PublicInputs::new(public_input(102));
+    let trace = |main| AuxTraceTable::<SharedSubexpressionsAux>::new(main, columns::AUX_WIDTH, 2);
+
+    let mut main = trace_columns(&rows);
+    prove_and_verify::<SharedSubexpressionsAir, _>(trace(main.clone()), public_inputs).unwrap();
+
+    // p does not accumulate the shared factor.
+    let mutated = trace(main.clone()).with_aux_mutation(0, 11);
+    assert!(prove_and_verify::<SharedSubexpressionsAir, _>(mutated, public_inputs).is_err());
+
+    // c matches neither a + b nor 1.
+    main[columns::C][11] += felt(2);
+    assert!(prove_and_verify::<SharedSubexpressionsAir, _>(trace(main), public_inputs).is_err());
+}
+
+#[test]
+fn system() {
+    use system::{columns, MainTraceRow, PublicInputs, SystemAir};
+
+    let (fmp, ctx) = (rand_felts(110, N), rand_felts(111, N));
+    let rows = (0..N)
+        .map(|i| {
+            *MainTraceRow::new()
+                .set_clk(felt(i as u64))
+                .set_fmp(fmp[i])
+                .set_ctx(ctx[i])
+                .values()
+        })
+        .collect::<Vec<_>>();
+    let public_inputs = || PublicInputs::new(public_input(112));
+
+    let mut main = trace_columns(&rows);
+    prove_and_verify::<SystemAir, _>(main_trace(main.clone()), public_inputs).unwrap();
+
+    // the clock skips a step.
+    main[columns::CLK][13] += Felt::ONE;
+    assert!(prove_and_verify::<SystemAir, _>(main_trace(main), public_inputs).is_err());
+}
+
+#[test]
+fn transition_exemptions() {
+    use transition_exemptions::{columns, MainTraceRow, PaddedAir, PublicInputs};
+
+    // the transition constraints are not enforced over the last 3 rows, so the trace ends with
+    // the last step of the computation followed by 2 rows of padding, which do not follow it.
+    let last_step = N - 3;
+    let a = bits(130, N);
+    let rows = (0..N)
+        .map(|i| {
+            let (clk, a) = match i {
+                i if i < last_step => (felt(i as u64), a[i]),
+                i if i == last_step => (felt(i as u64), Felt::ONE),
+                _ => (Felt::ZERO, Felt::ZERO),
+            };
+            *MainTraceRow::new().set_clk(clk).set_a(a).values()
+        })
+        .collect::<Vec<_>>();
+    let public_inputs = || PublicInputs::new(public_input(131));
+
+    let mut main = trace_columns(&rows);
+    prove_and_verify::<PaddedAir, _>(main_trace(main.clone()), public_inputs).unwrap();
+
+    // the clock skips a step before the padding.
+    main[columns::CLK][last_step] += Felt::ONE;
+    assert!(prove_and_verify::<PaddedAir, _>(main_trace(main), public_inputs).is_err());
+}
+
+// UNSATISFIABLE AIRS
+// ================================================================================================
+// The constraints of the following fixtures contradict each other, so no trace satisfies them.
+// Each test builds a trace which satisfies all of the constraints but the contradicting ones, and
+// checks that it is rejected.
+
+struct AuxTraceAux;
+
+impl AuxColumns for AuxTraceAux {
+    fn build<E: FieldElement<BaseField = Felt>>(main: &[Vec<Felt>], rand: &[E]) -> Vec<Vec<E>> {
+        use aux_trace::columns::{A, B, C};
+
+        let p0 = accumulate(E::ONE, |i, p0| {
+            p0 * (E::from(main[A][i]) + rand[0] + E::from(main[B][i]) + rand[1])
+        });
+        let p1 = accumulate(rand[0], |i, p1| p1 / (E::from(main[C][i]) + rand[0]));
+        vec![p0, p1]
+    }
+}
+
+/// p0 and p1 start at 1 and at a random value, and accumulate products of values offset by
+/// random values, so they cannot both end at 1.
+#[test]
+fn aux_trace_is_unsatisfiable() {
+    use aux_trace::{columns, AuxiliaryAir, MainTraceRow, PublicInputs};
+
+    let mut rows = Vec::with_capacity(N);
+    let (mut a, mut b) = (Felt::ONE, Felt::ONE);
+    for _ in 0..N {
+        let c = a + b;
+        rows.push(*MainTraceRow::new().set_a(a).set_b(b).set_c(c).values());
+        a = b + a * b * c;
+        b = c + a;
+    }
+    let trace = AuxTraceTable::<AuxTraceAux>::new(trace_columns(&rows), columns::AUX_WIDTH, 2);
+    let public_inputs = || PublicInputs::new(public_input(140));
+
+    assert!(prove_and_verify::<AuxiliaryAir, _>(trace, public_inputs).is_err());
+}
+
+struct ConstantsAux;
+
+impl AuxColumns for ConstantsAux {
+    fn build<E: FieldElement<BaseField = Felt>>(_main: &[Vec<Felt>], _rand: &[E]) -> Vec<Vec<E>> {
+        let e = (0..N).map(|i| felt(1 + i as u64)).collect();
+        lift(vec![e, rand_felts(141, N), rand_felts(142, N)])
+    }
+}
+
+/// e must be 1 on every row, but also be incremented by 1 from each row to the next and end at 0.
+#[test]
+fn constants_is_unsatisfiable() {
+    use constants::{columns, ConstantsAir, MainTraceRow, PublicInputs};
+
+    let d = rand_felts(143, N);
+    let rows = (0..N)
+        .map(|i| {
+            let (b, d) = if i == 0 {
+                (Felt::ONE, Felt::ONE)
+            } else {
+                (Felt::ZERO, d[i])
+            };
+            *MainTraceRow::new()
+                .set_a(felt(1 + i as u64))
+                .set_b(b)
+                .set_c(Felt::ZERO)
+                .set_d(d)
+                .values()
+        })
+        .collect::<Vec<_>>();
+    let trace = AuxTraceTable::<ConstantsAux>::new(trace_columns(&rows), columns::AUX_WIDTH, 0);
+    let public_inputs = || {
+        PublicInputs::new(
+            public_input(144),
+            public_input(145),
+            public_input(146),
+            public_input(147),
+        )
+    };
+
+    assert!(prove_and_verify::<ConstantsAir, _>(trace, public_inputs).is_err());
+}
+
+// AIRS WINTERFELL CANNOT INSTANTIATE
+// ================================================================================================
+// Winterfell requires at least one transition constraint and one assertion against each trace
+// segment. The following fixtures do not meet this requirement, so the prover cannot build their
+// context. Each test checks that the prover reports the missing constraints for a trace which
+// satisfies the constraints of the AIR, when there is one.
+
+const NO_MAIN_TRANSITIONS: &str = "at least one transition constraint degree must be specified";
+const NO_MAIN_ASSERTIONS: &str = "at least one assertion must be specified";
+const NO_AUX_ASSERTIONS: &str =
+    "at least one assertion must be specified against auxiliary trace segments";
+
+/// Asserts that the result is an error containing the specified message.
+fn assert_rejected(result: Result<(), String>, message: &str) {
+    let err = result.expect_err("expected the prover to reject the AIR");
+    assert!(err.contains(message), "unexpected error: {err}");
+}
+
+struct TraceAccessAux;
+
+impl AuxColumns for TraceAccessAux {
+    fn build<E: FieldElement<BaseField = Felt>>(_main: &[Vec<Felt>], _rand: &[E]) -> Vec<Vec<E>> {
+        let d = rand_felts(7, N);
+        let c = accumulate(felt(8), |i, _| d[i] + Felt::ONE);
+        lift(vec![c, d])
+    }
+}
+
+/// The AIR has no boundary constraint against the auxiliary trace.
+#[test]
+fn indexed_trace_access_is_unsupported() {
+    use indexed_trace_access::{columns, MainTraceRow, PublicInputs, TraceAccessAir};
+
+    let b = rand_felts(6, N);
+    let a = accumulate(Felt::ZERO, |i, _| b[i] + Felt::ONE);
+    let rows = (0..N)
+        .map(|i| *MainTraceRow::new().set_a(a[i]).set_b(b[i]).values())
+        .collect::<Vec<_>>();
+    let trace = AuxTraceTable::<TraceAccessAux>::new(trace_columns(&rows), columns::AUX_WIDTH, 0);
+    let public_inputs = || PublicInputs::new(public_input(9));
+
+    assert_rejected(
+        prove_and_verify::<TraceAccessAir, _>(trace, public_inputs),
+        NO_AUX_ASSERTIONS,
+    );
+}
+
+struct ListComprehensionAux;
+
+impl AuxColumns for ListComprehensionAux {
+    fn build<E: FieldElement<BaseField = Felt>>(main: &[Vec<Felt>], _rand: &[E]) -> Vec<Vec<E>> {
+        let clk = &main[list_comprehension::columns::CLK];
+        let mut c = (0..4).map(|j| rand_felts(20 + j, N)).collect::<Vec<_>>();
+        c[2][0] = Felt::ZERO;
+        let mut d = (0..4).map(|j| rand_felts(30 + j, N)).collect::<Vec<_>>();
+        // a = clk * 8 * c[3] must also equal clk * (c[0]' - d[0]').
+        for i in 1..N {
+            d[0][i] = c[0][i] - felt(8) * c[3][i - 1];
+        }
+
+        let a = (0..N)
+            .map(|i| clk[i] * felt(8) * c[3][i])
+            .collect::<Vec<_>>();
+        let b0 = rand_felts(40, N);
+        let b1 = (0..N)
+            .map(|i| clk[i] * (c[1][i] - d[2][i]))
+            .collect::<Vec<_>>();
+        // a = (0 + b[0] - c[0] - d[0]) + (1 + b[1] - c[1] - d[1]) + (2 + b[2] - c[2] - d[2]).
+        let b2 = (0..N)
+            .map(|i| {
+                a[i] - (b0[i] - c[0][i] - d[0][i]) - (Felt::ONE + b1[i] - c[1][i] - d[1][i])
+                    + c[2][i]
+                    + d[2][i]
+                    - felt(2)
+            })
+            .collect::<Vec<_>>();
+
+        let mut columns = vec![a, b0, b1, b2];
+        columns.extend(c);
+        columns.extend(d);
+        lift(columns)
+    }
+}
+
+/// The AIR has no boundary constraint against the main trace.
+#[test]
+fn list_comprehension_is_unsupported() {
+    use list_comprehension::{columns, ListComprehensionAir, MainTraceRow, PublicInputs};
+
+    let (clk, fmp0, ctx) = (rand_felts(10, N), rand_felts(11, N), rand_felts(12, N));
+    let rows = (0..N)
+        .map(|i| {
+            *MainTraceRow::new()
+                .set_clk(clk[i])
+                .set_fmp([fmp0[i], clk[i]])
+                .set_ctx(ctx[i])
+                .values()
+        })
+        .collect::<Vec<_>>();
+    let trace =
+        AuxTraceTable::<ListComprehensionAux>::new(trace_columns(&rows), columns::AUX_WIDTH, 0);
+    let public_inputs = || PublicInputs::new(public_input(13));
+
+    assert_rejected(
+        prove_and_verify::<ListComprehensionAir, _>(trace, public_inputs),
+        NO_MAIN_ASSERTIONS,
+    );
+}
+
+struct ListFoldingAux;
+
+impl AuxColumns for ListFoldingAux {
+    fn build<E: FieldElement<BaseField = Felt>>(main: &[Vec<Felt>], _rand: &[E]) -> Vec<Vec<E>> {
+        let fmp0 = &main[list_folding::columns::FMP_START];
+        let a = rand_felts(50, N);
+        let mut c = (0..4).map(|j| rand_felts(51 + j, N)).collect::<Vec<_>>();
+        c[2][0] = Felt::ZERO;
+        let d = (0..4).map(|j| rand_felts(55 + j, N)).collect::<Vec<_>>();
+
+        let sum = |i: usize| c.iter().fold(Felt::ZERO, |acc, c| acc + c[i]);
+        let prod = |i: usize| d.iter().fold(Felt::ONE, |acc, d| acc * d[i]);
+        let dot = |i: usize| (0..4).fold(Felt::ZERO, |acc, j| acc + c[j][i] * d[j][i]);
+        let prod_sums = |i: usize| (0..4).fold(Felt::ONE, |acc, j| acc * (c[j][i] + d[j][i]));
+        let b = vec![
+            accumulate(felt(60), |i, _| sum(i) + prod(i)),
+            accumulate(felt(61), |i, _| sum(i) + prod(i)),
+            accumulate(felt(62), |i, _| dot(i) + prod_sums(i)),
+            accumulate(felt(63), |i, _| fmp0[i] + dot(i) + dot(i)),
+        ];
+
+        let mut columns = vec![a];
+        columns.extend(b);
+        columns.extend(c);
+        columns.extend(d);
+        lift(columns)
+    }
+}
+
+/// The AIR has no integrity constraint against the main trace.
+#[test]
+fn list_folding_is_unsupported() {
+    use list_folding::{columns, ListFoldingAir, MainTraceRow, PublicInputs};
+
+    let values = (0..4).map(|j| rand_felts(64 + j, N)).collect::<Vec<_>>();
+    let rows = (0..N)
+        .map(|i| {
+            *MainTraceRow::new()
+                .set_clk(values[0][i])
+                .set_fmp([values[1][i], values[2][i]])
+                .set_ctx(values[3][i])
+                .values()
+        })
+        .collect::<Vec<_>>();
+    let trace = AuxTraceTable::<ListFoldingAux>::new(trace_columns(&rows), columns::AUX_WIDTH, 0);
+    let public_inputs = || PublicInputs::new(public_input(68));
+
+    assert_rejected(
+        prove_and_verify::<ListFoldingAir, _>(trace, public_inputs),
+        NO_MAIN_TRANSITIONS,
+    );
+}
+
+struct RandomValuesAux;
+
+impl AuxColumns for RandomValuesAux {
+    fn build<E: FieldElement<BaseField = Felt>>(_main: &[Vec<Felt>], rand: &[E]) -> Vec<Vec<E>> {
+        let c = accumulate(rand[5] + rand[3] + rand[15], |_, _| {
+            rand[15] - rand[0] + rand[3]
+        });
+        let d = rand_felts(150, N).into_iter().map(E::from).collect();
+        vec![c, d]
+    }
+}
+
+/// The AIRs have no integrity constraint against the main trace. Their constraints also
+/// contradict each other: c is constant after the first row, but its last value must differ from
+/// that constant for random values.
+#[test]
+fn random_values_is_unsupported() {
+    let main = vec![rand_felts(151, N), rand_felts(152, N)];
+
+    let trace = AuxTraceTable::<RandomValuesAux>::new(main.clone(), 2, 16);
+    let public_inputs = || random_values_simple::PublicInputs::new(public_input(153));
+    assert_rejected(
+        prove_and_verify::<random_values_simple::RandomValuesAir, _>(trace, public_inputs),
+        NO_MAIN_TRANSITIONS,
+    );
+
+    let trace = AuxTraceTable::<RandomValuesAux>::new(main, 2, 16);
+    let public_inputs = || random_values_bindings::PublicInputs::new(public_input(153));
+    assert_rejected(
+        prove_and_verify::<random_values_bindings::RandomValuesAir, _>(trace, public_inputs),
+        NO_MAIN_TRANSITIONS,
+    );
+}
+
+struct TraceColGroupsAux;
+
+impl AuxColumns for TraceColGroupsAux {
+    fn build<E: FieldElement<BaseField = Felt>>(_main: &[Vec<Felt>], _rand: &[E]) -> Vec<Vec<E>> {
+        use trace_col_groups::columns::C_START;
+
+        let mut columns = (0..5).map(|j| rand_felts(120 + j, N)).collect::<Vec<_>>();
+        columns[C_START + 2][0] = Felt::ZERO;
+        lift(columns)
+    }
+}
+
+/// The AIR has no boundary constraint against the main trace.
+#[test]
+fn trace_col_groups_is_unsupported() {
+    use trace_col_groups::{columns, MainTraceRow, PublicInputs, TraceColGroupAir};
+
+    let (clk, ctx) = (rand_felts(125, N), rand_felts(126, N));
+    let rows = (0..N)
+        .map(|i| {
+            let step = felt(i as u64);
+            *MainTraceRow::new()
+                .set_clk(clk[i])
+                .set_fmp([felt(1000) - step, felt(2000) + step])
+                .set_ctx(ctx[i])
+                .values()
+        })
+        .collect::<Vec<_>>();
+    let trace =
+        AuxTraceTable::<TraceColGroupsAux>::new(trace_columns(&rows), columns::AUX_WIDTH, 0);
+    let public_inputs = || PublicInputs::new(public_input(127));
+
+    assert_rejected(
+        prove_and_verify::<TraceColGroupAir, _>(trace, public_inputs),
+        NO_MAIN_ASSERTIONS,
+    );
+}
+
+struct VariablesAux;
+
+impl AuxColumns for VariablesAux {
+    fn build<E: FieldElement<BaseField = Felt>>(main: &[Vec<Felt>], rand: &[E]) -> Vec<Vec<E>> {
+        let c = &main[variables::columns::C];
+        vec![accumulate(E::ONE, |i, p| p * (E::from(c[i]) + rand[0]))]
+    }
+}
+
+/// The AIR has no boundary constraint against the auxiliary trace. Its constraints also
+/// contradict each other: when s = 0 the next value of s must be 5, and when s = 1, 0 must equal
+/// 5, so no binary s satisfies them.
+#[test]
+fn variables_is_unsupported() {
+    use variables::{columns, MainTraceRow, PublicInputs, VariablesAir};
+
+    let b = rand_felts(160, N);
+    let rows = (0..N)
+        .map(|i| {
+            let a = match i {
+                0 => Felt::ZERO,
+                i if i == N - 1 => Felt::ONE,
+                i => felt(i as u64),
+            };
+            *MainTraceRow::new()
+                .set_a(a)
+                .set_b(b[i])
+                .set_c(a + b[i] + felt(6))
+                .values()
+        })
+        .collect::<Vec<_>>();
+    let trace = AuxTraceTable::<VariablesAux>::new(trace_columns(&rows), columns::AUX_WIDTH, 1);
+    let public_inputs = || PublicInputs::new(public_input(161), public_input(162));
+
+    assert_rejected(
+        prove_and_verify::<VariablesAir, _>(trace, public_inputs),
+        NO_AUX_ASSERTIONS,
+    );
+}
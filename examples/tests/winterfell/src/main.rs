@@ -69,7 +69,7 @@ fn get_public_values(input_f:&str, output_f:&str) -> PublicInputs {
   for v in outputs.iter() {
     output_a.push(*v);
   }
-  PublicInputs::new(input_a, output_a)
+  PublicInputs::from_elements(&[input_a, output_a].concat()).unwrap()
 }
 
 
@@ -140,7 +140,7 @@ where
 //println!("Output {:?} ", trace.get(i, last_step));
           outputs.push (trace.get(i, last_step));
         }
-        PublicInputs::new(inputs, outputs)
+        PublicInputs::from_elements(&[inputs, outputs].concat()).unwrap()
     }
 
     fn options(&self) -> &WinterProofOptions {
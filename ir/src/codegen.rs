@@ -0,0 +1,188 @@
+use super::{AirIR, SourceSpan, TraceSegment};
+use std::fmt::Display;
+
+// CONSTRAINT LOCATION
+// ================================================================================================
+
+/// The kind of a constraint of an [AirIR](super::AirIR).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintKind {
+    Boundary,
+    Integrity,
+}
+
+/// Identifies a constraint of an [AirIR](super::AirIR) by its kind, the trace segment against
+/// which it is defined, and its position among the constraints of that kind and segment. The
+/// location can also carry the span of the source statement from which the constraint was built,
+/// which is ignored when comparing locations.
+#[derive(Debug, Clone, Copy)]
+pub struct ConstraintLocation {
+    kind: ConstraintKind,
+    trace_segment: TraceSegment,
+    index: usize,
+    span: Option<SourceSpan>,
+}
+
+impl ConstraintLocation {
+    /// Returns the location of the boundary constraint at the specified position of the
+    /// specified trace segment.
+    pub fn boundary(trace_segment: TraceSegment, index: usize) -> Self {
+        Self {
+            kind: ConstraintKind::Boundary,
+            trace_segment,
+            index,
+            span: None,
+        }
+    }
+
+    /// Returns the location of the integrity constraint at the specified position of the
+    /// specified trace segment.
+    pub fn integrity(trace_segment: TraceSegment, index: usize) -> Self {
+        Self {
+            kind: ConstraintKind::Integrity,
+            trace_segment,
+            index,
+            span: None,
+        }
+    }
+
+    /// Returns the kind of the constraint.
+    pub fn kind(&self) -> ConstraintKind {
+        self.kind
+    }
+
+    /// Returns the trace segment against which the constraint is defined.
+    pub fn trace_segment(&self) -> TraceSegment {
+        self.trace_segment
+    }
+
+    /// Returns the position of the constraint among the constraints of its kind and segment.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the span of the source statement from which the constraint was built, if it is
+    /// known.
+    pub fn span(&self) -> Option<SourceSpan> {
+        self.span
+    }
+}
+
+impl PartialEq for ConstraintLocation {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.trace_segment == other.trace_segment
+            && self.index == other.index
+    }
+}
+
+impl Eq for ConstraintLocation {}
+
+impl Display for ConstraintLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            ConstraintKind::Boundary => "boundary",
+            ConstraintKind::Integrity => "integrity",
+        };
+        write!(
+            f,
+            "{kind} constraint {} of trace segment {}",
+            self.index, self.trace_segment
+        )
+    }
+}
+
+// CODEGEN ERROR
+// ================================================================================================
+
+/// Errors which can occur while generating code for a target from a valid
+/// [AirIR](super::AirIR).
+///
+/// An error raised while generating a constraint is reported with the location of that
+/// constraint, which is attached by the code generator using [CodegenError::in_constraint].
+#[derive(Debug)]
+pub enum CodegenError {
+    /// The target does not support the named feature.
+    UnsupportedFeature(String, Option<ConstraintLocation>),
+    /// The constraint does not have the form which the target expects, as described by the
+    /// message.
+    InvalidConstraint(String, Option<ConstraintLocation>),
+    /// Two declarations of the generated code have the same name, for example a scalar constant
+    /// and an element of an unrolled vector or matrix constant.
+    NameConflict(String),
+}
+
+impl CodegenError {
+    /// Returns an error for the named feature, which the target does not support.
+    pub fn unsupported_feature(feature: impl Into<String>) -> Self {
+        Self::UnsupportedFeature(feature.into(), None)
+    }
+
+    /// Returns an error for a constraint which does not have the form which the target expects.
+    pub fn invalid_constraint(message: impl Into<String>) -> Self {
+        Self::InvalidConstraint(message.into(), None)
+    }
+
+    /// Returns an error for a name which is used by two declarations of the generated code.
+    pub fn name_conflict(name: impl Into<String>) -> Self {
+        Self::NameConflict(name.into())
+    }
+
+    /// Attaches the location of the constraint which was being generated when the error occurred,
+    /// unless the error already has one.
+    pub fn in_constraint(self, location: ConstraintLocation) -> Self {
+        match self {
+            Self::UnsupportedFeature(feature, None) => {
+                Self::UnsupportedFeature(feature, Some(location))
+            }
+            Self::InvalidConstraint(message, None) => {
+                Self::InvalidConstraint(message, Some(location))
+            }
+            err => err,
+        }
+    }
+
+    /// Attaches the span of the source statement from which the constraint which caused this
+    /// error was built, when the error has a location and the [AirIR] records the span.
+    pub fn with_source_span(mut self, ir: &AirIR) -> Self {
+        if let Self::UnsupportedFeature(_, Some(location))
+        | Self::InvalidConstraint(_, Some(location)) = &mut self
+        {
+            location.span = ir.constraint_span(location);
+        }
+        self
+    }
+
+    /// Returns the location of the constraint which caused this error, if it is known.
+    pub fn constraint(&self) -> Option<&ConstraintLocation> {
+        match self {
+            Self::UnsupportedFeature(_, location) | Self::InvalidConstraint(_, location) => {
+                location.as_ref()
+            }
+            Self::NameConflict(_) => None,
+        }
+    }
+}
+
+impl Display for CodegenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedFeature(feature, None) => write!(f, "{feature} are not supported"),
+            Self::UnsupportedFeature(feature, Some(location)) => {
+                write!(f, "{feature} are not supported, but are used by {location}")
+            }
+            Self::InvalidConstraint(message, None) => write!(f, "invalid constraint: {message}"),
+            Self::InvalidConstraint(message, Some(location)) => {
+                write!(f, "invalid {location}: {message}")
+            }
+            Self::NameConflict(name) => {
+                write!(
+                    f,
+                    "`{name}` is declared more than once in the generated code"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodegenError {}
@@ -1,8 +1,8 @@
 use super::{
     ast, AccessType, AlgebraicGraph, BTreeMap, BTreeSet, ConstantValueExpr, ConstraintDomain,
     ConstraintRoot, Constraints, Declarations, Expression, Identifier, Iterable, ListComprehension,
-    ListFolding, ListFoldingValueExpr, NodeIndex, Operation, SemanticError, Symbol, SymbolAccess,
-    SymbolBinding, SymbolTable, TraceAccess, TraceSegment, Value, VariableBinding,
+    ListFolding, ListFoldingValueExpr, NodeIndex, Operation, SemanticError, SourceSpan, Symbol,
+    SymbolAccess, SymbolBinding, SymbolTable, TraceAccess, TraceSegment, Value, VariableBinding,
     VariableValueExpr, CURRENT_ROW,
 };
 
@@ -40,6 +40,25 @@ pub(super) struct ConstraintBuilder {
     /// Constraint roots for all integrity constraints against the execution trace, by trace segment,
     /// where integrity constraints are any constraints that apply to every row or every frame.
     integrity_constraints: Vec<Vec<ConstraintRoot>>,
+
+    /// The position of the source statement from which each boundary constraint was built, by
+    /// trace segment, among the constraint statements of the `boundary_constraints` section.
+    boundary_statements: Vec<Vec<usize>>,
+
+    /// The position of the source statement from which each integrity constraint was built, by
+    /// trace segment, among the constraint statements of the `integrity_constraints` section.
+    integrity_statements: Vec<Vec<usize>>,
+
+    /// The span of the source of each constraint statement of the `boundary_constraints` section.
+    boundary_statement_spans: Vec<SourceSpan>,
+
+    /// The span of the source of each constraint statement of the `integrity_constraints`
+    /// section.
+    integrity_statement_spans: Vec<SourceSpan>,
+
+    /// The position of the constraint statement which is being inserted, among the constraint
+    /// statements of its section.
+    current_statement: usize,
 }
 
 impl ConstraintBuilder {
@@ -54,6 +73,11 @@ impl ConstraintBuilder {
             // accumulated data in the current context
             boundary_constraints: vec![Vec::new(); num_trace_segments],
             integrity_constraints: vec![Vec::new(); num_trace_segments],
+            boundary_statements: vec![Vec::new(); num_trace_segments],
+            integrity_statements: vec![Vec::new(); num_trace_segments],
+            boundary_statement_spans: Vec::new(),
+            integrity_statement_spans: Vec::new(),
+            current_statement: 0,
             graph: AlgebraicGraph::default(),
         }
     }
@@ -63,6 +87,11 @@ impl ConstraintBuilder {
             self.graph,
             self.boundary_constraints,
             self.integrity_constraints,
+        )
+        .with_statements(self.boundary_statements, self.integrity_statements)
+        .with_statement_spans(
+            self.boundary_statement_spans,
+            self.integrity_statement_spans,
         );
         (self.symbol_table.into_declarations(), constraints)
     }
@@ -74,26 +103,44 @@ impl ConstraintBuilder {
         self.graph.insert_node(op)
     }
 
-    // TODO: docs
+    /// Inserts the statements of the `boundary_constraints` section. An error found in a statement
+    /// is reported with the span of that statement.
     pub(crate) fn insert_boundary_constraints(
         &mut self,
         stmts: Vec<ast::BoundaryStmt>,
     ) -> Result<(), SemanticError> {
+        self.current_statement = 0;
         for stmt in stmts.into_iter() {
-            self.insert_boundary_stmt(stmt)?
+            let span = stmt.span();
+            let is_constraint = !matches!(stmt, ast::BoundaryStmt::VariableBinding(_));
+            self.insert_boundary_stmt(stmt)
+                .map_err(|err| err.in_statement(span))?;
+            if is_constraint {
+                self.boundary_statement_spans.push(span);
+                self.current_statement += 1;
+            }
         }
         self.symbol_table.clear_variables();
 
         Ok(())
     }
 
-    // TODO: docs
+    /// Inserts the statements of the `integrity_constraints` section. An error found in a
+    /// statement is reported with the span of that statement.
     pub(crate) fn insert_integrity_constraints(
         &mut self,
         stmts: Vec<ast::IntegrityStmt>,
     ) -> Result<(), SemanticError> {
+        self.current_statement = 0;
         for stmt in stmts.into_iter() {
-            self.insert_integrity_stmt(stmt)?
+            let span = stmt.span();
+            let is_constraint = !matches!(stmt, ast::IntegrityStmt::VariableBinding(_));
+            self.insert_integrity_stmt(stmt)
+                .map_err(|err| err.in_statement(span))?;
+            if is_constraint {
+                self.integrity_statement_spans.push(span);
+                self.current_statement += 1;
+            }
         }
         self.symbol_table.clear_variables();
 
@@ -121,8 +168,10 @@ impl ConstraintBuilder {
         // add the constraint to the appropriate set of constraints.
         if domain.is_boundary() {
             self.boundary_constraints[trace_segment].push(constraint_root);
+            self.boundary_statements[trace_segment].push(self.current_statement);
         } else {
             self.integrity_constraints[trace_segment].push(constraint_root);
+            self.integrity_statements[trace_segment].push(self.current_statement);
         }
 
         Ok(())
@@ -5,6 +5,7 @@ use std::fmt::Display;
 /// representing a constraint. It also contains the [ConstraintDomain] for the constraint, which is
 /// the domain against which the constraint should be applied.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConstraintRoot {
     pub index: NodeIndex,
     pub domain: ConstraintDomain,
@@ -34,6 +35,7 @@ impl ConstraintRoot {
 /// the frame. For example, for a transition constraint that is applied against the current and next
 /// rows, the frame size will be 2.
 #[derive(Debug, Clone, PartialEq, Eq, Copy, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConstraintDomain {
     FirstRow,          // for boundary constraints against the first row
     LastRow,           // for boundary constraints against the last row
@@ -19,6 +19,7 @@ use super::{
 ///   do not necessarily represent all constraints. There could be constraints which are also
 ///   subgraphs of other constraints.
 #[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AlgebraicGraph {
     /// All nodes in the graph.
     pub nodes: Vec<Node>,
@@ -157,9 +158,11 @@ impl AlgebraicGraph {
 
 /// Reference to a node in a graph by its index in the nodes vector of the graph struct.
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodeIndex(pub usize);
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Node {
     /// The operation represented by this node
     pub op: Operation,
@@ -173,6 +176,7 @@ impl Node {
 
 /// An integrity constraint operation or value reference.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Operation {
     /// TODO: docs
     Value(Value),
@@ -1,4 +1,7 @@
-use super::{ast::Boundary, SemanticError, TraceSegment, Value};
+use super::{
+    ast::Boundary, ConstraintKind, ConstraintLocation, SemanticError, SourceSpan, TraceSegment,
+    Value,
+};
 use std::collections::BTreeMap;
 
 mod constraint;
@@ -33,6 +36,7 @@ pub(super) const MIN_CYCLE_LENGTH: usize = 2;
 /// be specified by a vector in transition_constraints[0] containing a [ConstraintRoot] in the graph
 /// for each constraint against the main trace.
 #[derive(Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Constraints {
     /// Constraint roots for all boundary constraints against the execution trace, by trace segment,
     /// where boundary constraints are any constraints that apply to either the first or the last
@@ -43,6 +47,22 @@ pub struct Constraints {
     /// where integrity constraints are any constraints that apply to every row or every frame.
     pub integrity_constraints: Vec<Vec<ConstraintRoot>>,
 
+    /// For each boundary constraint, by trace segment, the position of the statement from which
+    /// it was built among the constraint statements of the `boundary_constraints` section.
+    pub boundary_statements: Vec<Vec<usize>>,
+
+    /// For each integrity constraint, by trace segment, the position of the statement from which
+    /// it was built among the constraint statements of the `integrity_constraints` section.
+    pub integrity_statements: Vec<Vec<usize>>,
+
+    /// The span of the source of each constraint statement of the `boundary_constraints` section,
+    /// by position.
+    pub boundary_statement_spans: Vec<SourceSpan>,
+
+    /// The span of the source of each constraint statement of the `integrity_constraints`
+    /// section, by position.
+    pub integrity_statement_spans: Vec<SourceSpan>,
+
     /// A directed acyclic graph which represents all of the constraints and their subexpressions.
     pub graph: AlgebraicGraph,
 }
@@ -59,9 +79,37 @@ impl Constraints {
             graph,
             boundary_constraints,
             integrity_constraints,
+            boundary_statements: Vec::new(),
+            integrity_statements: Vec::new(),
+            boundary_statement_spans: Vec::new(),
+            integrity_statement_spans: Vec::new(),
         }
     }
 
+    /// Records the positions of the source statements from which the boundary and integrity
+    /// constraints were built, by trace segment, in the order of the constraints.
+    pub fn with_statements(
+        mut self,
+        boundary_statements: Vec<Vec<usize>>,
+        integrity_statements: Vec<Vec<usize>>,
+    ) -> Self {
+        self.boundary_statements = boundary_statements;
+        self.integrity_statements = integrity_statements;
+        self
+    }
+
+    /// Records the spans of the source of the constraint statements of the boundary and integrity
+    /// constraints sections, by position.
+    pub fn with_statement_spans(
+        mut self,
+        boundary_statement_spans: Vec<SourceSpan>,
+        integrity_statement_spans: Vec<SourceSpan>,
+    ) -> Self {
+        self.boundary_statement_spans = boundary_statement_spans;
+        self.integrity_statement_spans = integrity_statement_spans;
+        self
+    }
+
     // --- PUBLIC ACCESSORS -----------------------------------------------------------------------
 
     /// Returns the number of boundary constraints applied against the specified trace segment.
@@ -111,6 +159,43 @@ impl Constraints {
         &self.integrity_constraints[trace_segment as usize]
     }
 
+    /// Returns the number of rows at the end of the trace over which the transition constraints
+    /// cannot be enforced, since a constraint applied to every frame of `n` rows cannot be
+    /// evaluated on the last `n - 1` rows. This is at least 1.
+    pub fn min_transition_exemptions(&self) -> usize {
+        self.integrity_constraints
+            .iter()
+            .flatten()
+            .filter_map(|constraint| match constraint.domain() {
+                ConstraintDomain::EveryFrame(size) => Some(size - 1),
+                _ => None,
+            })
+            .fold(1, usize::max)
+    }
+
+    /// Returns the position of the statement from which the constraint at the specified location
+    /// was built, among the constraint statements of its section of the source, if it is known.
+    pub fn statement(&self, location: &ConstraintLocation) -> Option<usize> {
+        let statements = match location.kind() {
+            ConstraintKind::Boundary => &self.boundary_statements,
+            ConstraintKind::Integrity => &self.integrity_statements,
+        };
+        statements
+            .get(location.trace_segment() as usize)?
+            .get(location.index())
+            .copied()
+    }
+
+    /// Returns the span of the source of the statement from which the constraint at the specified
+    /// location was built, if it is known.
+    pub fn statement_span(&self, location: &ConstraintLocation) -> Option<SourceSpan> {
+        let spans = match location.kind() {
+            ConstraintKind::Boundary => &self.boundary_statement_spans,
+            ConstraintKind::Integrity => &self.integrity_statement_spans,
+        };
+        spans.get(self.statement(location)?).copied()
+    }
+
     /// Returns the [AlgebraicGraph] representing all constraints and sub-expressions.
     pub fn graph(&self) -> &AlgebraicGraph {
         &self.graph
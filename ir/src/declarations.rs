@@ -1,16 +1,18 @@
-use super::{ConstantBinding, SemanticError};
+use super::{ConstantBinding, SemanticError, TraceBinding};
 
 // TYPE ALIASES
 // ================================================================================================
 
 pub type PublicInput = (String, usize);
 pub type PeriodicColumn = Vec<u64>;
+pub type RandomValueBinding = (String, usize);
 
 // DECLARATIONS
 // ================================================================================================
 
 /// TODO: docs
 #[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Declarations {
     /// A vector of constants declared in the AirScript module.
     constants: Vec<ConstantBinding>,
@@ -19,6 +21,9 @@ pub struct Declarations {
     /// periodic columns as the key and the vector of periodic values as the value
     periodic_columns: Vec<PeriodicColumn>,
 
+    /// The names of the periodic columns, in the same order as `periodic_columns`.
+    periodic_column_names: Vec<String>,
+
     /// A vector of public inputs with each value as a tuple of input identifier and it's array
     /// size.
     public_inputs: Vec<PublicInput>,
@@ -27,9 +32,19 @@ pub struct Declarations {
     /// `rand: [a, b[n], c, ...]` it will be length of the flattened array.
     num_random_values: u16,
 
+    /// The name of the random values array, if random values were declared.
+    random_values_name: Option<String>,
+
+    /// The random values bound to a name, in order, with each value as a tuple of the identifier
+    /// and the number of random values it is bound to.
+    random_value_bindings: Vec<RandomValueBinding>,
+
     /// The widths of each segment of the trace, in order such that the index is the trace segment
     /// and the value is the number of columns in this segment.
     trace_segment_widths: Vec<u16>,
+
+    /// The named groups of trace columns of all trace segments, in declaration order.
+    trace_bindings: Vec<TraceBinding>,
 }
 
 impl Declarations {
@@ -43,10 +58,31 @@ impl Declarations {
         &self.periodic_columns
     }
 
+    /// Returns the names of the periodic columns, in the same order as the periodic columns.
+    pub fn periodic_column_names(&self) -> &[String] {
+        &self.periodic_column_names
+    }
+
     pub fn public_inputs(&self) -> &[PublicInput] {
         &self.public_inputs
     }
 
+    /// Returns the number of random values declared for the auxiliary trace segments.
+    pub fn num_random_values(&self) -> u16 {
+        self.num_random_values
+    }
+
+    /// Returns the name of the random values array, or `None` if no random values were declared.
+    pub fn random_values_name(&self) -> Option<&str> {
+        self.random_values_name.as_deref()
+    }
+
+    /// Returns the named random value bindings, in order. The first binding is bound to the first
+    /// random values, and each following binding to the values after those of the previous one.
+    pub fn random_value_bindings(&self) -> &[RandomValueBinding] {
+        &self.random_value_bindings
+    }
+
     /// Gets the number of trace segments that were specified for this AIR.
     pub fn num_trace_segments(&self) -> usize {
         self.trace_segment_widths.len() + 1
@@ -57,6 +93,11 @@ impl Declarations {
         &self.trace_segment_widths
     }
 
+    /// Returns the named groups of trace columns of all trace segments, in declaration order.
+    pub fn trace_bindings(&self) -> &[TraceBinding] {
+        &self.trace_bindings
+    }
+
     /// Returns the width of the requested trace segment.
     ///
     /// # Errors
@@ -78,7 +119,8 @@ impl Declarations {
         self.constants.push(constant)
     }
 
-    pub(super) fn add_periodic_column(&mut self, periodic_column: PeriodicColumn) {
+    pub(super) fn add_periodic_column(&mut self, name: String, periodic_column: PeriodicColumn) {
+        self.periodic_column_names.push(name);
         self.periodic_columns.push(periodic_column)
     }
 
@@ -86,8 +128,19 @@ impl Declarations {
         self.public_inputs.push(public_input)
     }
 
-    pub(super) fn set_num_random_values(&mut self, num_random_values: u16) {
+    pub(super) fn set_random_values(
+        &mut self,
+        name: String,
+        num_random_values: u16,
+        bindings: Vec<RandomValueBinding>,
+    ) {
+        self.random_values_name = Some(name);
         self.num_random_values = num_random_values;
+        self.random_value_bindings = bindings;
+    }
+
+    pub(super) fn add_trace_binding(&mut self, binding: TraceBinding) {
+        self.trace_bindings.push(binding)
     }
 
     pub(super) fn set_trace_segment_width(&mut self, trace_segment: usize, width: u16) {
@@ -0,0 +1,171 @@
+use super::{
+    constraints::{AlgebraicGraph, ConstraintDomain, ConstraintRoot},
+    CodegenError, ConstantBinding, ConstantValueExpr,
+};
+use std::collections::BTreeSet;
+
+/// The modulus of the Goldilocks field over which the code generators evaluate the constraints.
+const GOLDILOCKS_MODULUS: u64 = 0xffff_ffff_0000_0001;
+
+/// The smallest blowup factor accepted by Winterfell.
+pub const MIN_BLOWUP_FACTOR: usize = 2;
+
+/// The number of rows of a Winterfell evaluation frame, which holds the current and next rows.
+pub const MIN_EVAL_FRAME_SIZE: usize = 2;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Returns the named constants as a list of scalar constants with their canonical values.
+///
+/// Vector and matrix constants are unrolled into one constant per element, named after the
+/// constant and the indices of the element, e.g. `B_0` for `B[0]` and `C_1_0` for `C[1][0]`, so
+/// that targets which resolve all accesses to the constants at compile time can declare them as
+/// scalars.
+///
+/// # Errors
+/// Returns an error if two of the scalar constants have the same name, e.g. if a scalar constant
+/// is named `B_0` and a vector constant is named `B`. The parser only accepts constant names made
+/// of uppercase letters, so this can only happen for constants which were not parsed from source.
+pub fn constant_elements(
+    constants: &[ConstantBinding],
+) -> Result<Vec<(String, u64)>, CodegenError> {
+    let mut elements = Vec::new();
+    for constant in constants.iter() {
+        let name = constant.name().name();
+        match constant.value() {
+            ConstantValueExpr::Scalar(value) => {
+                elements.push((name.to_string(), felt(*value)));
+            }
+            ConstantValueExpr::Vector(vector) => {
+                for (i, value) in vector.iter().enumerate() {
+                    elements.push((vector_element(name, i), felt(*value)));
+                }
+            }
+            ConstantValueExpr::Matrix(matrix) => {
+                for (i, row) in matrix.iter().enumerate() {
+                    for (j, value) in row.iter().enumerate() {
+                        elements.push((matrix_element(name, i, j), felt(*value)));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut names = BTreeSet::new();
+    for (name, _) in elements.iter() {
+        if !names.insert(name) {
+            return Err(CodegenError::name_conflict(name));
+        }
+    }
+    Ok(elements)
+}
+
+/// Returns the name of the scalar constant holding an element of a vector constant.
+pub fn vector_element(name: &str, idx: usize) -> String {
+    format!("{name}_{idx}")
+}
+
+/// Returns the name of the scalar constant holding an element of a matrix constant.
+pub fn matrix_element(name: &str, row_idx: usize, col_idx: usize) -> String {
+    format!("{name}_{row_idx}_{col_idx}")
+}
+
+/// Returns the canonical representation of the value as a Goldilocks field element, i.e. the
+/// value reduced modulo the Goldilocks prime.
+pub fn felt(value: u64) -> u64 {
+    // the modulus is larger than half of u64::MAX, so a single subtraction is enough
+    if value >= GOLDILOCKS_MODULUS {
+        value - GOLDILOCKS_MODULUS
+    } else {
+        value
+    }
+}
+
+// AIR LAYOUT
+// ================================================================================================
+
+/// The values of the layout of an AIR which the verifiers generated from it are configured with.
+///
+/// Every value is computed from the constraints, so that a verifier cannot be configured with a
+/// layout which disagrees with them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirLayout {
+    /// The number of columns of the main trace segment.
+    pub main_segment_width: u16,
+    /// The total number of columns of the auxiliary trace segments.
+    pub aux_trace_width: u16,
+    /// The number of auxiliary trace segments.
+    pub num_aux_segments: usize,
+    /// The number of random values declared for the auxiliary trace segments.
+    pub num_random_values: u16,
+    /// The number of integrity constraints of all trace segments.
+    pub num_transition_constraints: usize,
+    /// The number of boundary constraints of all trace segments.
+    pub num_assertions: usize,
+    /// The blowup factor of the constraint evaluation domain.
+    pub ce_blowup_factor: usize,
+    /// The number of rows of the frame over which the integrity constraints are evaluated.
+    pub eval_frame_size: usize,
+}
+
+impl AirLayout {
+    /// Returns the layout of an AIR with the specified trace segment widths, number of random
+    /// values and constraints. Only the constraints of the declared trace segments are counted.
+    pub fn new(
+        segment_widths: &[u16],
+        num_random_values: u16,
+        graph: &AlgebraicGraph,
+        integrity_constraints: &[Vec<ConstraintRoot>],
+        boundary_constraints: &[Vec<ConstraintRoot>],
+    ) -> Self {
+        let nsegments = segment_widths.len();
+        let aux_segment_widths = &segment_widths[1..];
+        let integrity_constraints =
+            &integrity_constraints[..nsegments.min(integrity_constraints.len())];
+        let boundary_constraints =
+            &boundary_constraints[..nsegments.min(boundary_constraints.len())];
+
+        Self {
+            main_segment_width: segment_widths[0],
+            aux_trace_width: aux_segment_widths.iter().sum(),
+            num_aux_segments: aux_segment_widths.len(),
+            num_random_values,
+            num_transition_constraints: integrity_constraints.iter().map(Vec::len).sum(),
+            num_assertions: boundary_constraints.iter().map(Vec::len).sum(),
+            ce_blowup_factor: ce_blowup_factor(graph, integrity_constraints),
+            eval_frame_size: eval_frame_size(integrity_constraints),
+        }
+    }
+}
+
+/// Returns the blowup factor of the constraint evaluation domain, computed as in Winterfell's
+/// `AirContext` from the blowup factor needed by the highest degree integrity constraint.
+pub fn ce_blowup_factor(
+    graph: &AlgebraicGraph,
+    integrity_constraints: &[Vec<ConstraintRoot>],
+) -> usize {
+    integrity_constraints
+        .iter()
+        .flatten()
+        .map(|constraint| {
+            let degree = graph.degree(&constraint.index);
+            let degree_bound = degree.base() + degree.cycles().len() - 1;
+            degree_bound.next_power_of_two().max(MIN_BLOWUP_FACTOR)
+        })
+        .max()
+        .unwrap_or(MIN_BLOWUP_FACTOR)
+}
+
+/// Returns the number of rows of the evaluation frame, which spans the rows accessed by the widest
+/// integrity constraint and holds at least the current and next rows, as in Winterfell.
+pub fn eval_frame_size(integrity_constraints: &[Vec<ConstraintRoot>]) -> usize {
+    integrity_constraints
+        .iter()
+        .flatten()
+        .filter_map(|constraint| match constraint.domain() {
+            ConstraintDomain::EveryFrame(size) => Some(size),
+            _ => None,
+        })
+        .fold(MIN_EVAL_FRAME_SIZE, usize::max)
+}
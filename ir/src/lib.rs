@@ -1,7 +1,9 @@
+#[cfg(feature = "serde")]
+pub use air_script_core::schema;
 pub use air_script_core::{
     AccessType, ConstantBinding, ConstantValueExpr, Expression, Identifier, Iterable,
-    ListComprehension, ListFolding, ListFoldingValueExpr, SymbolAccess, TraceAccess, TraceBinding,
-    TraceSegment, VariableBinding, VariableValueExpr,
+    ListComprehension, ListFolding, ListFoldingValueExpr, SourceSpan, SymbolAccess, TraceAccess,
+    TraceBinding, TraceSegment, VariableBinding, VariableValueExpr,
 };
 pub use parser::ast;
 use std::collections::{BTreeMap, BTreeSet};
@@ -18,14 +20,21 @@ pub use constraints::{IntegrityConstraintDegree, NodeIndex};
 
 pub mod declarations;
 use declarations::Declarations;
-pub use declarations::{PeriodicColumn, PublicInput};
+pub use declarations::{PeriodicColumn, PublicInput, RandomValueBinding};
+
+pub mod layout;
+pub use layout::AirLayout;
+
+mod codegen;
+pub use codegen::{CodegenError, ConstraintKind, ConstraintLocation};
 
 mod symbol_table;
 pub use symbol_table::Value;
 use symbol_table::{Symbol, SymbolBinding, SymbolTable};
 
 mod validation;
-use validation::{SemanticError, SourceValidator};
+pub use validation::SemanticError;
+use validation::SourceValidator;
 
 #[cfg(test)]
 mod tests;
@@ -40,11 +49,28 @@ pub type BoundaryConstraintsMap = BTreeMap<usize, Expression>;
 /// Internal representation of an AIR.
 ///
 /// TODO: docs
-#[derive(Default, Debug)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AirIR {
     pub air_name: String,
     pub declarations: Declarations,
     pub constraints: Constraints,
+
+    /// The number of rows at the end of the trace over which the transition constraints are not
+    /// enforced. This is the number required by the domains of the integrity constraints, unless
+    /// a larger number was declared in the `transition_exemptions` section.
+    pub num_transition_exemptions: usize,
+}
+
+impl Default for AirIR {
+    fn default() -> Self {
+        Self {
+            air_name: String::default(),
+            declarations: Declarations::default(),
+            constraints: Constraints::default(),
+            num_transition_exemptions: Self::default_transition_exemptions(),
+        }
+    }
 }
 
 impl AirIR {
@@ -64,6 +90,7 @@ impl AirIR {
         let mut eval_exprs = Vec::new();
         let mut boundary_stmts = Vec::new();
         let mut integrity_stmts = Vec::new();
+        let mut declared_exemptions = None;
 
         for section in source {
             match section {
@@ -107,6 +134,11 @@ impl AirIR {
                     integrity_stmts.extend(stmts);
                     validator.exists("integrity_constraints");
                 }
+                ast::SourceSection::TransitionExemptions(num_exemptions) => {
+                    if declared_exemptions.replace(num_exemptions).is_some() {
+                        return Err(SemanticError::duplicate_transition_exemptions_declaration());
+                    }
+                }
                 ast::SourceSection::EvaluatorFunction(eval_expr) => eval_exprs.push(eval_expr),
             }
         }
@@ -125,13 +157,33 @@ impl AirIR {
 
         let (declarations, constraints) = constraint_builder.into_air();
 
+        // the declared number of transition exemptions can only raise the required number.
+        let required_exemptions = constraints.min_transition_exemptions();
+        let num_transition_exemptions = match declared_exemptions {
+            Some(declared) if declared < required_exemptions => {
+                return Err(SemanticError::too_few_transition_exemptions(
+                    declared,
+                    required_exemptions,
+                ))
+            }
+            Some(declared) => declared,
+            None => required_exemptions,
+        };
+
         Ok(Self {
             air_name,
             declarations,
             constraints,
+            num_transition_exemptions,
         })
     }
 
+    /// Returns the number of transition exemptions of an AIR with no transition constraints
+    /// wider than two rows and no declared exemptions.
+    fn default_transition_exemptions() -> usize {
+        1
+    }
+
     // --- PUBLIC ACCESSORS FOR DECLARATIONS ------------------------------------------------------
 
     pub fn air_name(&self) -> &str {
@@ -146,6 +198,10 @@ impl AirIR {
         self.declarations.periodic_columns()
     }
 
+    pub fn periodic_column_names(&self) -> &[String] {
+        self.declarations.periodic_column_names()
+    }
+
     pub fn public_inputs(&self) -> &[PublicInput] {
         self.declarations.public_inputs()
     }
@@ -154,6 +210,22 @@ impl AirIR {
         self.declarations.trace_segment_widths()
     }
 
+    pub fn trace_bindings(&self) -> &[TraceBinding] {
+        self.declarations.trace_bindings()
+    }
+
+    pub fn num_random_values(&self) -> u16 {
+        self.declarations.num_random_values()
+    }
+
+    pub fn random_values_name(&self) -> Option<&str> {
+        self.declarations.random_values_name()
+    }
+
+    pub fn random_value_bindings(&self) -> &[RandomValueBinding] {
+        self.declarations.random_value_bindings()
+    }
+
     // --- PUBLIC ACCESSORS FOR BOUNDARY CONSTRAINTS ----------------------------------------------
 
     pub fn num_boundary_constraints(&self, trace_segment: u8) -> usize {
@@ -193,7 +265,41 @@ impl AirIR {
             .collect()
     }
 
+    /// Returns the number of rows at the end of the trace over which the transition constraints
+    /// are not enforced.
+    pub fn num_transition_exemptions(&self) -> usize {
+        self.num_transition_exemptions
+    }
+
     pub fn constraint_graph(&self) -> &AlgebraicGraph {
         self.constraints.graph()
     }
+
+    /// Returns the [AirLayout] of this AIR, which is computed from its declarations and
+    /// constraints.
+    pub fn layout(&self) -> AirLayout {
+        AirLayout::new(
+            self.trace_segment_widths(),
+            self.num_random_values(),
+            self.constraint_graph(),
+            &self.constraints.integrity_constraints,
+            &self.constraints.boundary_constraints,
+        )
+    }
+
+    /// Returns the position of the statement from which the constraint at the specified location
+    /// was built, among the constraint statements of the `boundary_constraints` or
+    /// `integrity_constraints` section of the source. This is `None` if the statements of the
+    /// constraints were not recorded, which is only the case if the [AirIR] was not built by
+    /// [AirIR::new].
+    pub fn constraint_statement(&self, location: &ConstraintLocation) -> Option<usize> {
+        self.constraints.statement(location)
+    }
+
+    /// Returns the span of the source of the statement from which the constraint at the specified
+    /// location was built. This is `None` if the statements of the constraints or their spans
+    /// were not recorded, which is only the case if the [AirIR] was not built by [AirIR::new].
+    pub fn constraint_span(&self, location: &ConstraintLocation) -> Option<SourceSpan> {
+        self.constraints.statement_span(location)
+    }
 }
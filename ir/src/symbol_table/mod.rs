@@ -77,8 +77,11 @@ impl SymbolTable {
             validate_cycles(&column)?;
 
             let (name, values) = column.into_parts();
-            self.insert_symbol(name, SymbolBinding::PeriodicColumn(index, values.len()))?;
-            self.declarations.add_periodic_column(values);
+            self.insert_symbol(
+                name.clone(),
+                SymbolBinding::PeriodicColumn(index, values.len()),
+            )?;
+            self.declarations.add_periodic_column(name, values);
         }
 
         Ok(())
@@ -113,14 +116,20 @@ impl SymbolTable {
         )?;
 
         // add the named random value bindings to the symbol table
+        let mut random_value_bindings = Vec::new();
         for binding in bindings {
-            let (name, size) = binding.into_parts();
-            self.insert_symbol(name, SymbolBinding::RandomValues(offset, size as usize))?;
+            let (binding_name, size) = binding.into_parts();
+            self.insert_symbol(
+                binding_name.clone(),
+                SymbolBinding::RandomValues(offset, size as usize),
+            )?;
+            random_value_bindings.push((binding_name, size as usize));
             offset += size as usize;
         }
 
         // TODO: check this type coercion
-        self.declarations.set_num_random_values(num_values as u16);
+        self.declarations
+            .set_random_values(name, num_values as u16, random_value_bindings);
 
         Ok(())
     }
@@ -134,6 +143,7 @@ impl SymbolTable {
             let mut width = 0;
             for binding in bindings {
                 width = binding.offset() + binding.size();
+                self.declarations.add_trace_binding(binding.clone());
                 self.insert_symbol(binding.name().to_string(), SymbolBinding::Trace(binding))?;
             }
 
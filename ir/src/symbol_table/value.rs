@@ -1,6 +1,7 @@
 use super::{SymbolAccess, TraceAccess};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value {
     /// A named constant with identifier and access indices.
     BoundConstant(SymbolAccess),
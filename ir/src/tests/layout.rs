@@ -0,0 +1,79 @@
+use super::{parse, AirIR};
+use crate::layout::{constant_elements, felt, AirLayout};
+use crate::{CodegenError, ConstantBinding, ConstantValueExpr, Identifier};
+
+#[test]
+fn constants_are_unrolled() {
+    let source = "
+    const A = 18446744069414584322
+    const B = [1, 2]
+    const C = [[3], [4]]
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = A
+    integrity_constraints:
+        enf clk' = clk + B[1] + C[1][0]";
+
+    let parsed = parse(source).expect("Parsing failed");
+    let ir = AirIR::new(parsed).expect("IR building failed");
+    let elements = constant_elements(ir.constants()).expect("unrolling failed");
+    let expected = [("A", 1), ("B_0", 1), ("B_1", 2), ("C_0_0", 3), ("C_1_0", 4)];
+    assert_eq!(elements.len(), expected.len());
+    for ((name, value), (expected_name, expected_value)) in elements.iter().zip(expected) {
+        assert_eq!(name, expected_name);
+        assert_eq!(*value, expected_value);
+    }
+    assert_eq!(felt(u64::MAX), 0xffff_fffe);
+}
+
+#[test]
+fn unrolled_constant_names_conflict() {
+    // the parser only accepts constant names made of uppercase letters, so the conflicting
+    // constants are built directly
+    let constants = [
+        ConstantBinding::new(Identifier("B_0".to_string()), ConstantValueExpr::Scalar(1)),
+        ConstantBinding::new(
+            Identifier("B".to_string()),
+            ConstantValueExpr::Vector(vec![1, 2]),
+        ),
+    ];
+    let err = constant_elements(&constants).unwrap_err();
+    assert!(matches!(err, CodegenError::NameConflict(name) if name == "B_0"));
+}
+
+#[test]
+fn layout_of_air() {
+    let source = "
+    trace_columns:
+        main: [a, b]
+        aux: [p]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf a.first = 0
+        enf p.first = 1
+        enf p.last = 1
+    integrity_constraints:
+        enf a' = a * a * a * b
+        enf b' = a + b
+        enf p' = p * $rand[0]";
+
+    let parsed = parse(source).expect("Parsing failed");
+    let ir = AirIR::new(parsed).expect("IR building failed");
+    let expected = AirLayout {
+        main_segment_width: 2,
+        aux_trace_width: 1,
+        num_aux_segments: 1,
+        num_random_values: 2,
+        num_transition_constraints: 3,
+        num_assertions: 3,
+        ce_blowup_factor: 4,
+        eval_frame_size: 2,
+    };
+    assert_eq!(ir.layout(), expected);
+}
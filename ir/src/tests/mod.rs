@@ -6,11 +6,14 @@ mod boundary_constraints;
 mod constant;
 mod evaluators;
 mod integrity_constraints;
+mod layout;
 mod list_comprehension;
 mod list_folding;
 mod pub_inputs;
 mod random_values;
 mod selectors;
+#[cfg(feature = "serde")]
+mod serde;
 mod source_sections;
 mod trace;
 mod variables;
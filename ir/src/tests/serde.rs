@@ -0,0 +1,70 @@
+use super::{parse, AirIR};
+use crate::schema::{SchemaError, Versioned, SCHEMA_VERSION};
+
+const SOURCE: &str = "
+    def SerdeAir
+
+    const A = [1, 2, 3]
+
+    trace_columns:
+        main: [a, b[2]]
+        aux: [c]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    periodic_columns:
+        k0: [1, 0, 0, 0]
+
+    random_values:
+        rand: [2]
+
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+        enf c.last = $rand[1]
+
+    integrity_constraints:
+        let x = a + A[1]
+        enf a' = x * k0
+        enf c' = c * $rand[0] + b[1]^2";
+
+#[test]
+fn air_ir_roundtrip() {
+    let parsed = parse(SOURCE).expect("Parsing failed");
+    let ir = AirIR::new(parsed).expect("AIR is invalid");
+
+    let serialized = serde_json::to_string(&Versioned::new(&ir)).unwrap();
+    let deserialized: Versioned<AirIR> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.version(), SCHEMA_VERSION);
+
+    let ir_copy = deserialized.into_inner().unwrap();
+    assert_eq!(ir_copy.air_name(), "SerdeAir");
+    assert_eq!(ir_copy.trace_segment_widths(), ir.trace_segment_widths());
+    assert_eq!(
+        serde_json::to_string(&ir_copy).unwrap(),
+        serde_json::to_string(&ir).unwrap()
+    );
+}
+
+#[test]
+fn ast_roundtrip() {
+    let parsed = parse(SOURCE).expect("Parsing failed");
+
+    let serialized = serde_json::to_string(&Versioned::new(&parsed)).unwrap();
+    let deserialized: Versioned<parser::ast::Source> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.into_inner().unwrap(), parsed);
+}
+
+#[test]
+fn err_unsupported_schema_version() {
+    let parsed = parse(SOURCE).expect("Parsing failed");
+    let ir = AirIR::new(parsed).expect("AIR is invalid");
+
+    let mut serialized = serde_json::to_value(Versioned::new(&ir)).unwrap();
+    serialized["version"] = (SCHEMA_VERSION + 1).into();
+    let deserialized: Versioned<AirIR> = serde_json::from_value(serialized).unwrap();
+    assert_eq!(
+        deserialized.into_inner().unwrap_err(),
+        SchemaError::UnsupportedVersion(SCHEMA_VERSION + 1)
+    );
+}
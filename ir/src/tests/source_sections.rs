@@ -115,3 +115,76 @@ fn err_ic_empty_or_omitted() {
     let result = AirIR::new(parsed);
     assert!(result.is_err());
 }
+
+#[test]
+fn transition_exemptions_from_constraint_domains() {
+    let source = "
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    let parsed = parse(source).expect("Parsing failed");
+    let ir = AirIR::new(parsed).expect("AIR is invalid");
+    assert_eq!(ir.num_transition_exemptions(), 1);
+}
+
+#[test]
+fn transition_exemptions_declared() {
+    let source = "
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    transition_exemptions: 3
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    let parsed = parse(source).expect("Parsing failed");
+    let ir = AirIR::new(parsed).expect("AIR is invalid");
+    assert_eq!(ir.num_transition_exemptions(), 3);
+}
+
+#[test]
+fn err_transition_exemptions_too_few() {
+    // a transition constraint against the next row cannot be enforced on the last row.
+    let source = "
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    transition_exemptions: 0
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    let parsed = parse(source).expect("Parsing failed");
+    let result = AirIR::new(parsed);
+    assert!(result.is_err());
+}
+
+#[test]
+fn err_transition_exemptions_declared_twice() {
+    let source = "
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    transition_exemptions: 2
+    transition_exemptions: 3
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    let parsed = parse(source).expect("Parsing failed");
+    let result = AirIR::new(parsed);
+    assert!(result.is_err());
+}
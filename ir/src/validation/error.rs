@@ -1,7 +1,8 @@
 use super::{
-    AccessType, ConstrainedBoundary, ConstraintDomain, Symbol, SymbolBinding, TraceAccess,
-    TraceSegment, MIN_CYCLE_LENGTH,
+    AccessType, ConstrainedBoundary, ConstraintDomain, SourceSpan, Symbol, SymbolBinding,
+    TraceAccess, TraceSegment, MIN_CYCLE_LENGTH,
 };
+use std::fmt::Display;
 
 #[derive(Debug)]
 pub enum SemanticError {
@@ -19,9 +20,30 @@ pub enum SemanticError {
     MissingDeclaration(String),
     OutOfScope(String),
     TooManyConstraints(String),
+    /// The error was found in the constraints section statement at the specified span of the
+    /// source.
+    InStatement(Box<SemanticError>, SourceSpan),
 }
 
 impl SemanticError {
+    /// Returns the span of the source of the statement in which the error was found. Only errors
+    /// found in the statements of the constraints sections are located.
+    pub fn span(&self) -> Option<SourceSpan> {
+        match self {
+            Self::InStatement(_, span) => Some(*span),
+            _ => None,
+        }
+    }
+
+    /// Attaches the span of the statement in which the error was found, unless the error already
+    /// has one.
+    pub(crate) fn in_statement(self, span: SourceSpan) -> Self {
+        match self {
+            Self::InStatement(..) => self,
+            err => Self::InStatement(Box::new(err), span),
+        }
+    }
+
     // --- DECLARATION ERRORS ---------------------------------------------------------------------
 
     fn missing_section_declaration(missing_section: &str) -> Self {
@@ -51,6 +73,12 @@ impl SemanticError {
         )
     }
 
+    pub(crate) fn duplicate_transition_exemptions_declaration() -> Self {
+        SemanticError::InvalidUsage(
+            "transition_exemptions section can only be declared once".to_string(),
+        )
+    }
+
     // --- ILLEGAL IDENTIFIER ERRORS --------------------------------------------------------------
 
     pub(crate) fn duplicate_identifer(
@@ -164,6 +192,12 @@ impl SemanticError {
         ))
     }
 
+    pub(crate) fn too_few_transition_exemptions(declared: usize, required: usize) -> Self {
+        SemanticError::InvalidConstraintDomain(format!(
+            "{declared} transition exemptions were declared, but the domains of the integrity constraints require at least {required}"
+        ))
+    }
+
     pub(crate) fn boundary_already_constrained(boundary: &ConstrainedBoundary) -> Self {
         SemanticError::TooManyConstraints(format!("A constraint was already defined at {boundary}"))
     }
@@ -191,3 +225,25 @@ impl SemanticError {
         ))
     }
 }
+
+impl Display for SemanticError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateIdentifier(msg)
+            | Self::IndexOutOfRange(msg)
+            | Self::InvalidConstant(msg)
+            | Self::InvalidConstraint(msg)
+            | Self::InvalidConstraintDomain(msg)
+            | Self::InvalidIdentifier(msg)
+            | Self::InvalidListComprehension(msg)
+            | Self::InvalidListFolding(msg)
+            | Self::InvalidPeriodicColumn(msg)
+            | Self::InvalidTraceSegment(msg)
+            | Self::InvalidUsage(msg)
+            | Self::MissingDeclaration(msg)
+            | Self::OutOfScope(msg)
+            | Self::TooManyConstraints(msg) => write!(f, "{msg}"),
+            Self::InStatement(err, _) => write!(f, "{err}"),
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use super::{
-    constraints::ConstraintDomain, AccessType, ConstrainedBoundary, Symbol, SymbolBinding,
-    TraceAccess, TraceSegment, MIN_CYCLE_LENGTH,
+    constraints::ConstraintDomain, AccessType, ConstrainedBoundary, SourceSpan, Symbol,
+    SymbolBinding, TraceAccess, TraceSegment, MIN_CYCLE_LENGTH,
 };
 
 mod error;
-pub(super) use error::SemanticError;
+pub use error::SemanticError;
 
 mod validator;
 pub(super) use validator::SourceValidator;
@@ -1,5 +1,6 @@
 use super::{
-    ComprehensionContext, Expression, Identifier, Iterable, SymbolAccess, VariableBinding,
+    ComprehensionContext, Expression, Identifier, Iterable, SourceSpan, SymbolAccess,
+    VariableBinding,
 };
 use std::fmt::Display;
 
@@ -7,18 +8,44 @@ use std::fmt::Display;
 // ================================================================================================
 
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BoundaryStmt {
     Constraint(BoundaryConstraint),
     ConstraintComprehension(BoundaryConstraint, ComprehensionContext),
     VariableBinding(VariableBinding),
 }
 
+impl BoundaryStmt {
+    /// Sets the span of the source from which the statement was parsed.
+    pub fn with_span(self, span: impl Into<SourceSpan>) -> Self {
+        match self {
+            Self::Constraint(constraint) => Self::Constraint(constraint.with_span(span)),
+            Self::ConstraintComprehension(constraint, context) => {
+                Self::ConstraintComprehension(constraint.with_span(span), context)
+            }
+            Self::VariableBinding(binding) => Self::VariableBinding(binding.with_span(span)),
+        }
+    }
+
+    /// Returns the span of the source from which the statement was parsed.
+    pub fn span(&self) -> SourceSpan {
+        match self {
+            Self::Constraint(constraint) | Self::ConstraintComprehension(constraint, _) => {
+                constraint.span()
+            }
+            Self::VariableBinding(binding) => binding.span(),
+        }
+    }
+}
+
 /// Stores the expression corresponding to the boundary constraint.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoundaryConstraint {
     access: SymbolAccess,
     boundary: Boundary,
     value: Expression,
+    span: SourceSpan,
 }
 
 impl BoundaryConstraint {
@@ -27,9 +54,21 @@ impl BoundaryConstraint {
             access,
             boundary,
             value,
+            span: SourceSpan::default(),
         }
     }
 
+    /// Sets the span of the source from which the constraint was parsed.
+    pub fn with_span(mut self, span: impl Into<SourceSpan>) -> Self {
+        self.span = span.into();
+        self
+    }
+
+    /// Returns the span of the source from which the constraint was parsed.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
     pub fn access(&self) -> &SymbolAccess {
         &self.access
     }
@@ -50,6 +89,7 @@ impl BoundaryConstraint {
 
 /// Describes the type of boundary in the boundary constraint.
 #[derive(Debug, Eq, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Boundary {
     First,
     Last,
@@ -65,6 +105,7 @@ impl Display for Boundary {
 }
 
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoundaryConstraintComprehension {
     access: SymbolAccess,
     boundary: Boundary,
@@ -1,9 +1,10 @@
-use super::{Identifier, IntegrityStmt, SymbolAccess, TraceBinding};
+use super::{Identifier, IntegrityStmt, SourceSpan, SymbolAccess, TraceBinding};
 
 /// Evaluator functions take a vector of trace bindings as parameters where each trace binding
 /// represents one or a group of columns in the execution trace that are passed to the evaluator
 /// function, and enforce integrity constraints on those trace columns.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EvaluatorFunction {
     name: Identifier,
     params: Vec<TraceBinding>,
@@ -49,15 +50,32 @@ impl EvaluatorFunction {
 /// of trace binding accesses as input, where each vector of trace binding accesses represents
 /// trace columns of that trace segment that are used as arguments to the evaluator function.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EvaluatorFunctionCall {
     name: Identifier,
     args: Vec<Vec<SymbolAccess>>,
+    span: SourceSpan,
 }
 
 impl EvaluatorFunctionCall {
     /// Creates a new evaluator function call.
     pub fn new(name: Identifier, args: Vec<Vec<SymbolAccess>>) -> Self {
-        Self { name, args }
+        Self {
+            name,
+            args,
+            span: SourceSpan::default(),
+        }
+    }
+
+    /// Sets the span of the source from which the evaluator function call was parsed.
+    pub fn with_span(mut self, span: impl Into<SourceSpan>) -> Self {
+        self.span = span.into();
+        self
+    }
+
+    /// Returns the span of the source from which the evaluator function call was parsed.
+    pub fn span(&self) -> SourceSpan {
+        self.span
     }
 
     /// Returns the name of the evaluator function.
@@ -1,34 +1,96 @@
 use air_script_core::ComprehensionContext;
 
-use super::{EvaluatorFunctionCall, Expression, VariableBinding};
+use super::{EvaluatorFunctionCall, Expression, SourceSpan, VariableBinding};
 
 // INTEGRITY STATEMENTS
 // ================================================================================================
 
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IntegrityStmt {
     Constraint(ConstraintType, Option<Expression>),
     ConstraintComprehension(ConstraintType, Option<Expression>, ComprehensionContext),
     VariableBinding(VariableBinding),
 }
 
+impl IntegrityStmt {
+    /// Sets the span of the source from which the statement was parsed.
+    pub fn with_span(self, span: impl Into<SourceSpan>) -> Self {
+        match self {
+            Self::Constraint(constraint, selectors) => {
+                Self::Constraint(constraint.with_span(span), selectors)
+            }
+            Self::ConstraintComprehension(constraint, selectors, context) => {
+                Self::ConstraintComprehension(constraint.with_span(span), selectors, context)
+            }
+            Self::VariableBinding(binding) => Self::VariableBinding(binding.with_span(span)),
+        }
+    }
+
+    /// Returns the span of the source from which the statement was parsed.
+    pub fn span(&self) -> SourceSpan {
+        match self {
+            Self::Constraint(constraint, _) | Self::ConstraintComprehension(constraint, _, _) => {
+                constraint.span()
+            }
+            Self::VariableBinding(binding) => binding.span(),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConstraintType {
     Inline(IntegrityConstraint),
     Evaluator(EvaluatorFunctionCall),
 }
 
+impl ConstraintType {
+    /// Sets the span of the source from which the constraint was parsed.
+    pub fn with_span(self, span: impl Into<SourceSpan>) -> Self {
+        match self {
+            Self::Inline(constraint) => Self::Inline(constraint.with_span(span)),
+            Self::Evaluator(call) => Self::Evaluator(call.with_span(span)),
+        }
+    }
+
+    /// Returns the span of the source from which the constraint was parsed.
+    pub fn span(&self) -> SourceSpan {
+        match self {
+            Self::Inline(constraint) => constraint.span(),
+            Self::Evaluator(call) => call.span(),
+        }
+    }
+}
+
 /// Stores the expression corresponding to the integrity constraint.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IntegrityConstraint {
     lhs: Expression,
     rhs: Expression,
+    span: SourceSpan,
 }
 
 impl IntegrityConstraint {
     /// Creates a new integrity constraint.
     pub fn new(lhs: Expression, rhs: Expression) -> Self {
-        Self { lhs, rhs }
+        Self {
+            lhs,
+            rhs,
+            span: SourceSpan::default(),
+        }
+    }
+
+    /// Sets the span of the source from which the constraint was parsed.
+    pub fn with_span(mut self, span: impl Into<SourceSpan>) -> Self {
+        self.span = span.into();
+        self
+    }
+
+    /// Returns the span of the source from which the constraint was parsed.
+    pub fn span(&self) -> SourceSpan {
+        self.span
     }
 
     /// Returns the left-hand side of the integrity constraint.
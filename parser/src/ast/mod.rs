@@ -1,7 +1,7 @@
 pub(crate) use air_script_core::{
     AccessType, ComprehensionContext, ConstantBinding, ConstantValueExpr, Expression, Identifier,
-    Iterable, ListComprehension, ListFolding, ListFoldingValueExpr, Range, SymbolAccess,
-    TraceAccess, TraceBinding, TraceSegment, VariableBinding, VariableValueExpr,
+    Iterable, ListComprehension, ListFolding, ListFoldingValueExpr, Range, SourceSpan,
+    SymbolAccess, TraceAccess, TraceBinding, TraceSegment, VariableBinding, VariableValueExpr,
 };
 
 // declaration modules
@@ -29,6 +29,7 @@ pub use integrity_constraints::*;
 
 /// [Source] is the root node of the AST representing the AIR constraints file.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Source(pub Vec<SourceSection>);
 
 /// Source is divided into SourceSections. Each source section is responsible for declarations of a
@@ -49,6 +50,9 @@ pub struct Source(pub Vec<SourceSection>);
 /// - RandomValues: Random Values represent the randomness sent by the Verifier.
 /// - Trace: A vector of trace segments, each containing a vector of trace bindings, which bind an
 ///   identifier to one or more columns in the execution trace.
+/// - TransitionExemptions: The number of rows at the end of the trace over which the transition
+///   constraints are not enforced. It cannot be smaller than the number of rows required by the
+///   domains of the integrity constraints, but this is not checked during parsing.
 ///
 /// The constraint definition sections are:
 /// - BoundaryConstraints: Boundary Constraints to be enforced on the boundaries of columns defined
@@ -57,6 +61,7 @@ pub struct Source(pub Vec<SourceSection>);
 /// - IntegrityConstraints: Integrity Constraints to be enforced on the trace columns defined
 ///   in the TraceCols section.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SourceSection {
     // AIR name definition
     AirDef(Identifier),
@@ -68,6 +73,7 @@ pub enum SourceSection {
     PublicInputs(Vec<PublicInput>),
     RandomValues(RandomValues),
     Trace(Vec<Vec<TraceBinding>>),
+    TransitionExemptions(usize),
 
     // constraint definitions
     BoundaryConstraints(Vec<BoundaryStmt>),
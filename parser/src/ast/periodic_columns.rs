@@ -8,6 +8,7 @@ use super::Identifier;
 /// be repeated. The length of the values vector is expected to be a power of 2 with a minimum
 /// length of 2, but this is not enforced here.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PeriodicColumn {
     name: Identifier,
     values: Vec<u64>,
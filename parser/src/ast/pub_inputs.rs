@@ -6,6 +6,7 @@ use super::Identifier;
 /// Declaration of a public input for an AIR. Public inputs are represented by a named identifier
 /// which is used to identify a fixed size array of length `size`.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PublicInput {
     name: Identifier,
     size: usize,
@@ -32,6 +32,7 @@ use super::Identifier;
 ///
 /// `RandomValues { name: "rand", size: 13, bindings: [ RandBinding { name: "a", size: 1 }, RandBinding { name: "b", size: 12 } ] }`
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RandomValues {
     name: Identifier,
     size: u64,
@@ -68,6 +69,7 @@ impl RandomValues {
 /// Declaration of a random value binding used in [RandomValues]. It is represented by a named
 /// identifier and its size.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RandBinding {
     name: Identifier,
     size: u64,
@@ -0,0 +1,145 @@
+use super::{Error, ParseError, SourceError, Span};
+use std::fmt::Write;
+
+// DIAGNOSTIC
+// ================================================================================================
+
+/// A description of a problem with an AirScript source file, optionally pointing at the span of
+/// the source which caused it. A [Diagnostic] can be rendered against the source it refers to,
+/// which shows the offending line of source with the location of the problem underlined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    message: String,
+    span: Option<Span>,
+}
+
+impl Diagnostic {
+    /// Returns a new [Diagnostic] with the specified message and source span.
+    pub fn new(message: impl Into<String>, span: Option<Span>) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// Returns the message describing the problem.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the span of the source which caused the problem, if it is known.
+    pub fn span(&self) -> Option<&Span> {
+        self.span.as_ref()
+    }
+
+    /// Returns the 1-based line and column at which the span of this diagnostic starts within the
+    /// provided source, if the span is known.
+    pub fn line_col(&self, source: &str) -> Option<(usize, usize)> {
+        let start = self.span.as_ref()?.start.min(source.len());
+        let preceding = &source[..start];
+        let line = preceding.matches('\n').count() + 1;
+        let line_start = preceding.rfind('\n').map_or(0, |idx| idx + 1);
+        let col = source[line_start..start].chars().count() + 1;
+        Some((line, col))
+    }
+
+    /// Renders this diagnostic against the provided source, where `file_name` is the name used
+    /// to refer to the source file. For example:
+    ///
+    /// ```text
+    /// error: invalid character
+    ///  --> example.air:3:13
+    ///   |
+    /// 3 |     main: [a@]
+    ///   |             ^
+    /// ```
+    pub fn render(&self, file_name: &str, source: &str) -> String {
+        let mut result = format!("error: {}\n", self.message);
+
+        let (line, col) = match self.line_col(source) {
+            Some(line_col) => line_col,
+            None => {
+                let _ = writeln!(result, " --> {file_name}");
+                return result;
+            }
+        };
+
+        let line_text = source.lines().nth(line - 1).unwrap_or_default();
+        let span = self
+            .span
+            .as_ref()
+            .expect("span exists if line_col is known");
+        let underline_len = source[span.start.min(source.len())..span.end.min(source.len())]
+            .lines()
+            .next()
+            .map_or(1, |text| text.chars().count().max(1));
+
+        let gutter = " ".repeat(line.to_string().len());
+        let _ = writeln!(result, "{gutter}--> {file_name}:{line}:{col}");
+        let _ = writeln!(result, "{gutter} |");
+        let _ = writeln!(result, "{line} | {line_text}");
+        let _ = writeln!(
+            result,
+            "{gutter} | {}{}",
+            " ".repeat(col - 1),
+            "^".repeat(underline_len)
+        );
+        result
+    }
+}
+
+impl From<&SourceError> for Diagnostic {
+    fn from(err: &SourceError) -> Self {
+        match err {
+            SourceError::InvalidToken { location } => {
+                Diagnostic::new("invalid token", Some(*location..*location + 1))
+            }
+            SourceError::UnrecognizedEOF { location, expected } => Diagnostic::new(
+                format!("unexpected end of file{}", format_expected(expected)),
+                Some(*location..*location),
+            ),
+            SourceError::UnrecognizedToken {
+                token: (start, token, end),
+                expected,
+            } => Diagnostic::new(
+                format!("unexpected token {token:?}{}", format_expected(expected)),
+                Some(*start..*end),
+            ),
+            SourceError::ExtraToken {
+                token: (start, token, end),
+            } => Diagnostic::new(format!("extra token {token:?}"), Some(*start..*end)),
+            SourceError::User { error } => match error {
+                Error::ScanError(span) => Diagnostic::new("invalid character", Some(span.clone())),
+                Error::ParseError(err) => Diagnostic::new(parse_error_message(err), None),
+            },
+        }
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns a description of the tokens which were expected by the parser.
+fn format_expected(expected: &[String]) -> String {
+    match expected {
+        [] => String::new(),
+        [token] => format!(", expected {token}"),
+        _ => format!(", expected one of {}", expected.join(", ")),
+    }
+}
+
+/// Returns the message contained in the provided [ParseError].
+fn parse_error_message(err: &ParseError) -> String {
+    match err {
+        ParseError::InvalidConst(msg)
+        | ParseError::InvalidEvaluatorFunction(msg)
+        | ParseError::InvalidInt(msg)
+        | ParseError::InvalidConstraintComprehension(msg)
+        | ParseError::InvalidListComprehension(msg)
+        | ParseError::InvalidRandomValues(msg)
+        | ParseError::InvalidTraceCols(msg)
+        | ParseError::MissingBoundaryConstraint(msg)
+        | ParseError::MissingIntegrityConstraint(msg)
+        | ParseError::MissingMainTraceCols(msg) => msg.clone(),
+    }
+}
@@ -67,6 +67,10 @@ pub enum Token {
     #[token("random_values")]
     RandomValues,
 
+    /// Keyword to declare the number of transition exemptions of the AIR.
+    #[token("transition_exemptions")]
+    TransitionExemptions,
+
     /// A reserved symbol for accessing random values provided by the verifier.
     #[token("$")]
     Rand,
@@ -3,17 +3,24 @@ extern crate lalrpop_util;
 
 pub mod ast;
 
+mod diagnostic;
+pub use diagnostic::Diagnostic;
+
 mod error;
-use error::Error;
+pub use error::{Error, ParseError};
 
 mod lexer;
-use lexer::{Lexer, Token};
+use lexer::Lexer;
+pub use lexer::{Span, Token};
 
 mod parser;
 use crate::parser::SourceParser;
 
+/// The error returned by [parse] when the provided source is not valid AirScript.
+pub type SourceError = lalrpop_util::ParseError<usize, Token, Error>;
+
 /// Parses the provided source and returns the AST.
-pub fn parse(source: &str) -> Result<ast::Source, lalrpop_util::ParseError<usize, Token, Error>> {
+pub fn parse(source: &str) -> Result<ast::Source, SourceError> {
     let lex = Lexer::new(source).spanned().map(Token::to_spanned);
     SourceParser::new().parse(lex)
 }
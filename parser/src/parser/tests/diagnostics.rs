@@ -0,0 +1,46 @@
+use crate::{parse, Diagnostic};
+
+// DIAGNOSTICS
+// ================================================================================================
+
+#[test]
+fn unrecognized_token_location() {
+    let source = "
+def Example
+trace_columns:
+    main: [a]
+integrity_constraints:
+    enf a' == a";
+    let err = parse(source).expect_err("Parsing should fail");
+    let diagnostic = Diagnostic::from(&err);
+
+    assert_eq!(diagnostic.line_col(source), Some((6, 13)));
+    assert_eq!(
+        diagnostic.render("example.air", source),
+        format!(
+            "error: {}\n --> example.air:6:13\n  |\n6 |     enf a' == a\n  |             ^\n",
+            diagnostic.message()
+        )
+    );
+}
+
+#[test]
+fn scan_error_location() {
+    let source = "def Example\ntrace_columns:\n    main: [a@]";
+    let err = parse(source).expect_err("Parsing should fail");
+    let diagnostic = Diagnostic::from(&err);
+
+    assert_eq!(diagnostic.message(), "invalid character");
+    assert_eq!(diagnostic.line_col(source), Some((3, 13)));
+}
+
+#[test]
+fn diagnostic_without_span() {
+    let diagnostic = Diagnostic::new("public_inputs section is missing", None);
+
+    assert_eq!(diagnostic.line_col("def Example"), None);
+    assert_eq!(
+        diagnostic.render("example.air", "def Example"),
+        "error: public_inputs section is missing\n --> example.air\n"
+    );
+}
@@ -1,4 +1,4 @@
-use super::build_parse_test;
+use super::{build_parse_test, Source, SourceSection::*};
 
 // SECTIONS
 // ================================================================================================
@@ -9,3 +9,20 @@ fn error_constraint_without_section() {
     let source = "enf clk' = clk + 1";
     build_parse_test!(source).expect_unrecognized_token();
 }
+
+#[test]
+fn transition_exemptions() {
+    let source = "
+transition_exemptions: 2";
+    let expected = Source(vec![TransitionExemptions(2)]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn error_transition_exemptions_without_value() {
+    let source = "
+transition_exemptions:
+integrity_constraints:
+    enf clk' = clk + 1";
+    build_parse_test!(source).expect_unrecognized_token();
+}